@@ -1,20 +1,26 @@
 use crate::collision_detection::hazards::HazKey;
 use crate::collision_detection::hazards::Hazard;
 use crate::collision_detection::hazards::HazardEntity;
-use crate::collision_detection::hazards::collector::HazardCollector;
-use crate::collision_detection::hazards::filter::HazardFilter;
+use crate::collision_detection::hazards::collector::{BasicHazardCollector, HazardCollector};
+use crate::collision_detection::hazards::filter::{EntityFilter, HazardFilter};
 use crate::collision_detection::quadtree::{QTHazPresence, QTHazard, QTNode};
-use crate::entities::PItemKey;
-use crate::geometry::Transformation;
+#[cfg(feature = "cde_stats")]
+use crate::collision_detection::stats::{CDEStats, EngineStats};
+use crate::collision_detection::{
+    CDQueryable, CollisionWitness, WitnessKind, record_stat, stats_scope,
+};
+use crate::entities::{GrainZone, ItemQualityRegion, PItemKey};
 use crate::geometry::fail_fast::{SPSurrogate, SPSurrogateConfig};
 use crate::geometry::geo_enums::{GeoPosition, GeoRelation};
-use crate::geometry::geo_traits::{CollidesWith, Transformable};
-use crate::geometry::primitives::Rect;
+use crate::geometry::geo_traits::{CollidesWith, DistanceTo, Transformable};
 use crate::geometry::primitives::SPolygon;
+use crate::geometry::primitives::{Circle, Edge, Point, Rect};
+use crate::geometry::{Transformation, normalize_rotation};
 use crate::util::assertions;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use slotmap::SlotMap;
+use std::f32::consts::PI;
 
 /// The Collision Detection Engine (CDE).
 /// [`Hazard`]s can be (de)registered and collision queries can be performed.
@@ -28,10 +34,20 @@ pub struct CDEngine {
     pub config: CDEConfig,
     /// The key of the hazard that represents the exterior of the container.
     hkey_exterior: HazKey,
+    /// Zones restricting the rotations of grain sensitive items, not registered as hazards
+    grain_zones: Vec<GrainZone>,
+    /// Instrumentation counters of this engine
+    #[cfg(feature = "cde_stats")]
+    stats: EngineStats,
 }
 
 impl CDEngine {
-    pub fn new(bbox: Rect, static_hazards: Vec<Hazard>, config: CDEConfig) -> CDEngine {
+    pub fn new(
+        bbox: Rect,
+        static_hazards: Vec<Hazard>,
+        grain_zones: Vec<GrainZone>,
+        config: CDEConfig,
+    ) -> CDEngine {
         let mut quadtree = QTNode::new(config.quadtree_depth, bbox, config.cd_threshold);
         let mut hazards_map = SlotMap::with_key();
 
@@ -52,6 +68,9 @@ impl CDEngine {
             hazards_map,
             config,
             hkey_exterior,
+            grain_zones,
+            #[cfg(feature = "cde_stats")]
+            stats: EngineStats::default(),
         }
     }
 
@@ -61,6 +80,8 @@ impl CDEngine {
             !self.hazards_map.values().any(|h| h.entity == hazard.entity),
             "Hazard with an identical entity already registered"
         );
+        stats_scope!(self);
+        record_stat!(n_registrations);
         let hkey = self.hazards_map.insert(hazard);
         let qt_hazard = QTHazard::from_root(self.bbox(), &self.hazards_map[hkey], hkey);
         self.quadtree.register_hazard(qt_hazard, &self.hazards_map);
@@ -77,6 +98,8 @@ impl CDEngine {
             .map(|(hkey, _)| hkey)
             .expect("Cannot deregister hazard that is not registered");
 
+        stats_scope!(self);
+        record_stat!(n_deregistrations);
         self.quadtree.deregister_hazard(hkey);
         let hazard = self.hazards_map.remove(hkey).unwrap();
         debug_assert!(assertions::qt_contains_no_dangling_hazards(self));
@@ -89,6 +112,8 @@ impl CDEngine {
             .hazards_map
             .remove(hkey)
             .expect("Cannot deregister hazard that is not registered");
+        stats_scope!(self);
+        record_stat!(n_deregistrations);
         self.quadtree.deregister_hazard(hkey);
         debug_assert!(assertions::qt_contains_no_dangling_hazards(self));
 
@@ -158,8 +183,11 @@ impl CDEngine {
     /// * `shape` - The shape (already transformed) to be checked for collisions
     /// * `filter` - Hazard filter to be applied
     pub fn detect_poly_collision(&self, shape: &SPolygon, filter: &impl HazardFilter) -> bool {
+        stats_scope!(self);
+        record_stat!(n_queries);
         if self.bbox().relation_to(shape.bbox) != GeoRelation::Surrounding {
             //The CDE does not capture the entire shape, so we can immediately return true
+            record_stat!(n_resolved_by_bbox);
             true
         } else {
             //Instead of each time starting from the quadtree root, we can use the virtual root (lowest level node which fully surrounds the shape)
@@ -168,6 +196,7 @@ impl CDEngine {
             // Check for edge intersections with the shape
             for edge in shape.edge_iter() {
                 if v_qt_root.collides(&edge, filter).is_some() {
+                    record_stat!(n_resolved_by_poly);
                     return true;
                 }
             }
@@ -180,11 +209,12 @@ impl CDEngine {
                         "Entire hazards in the virtual root should have been caught by the edge intersection tests"
                     ),
                     QTHazPresence::Partial(_) => {
-                        if !filter.is_irrelevant(qt_hazard.hkey) {
+                        if !filter.is_irrelevant(qt_hazard.hkey, &qt_hazard.entity) {
                             let haz_shape = &self.hazards_map[qt_hazard.hkey].shape;
                             if self.detect_containment_collision(shape, haz_shape, qt_hazard.entity)
                             {
                                 // The hazard is contained in the shape (or vice versa)
+                                record_stat!(n_resolved_by_poly);
                                 return true;
                             }
                         }
@@ -207,15 +237,19 @@ impl CDEngine {
         transform: &Transformation,
         filter: &impl HazardFilter,
     ) -> bool {
+        stats_scope!(self);
+        record_stat!(n_queries);
         for pole in base_surrogate.ff_poles() {
             let t_pole = pole.transform_clone(transform);
             if self.quadtree.collides(&t_pole, filter).is_some() {
+                record_stat!(n_resolved_by_poles);
                 return true;
             }
         }
         for pier in base_surrogate.ff_piers() {
             let t_pier = pier.transform_clone(transform);
             if self.quadtree.collides(&t_pier, filter).is_some() {
+                record_stat!(n_resolved_by_piers);
                 return true;
             }
         }
@@ -259,6 +293,8 @@ impl CDEngine {
     /// * `shape` - The shape to be checked for collisions
     /// * `collector` - The collector to which the hazards are reported
     pub fn collect_poly_collisions(&self, shape: &SPolygon, collector: &mut impl HazardCollector) {
+        stats_scope!(self);
+        record_stat!(n_queries);
         if self.bbox().relation_to(shape.bbox) != GeoRelation::Surrounding {
             collector.insert(self.hkey_exterior, HazardEntity::Exterior);
         }
@@ -299,6 +335,8 @@ impl CDEngine {
         transform: &Transformation,
         collector: &mut impl HazardCollector,
     ) {
+        stats_scope!(self);
+        record_stat!(n_queries);
         for pole in base_surrogate.ff_poles() {
             let t_pole = pole.transform_clone(transform);
             self.quadtree.collect_collisions(&t_pole, collector)
@@ -309,6 +347,305 @@ impl CDEngine {
         }
     }
 
+    /// Collects a witness for every collision of the polygon with a (relevant) hazard.
+    /// For each intersecting pair of edges, the intersection point is reported.
+    /// If a hazard collides without any intersecting edges (full inclusion), a single point inside the overlap is reported.
+    /// Intended for diagnostics: this is considerably slower than [`CDEngine::detect_poly_collision`].
+    /// # Arguments
+    /// * `shape` - The shape to be checked for collisions
+    /// * `filter` - Hazard filter to be applied
+    pub fn collect_poly_collision_witnesses(
+        &self,
+        shape: &SPolygon,
+        filter: &impl HazardFilter,
+    ) -> Vec<CollisionWitness> {
+        let mut colliding = BasicHazardCollector::with_capacity(self.hazards_map.len());
+        self.collect_poly_collisions(shape, &mut colliding);
+
+        let mut witnesses = vec![];
+        for (hkey, entity) in colliding.iter() {
+            if filter.is_irrelevant(hkey, entity) {
+                continue;
+            }
+            let haz_shape = &self.hazards_map[hkey].shape;
+            let n_witnesses = witnesses.len();
+
+            for shape_edge in shape.edge_iter() {
+                if !shape_edge.bbox().collides_with(&haz_shape.bbox)
+                    && entity.scope() == GeoPosition::Interior
+                {
+                    continue;
+                }
+                for haz_edge in haz_shape.edge_iter() {
+                    if let Some(point) = shape_edge.collides_at(&haz_edge) {
+                        witnesses.push(CollisionWitness {
+                            hkey,
+                            entity: *entity,
+                            kind: WitnessKind::EdgeIntersection {
+                                shape_edge,
+                                haz_edge,
+                                point,
+                            },
+                        });
+                    }
+                }
+            }
+
+            if witnesses.len() == n_witnesses {
+                //no edges intersect, so one of the shapes is fully included in the other
+                let point = match (
+                    entity.scope(),
+                    haz_shape.bbox.almost_relation_to(shape.bbox),
+                ) {
+                    (GeoPosition::Interior, GeoRelation::Enclosed) => haz_shape.poi.center,
+                    _ => shape.poi.center,
+                };
+                witnesses.push(CollisionWitness {
+                    hkey,
+                    entity: *entity,
+                    kind: WitnessKind::Containment { point },
+                });
+            }
+        }
+        witnesses
+    }
+
+    /// Checks whether any of the quality regions of an item, placed using `transform`,
+    /// collides with an inferior quality zone below the quality required by that region.
+    /// All other hazards are ignored, these should be checked using the full shape of the item.
+    /// Like any other query, regions extending beyond the bounds of the CDE are always reported as colliding.
+    pub fn detect_quality_region_collision(
+        &self,
+        regions: &[ItemQualityRegion],
+        transform: &Transformation,
+    ) -> bool {
+        regions.iter().any(|region| {
+            let filter = EntityFilter(|e: &HazardEntity| match e {
+                HazardEntity::InferiorQualityZone { quality, .. } => {
+                    region.min_quality.is_some_and(|q| *quality >= q)
+                }
+                _ => true,
+            });
+            let shape = region.shape_cd.transform_clone(transform);
+            self.detect_poly_collision(&shape, &filter)
+        })
+    }
+
+    /// Checks whether a shape, placed with `rotation` (in radians), overlaps any of the [`GrainZone`]s which do not allow that rotation.
+    /// Only relevant for [grain sensitive](crate::entities::Item::grain_sensitive) items.
+    pub fn detect_grain_collision(&self, shape: &SPolygon, rotation: f32) -> bool {
+        self.grain_zones
+            .iter()
+            .filter(|gz| !gz.allowed_rotation.contains(rotation))
+            .any(|gz| {
+                let gz_shape = &gz.shape_cd;
+                if !gz_shape.bbox.collides_with(&shape.bbox) {
+                    return false;
+                }
+                let edges_intersect = shape
+                    .edge_iter()
+                    .any(|e| gz_shape.edge_iter().any(|gz_e| e.collides_with(&gz_e)));
+                //no intersecting edges: the shapes only overlap if one is contained in the other
+                edges_intersect
+                    || gz_shape.collides_with(&shape.poi.center)
+                    || shape.collides_with(&gz_shape.poi.center)
+            })
+    }
+
+    /// Checks whether a geometric primitive ([Rect], [Circle] or [Point]) collides with any of the (relevant) hazards.
+    /// Collisions caused by containment (of the primitive in a hazard or vice versa) are also detected.
+    /// # Arguments
+    /// * `entity` - The primitive to be checked for collisions
+    /// * `filter` - Hazard filter to be applied
+    pub fn detect_collision<T: CDQueryable>(&self, entity: &T, filter: &impl HazardFilter) -> bool {
+        stats_scope!(self);
+        record_stat!(n_queries);
+        if self.bbox().relation_to(entity.bbox()) != GeoRelation::Surrounding {
+            //The CDE does not capture the entire primitive, so we can immediately return true
+            record_stat!(n_resolved_by_bbox);
+            return true;
+        }
+        let v_qt_root = self.get_virtual_root(entity.bbox());
+
+        //Check for edge intersections with the primitive (and hazards entirely covering it)
+        if v_qt_root.collides(entity, filter).is_some() {
+            return true;
+        }
+
+        //Check for containment of the primitive in any of the hazards (or vice versa)
+        v_qt_root
+            .hazards
+            .iter()
+            .any(|qt_haz| match &qt_haz.presence {
+                QTHazPresence::None | QTHazPresence::Entire => false,
+                QTHazPresence::Partial(_) => {
+                    !filter.is_irrelevant(qt_haz.hkey, &qt_haz.entity)
+                        && self.detect_primitive_containment_collision(entity, qt_haz)
+                }
+            })
+    }
+
+    /// Collects all hazards with which a geometric primitive ([Rect], [Circle] or [Point]) collides and reports them to the collector.
+    /// For a [Point], this amounts to all hazards containing the point.
+    /// # Arguments
+    /// * `entity` - The primitive to be checked for collisions
+    /// * `collector` - The collector to which the hazards are reported
+    pub fn collect_collisions<T: CDQueryable>(
+        &self,
+        entity: &T,
+        collector: &mut impl HazardCollector,
+    ) {
+        stats_scope!(self);
+        record_stat!(n_queries);
+        if self.bbox().relation_to(entity.bbox()) != GeoRelation::Surrounding {
+            collector.insert(self.hkey_exterior, HazardEntity::Exterior);
+        }
+        let v_qt_root = self.get_virtual_root(entity.bbox());
+
+        //Collect all colliding entities due to edge intersection
+        v_qt_root.collect_collisions(entity, collector);
+
+        //Check if there are any other collisions due to containment
+        for qt_haz in v_qt_root.hazards.iter() {
+            if let QTHazPresence::Partial(_) = &qt_haz.presence
+                && !collector.contains_key(qt_haz.hkey)
+                && self.detect_primitive_containment_collision(entity, qt_haz)
+            {
+                collector.insert(qt_haz.hkey, qt_haz.entity);
+            }
+        }
+    }
+
+    /// Check for collision by containment between a geometric primitive and a hazard.
+    fn detect_primitive_containment_collision<T: CDQueryable>(
+        &self,
+        entity: &T,
+        qt_haz: &QTHazard,
+    ) -> bool {
+        let haz_shape = &self.hazards_map[qt_haz.hkey].shape;
+        let contained = match haz_shape.bbox.almost_relation_to(entity.bbox()) {
+            GeoRelation::Surrounding => haz_shape.collides_with(&entity.interior_point()),
+            GeoRelation::Enclosed => entity.contains(&haz_shape.poi.center),
+            GeoRelation::Disjoint | GeoRelation::Intersecting => false,
+        };
+
+        match (qt_haz.entity.scope(), contained) {
+            (GeoPosition::Interior, true) | (GeoPosition::Exterior, false) => true,
+            (GeoPosition::Interior, false) | (GeoPosition::Exterior, true) => false,
+        }
+    }
+
+    /// Computes the interval of rotations around `pivot` for which `shape` remains free of collisions
+    /// with all (relevant) hazards, using the contact events between vertices and edges.
+    /// Returns `None` if `shape` is already colliding in its current position.
+    /// # Arguments
+    /// * `shape` - The shape (already transformed) to be rotated
+    /// * `pivot` - The point around which the shape is rotated
+    /// * `filter` - Hazard filter to be applied
+    ///
+    /// The returned `(lower, upper)` angles (in radians) are relative to the current orientation of `shape`,
+    /// with `lower <= 0.0 <= upper`. Counterclockwise rotations are positive.
+    /// At both bounds the shape is in contact with a hazard, so callers might want to stay slightly within the interval.
+    /// If no contact can occur, `(-π, π)` is returned.
+    pub fn feasible_rotation_interval(
+        &self,
+        shape: &SPolygon,
+        pivot: Point,
+        filter: &impl HazardFilter,
+    ) -> Option<(f32, f32)> {
+        if self.detect_poly_collision(shape, filter) {
+            return None;
+        }
+
+        //the disk swept by the shape while rotating around the pivot
+        let swept_disk = {
+            let radius = shape
+                .vertices
+                .iter()
+                .map(|v| v.sq_distance_to(&pivot))
+                .fold(0.0, f32::max)
+                .sqrt();
+            Circle::try_new(pivot, radius).unwrap()
+        };
+
+        //collect all hazards which are (partially) within the swept disk
+        let mut candidates = BasicHazardCollector::with_capacity(self.hazards_map.len());
+        if self.bbox().relation_to(swept_disk.bbox()) != GeoRelation::Surrounding {
+            candidates.insert(self.hkey_exterior, HazardEntity::Exterior);
+        }
+        self.quadtree
+            .collect_collisions(&swept_disk, &mut candidates);
+
+        // (lower, upper)
+        let mut interval = (-PI, PI);
+        let mut register_contact = |angle: f32, entering_ccw: bool| {
+            let angle = normalize_rotation(angle);
+            if angle < ROT_CONTACT_TOLERANCE || angle > 2.0 * PI - ROT_CONTACT_TOLERANCE {
+                //already in contact, only the direction in which the shapes move into each other is blocked
+                match entering_ccw {
+                    true => interval.1 = 0.0,
+                    false => interval.0 = 0.0,
+                }
+            } else {
+                interval.1 = f32::min(interval.1, angle);
+                interval.0 = f32::max(interval.0, angle - 2.0 * PI);
+            }
+        };
+
+        for (hkey, entity) in candidates.iter() {
+            if filter.is_irrelevant(hkey, entity) {
+                continue;
+            }
+            let haz_shape = &self.hazards_map[hkey].shape;
+            let haz_scope = entity.scope();
+
+            for haz_edge in haz_shape.edge_iter() {
+                if !swept_disk.collides_with(&haz_edge) {
+                    continue;
+                }
+                //vertices of the shape coming into contact with the edges of the hazard
+                for v in shape.vertices.iter() {
+                    for angle in haz_edge
+                        .rotational_contact_angles(*v, pivot)
+                        .into_iter()
+                        .flatten()
+                    {
+                        let contact = v.transform_clone(&rotation_around(pivot, angle));
+                        //direction in which the contact point moves when rotating counterclockwise
+                        let motion = (pivot.1 - contact.1, contact.0 - pivot.0);
+                        //hazards are oriented counterclockwise, the interior is on the left side of each edge
+                        let moves_left = cross(&haz_edge, motion) > 0.0;
+                        let entering_ccw = match haz_scope {
+                            GeoPosition::Interior => moves_left,
+                            GeoPosition::Exterior => !moves_left,
+                        };
+                        register_contact(angle, entering_ccw);
+                    }
+                }
+                //vertices of the hazard coming into contact with the edges of the shape
+                let h = haz_edge.start;
+                if swept_disk.collides_with(&h) {
+                    for edge in shape.edge_iter() {
+                        //rotating the shape counterclockwise is equivalent to rotating the hazard clockwise
+                        for angle in edge
+                            .rotational_contact_angles(h, pivot)
+                            .into_iter()
+                            .flatten()
+                        {
+                            let contact = h.transform_clone(&rotation_around(pivot, angle));
+                            let motion = (contact.1 - pivot.1, pivot.0 - contact.0);
+                            let entering_ccw = cross(&edge, motion) > 0.0;
+                            register_contact(-angle, entering_ccw);
+                        }
+                    }
+                }
+            }
+        }
+
+        debug_assert!(interval.0 <= 0.0 && interval.1 >= 0.0);
+        Some(interval)
+    }
+
     /// Returns the lowest `QTNode` that completely surrounds the given bounding box.
     /// Used to initiate collision checks from lower in the quadtree.
     pub fn get_virtual_root(&self, bbox: Rect) -> &QTNode {
@@ -330,6 +667,20 @@ impl CDEngine {
         self.quadtree.bbox
     }
 
+    /// Returns the instrumentation counters of this engine, since its creation or the last [`CDEngine::take_stats`].
+    /// Only available with the `cde_stats` feature enabled.
+    #[cfg(feature = "cde_stats")]
+    pub fn stats(&self) -> CDEStats {
+        self.stats.get()
+    }
+
+    /// Returns the instrumentation counters of this engine and resets them.
+    /// Only available with the `cde_stats` feature enabled.
+    #[cfg(feature = "cde_stats")]
+    pub fn take_stats(&self) -> CDEStats {
+        self.stats.take()
+    }
+
     pub fn haz_key_from_pi_key(&self, pik: PItemKey) -> Option<HazKey> {
         self.hazards_map
             .iter()
@@ -353,7 +704,393 @@ pub struct CDEConfig {
 }
 
 /// Snapshot of the state of [`CDEngine`]. Can be used to restore to a previous state.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CDESnapshot {
     pub dynamic_hazards: Vec<Hazard>,
 }
+
+/// Rotations within this tolerance (in radians) of the current orientation are considered contacts at the current orientation.
+const ROT_CONTACT_TOLERANCE: f32 = 1e-5;
+
+/// Rotation by `angle` around `pivot`.
+fn rotation_around(pivot: Point, angle: f32) -> Transformation {
+    Transformation::from_translation((-pivot.0, -pivot.1))
+        .rotate(angle)
+        .translate((pivot.0, pivot.1))
+}
+
+/// 2D cross product between the direction of `edge` and the vector `(dx, dy)`.
+fn cross(edge: &Edge, (dx, dy): (f32, f32)) -> f32 {
+    let (ex, ey) = (edge.end.0 - edge.start.0, edge.end.1 - edge.start.1);
+    ex * dy - ey * dx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision_detection::hazards::filter::NoFilter;
+    use crate::entities::Layout;
+    use crate::entities::{Container, InferiorQualityZone};
+    use crate::geometry::DTransformation;
+    use crate::geometry::geo_enums::RotationRange;
+    use crate::geometry::shape_modification::ShapeModifyMode;
+    use crate::util::test_util::{cde_config, container, item, rect, rect_shape};
+    use float_cmp::approx_eq;
+    use ordered_float::OrderedFloat;
+
+    /// Square of 10 by 10 centered at (50, 50), to be rotated around its center
+    fn square() -> (SPolygon, Point) {
+        (
+            SPolygon::from(rect(45.0, 45.0, 55.0, 55.0)),
+            Point(50.0, 50.0),
+        )
+    }
+
+    /// Checks that rotating the shape just within the bounds is feasible and just beyond them is not
+    fn assert_tight(cde: &CDEngine, shape: &SPolygon, pivot: Point, (lower, upper): (f32, f32)) {
+        let collides = |angle: f32| {
+            let rotated = shape.transform_clone(&rotation_around(pivot, angle));
+            cde.detect_poly_collision(&rotated, &NoFilter)
+        };
+        for (bound, inwards) in [(lower, 1.0), (upper, -1.0)] {
+            if bound.abs() < PI {
+                assert!(!collides(bound + inwards * 1e-2), "{bound}");
+                assert!(collides(bound - inwards * 1e-2), "{bound}");
+            }
+        }
+    }
+
+    #[test]
+    fn rotation_interval_free() {
+        let layout = Layout::new(container(0, 100.0, 100.0));
+        let (shape, pivot) = square();
+
+        let interval = layout
+            .cde()
+            .feasible_rotation_interval(&shape, pivot, &NoFilter);
+        assert_eq!(interval, Some((-PI, PI)));
+    }
+
+    #[test]
+    fn rotation_interval_blocked() {
+        //a wall 1 unit to the right of the square: its corners hit the wall after rotating ~13 degrees in both directions
+        let mut layout = Layout::new(container(0, 100.0, 100.0));
+        layout.place_item(
+            &item(0, 10.0, 100.0),
+            DTransformation::new(0.0, (56.0, 0.0)),
+        );
+        let (shape, pivot) = square();
+
+        let (lower, upper) = layout
+            .cde()
+            .feasible_rotation_interval(&shape, pivot, &NoFilter)
+            .unwrap();
+        //the corner at distance 5√2 reaches x = 56 when √2·sin(θ + π/4) = 1.2
+        let expected = (1.2 / 2.0f32.sqrt()).asin() - PI / 4.0;
+        assert!(approx_eq!(f32, upper, expected, epsilon = 1e-3), "{upper}");
+        assert!(approx_eq!(f32, lower, -expected, epsilon = 1e-3), "{lower}");
+        assert_tight(layout.cde(), &shape, pivot, (lower, upper));
+
+        //an overlapping shape has no feasible interval at all
+        let overlapping = SPolygon::from(rect(50.0, 45.0, 60.0, 55.0));
+        let interval =
+            layout
+                .cde()
+                .feasible_rotation_interval(&overlapping, Point(55.0, 50.0), &NoFilter);
+        assert_eq!(interval, None);
+    }
+
+    #[test]
+    fn rotation_interval_wrap_around() {
+        //an arm pointing right from the pivot, with a block below the pivot.
+        //Rotating clockwise hits the block almost immediately, rotating counterclockwise only after more than half a turn.
+        let mut layout = Layout::new(container(0, 100.0, 100.0));
+        layout.place_item(&item(0, 20.0, 5.0), DTransformation::new(0.0, (40.0, 30.0)));
+        let arm = SPolygon::from(rect(50.0, 49.0, 70.0, 51.0));
+        let pivot = Point(50.0, 50.0);
+
+        let (lower, upper) = layout
+            .cde()
+            .feasible_rotation_interval(&arm, pivot, &NoFilter)
+            .unwrap();
+        //the clockwise contact is found as a counterclockwise contact beyond π, and wraps around to the lower bound
+        assert!(lower > -PI / 2.0 && lower < -PI / 4.0, "{lower}");
+        assert_eq!(upper, PI);
+        assert_tight(layout.cde(), &arm, pivot, (lower, upper));
+    }
+
+    /// Empty 100 by 100 container, with a single 20 by 20 item placed in its center (40..60)
+    fn layout_with_center_item() -> Layout {
+        let mut layout = Layout::new(container(0, 100.0, 100.0));
+        layout.place_item(
+            &item(0, 20.0, 20.0),
+            DTransformation::new(0.0, (40.0, 40.0)),
+        );
+        layout
+    }
+
+    fn collected_entities<T: CDQueryable>(cde: &CDEngine, entity: &T) -> Vec<HazardEntity> {
+        let mut collector = BasicHazardCollector::with_capacity(cde.hazards_map.len());
+        cde.collect_collisions(entity, &mut collector);
+        collector.values().copied().collect()
+    }
+
+    #[test]
+    fn rect_queries() {
+        let layout = layout_with_center_item();
+        let cde = layout.cde();
+
+        //overlapping an edge of the item, contained in the item, and free
+        assert!(cde.detect_collision(&rect(55.0, 55.0, 65.0, 65.0), &NoFilter));
+        assert!(cde.detect_collision(&rect(45.0, 45.0, 50.0, 50.0), &NoFilter));
+        assert!(!cde.detect_collision(&rect(10.0, 10.0, 20.0, 20.0), &NoFilter));
+
+        //partially outside the container and overlapping the item
+        let entities = collected_entities(cde, &rect(55.0, 45.0, 110.0, 50.0));
+        assert_eq!(entities.len(), 2);
+        assert!(entities.contains(&HazardEntity::Exterior));
+        assert!(
+            entities
+                .iter()
+                .any(|e| matches!(e, HazardEntity::PlacedItem { .. }))
+        );
+        assert!(collected_entities(cde, &rect(10.0, 10.0, 20.0, 20.0)).is_empty());
+    }
+
+    #[test]
+    fn circle_queries() {
+        let layout = layout_with_center_item();
+        let cde = layout.cde();
+
+        //contained in the item, crossing the border of the container, and free
+        let inside = Circle::try_new(Point(50.0, 50.0), 2.0).unwrap();
+        let border = Circle::try_new(Point(2.0, 50.0), 5.0).unwrap();
+        let free = Circle::try_new(Point(20.0, 80.0), 5.0).unwrap();
+        assert!(cde.detect_collision(&inside, &NoFilter));
+        assert!(cde.detect_collision(&border, &NoFilter));
+        assert!(!cde.detect_collision(&free, &NoFilter));
+
+        let entities = collected_entities(cde, &inside);
+        assert!(matches!(entities[..], [HazardEntity::PlacedItem { .. }]));
+        assert_eq!(
+            collected_entities(cde, &border),
+            vec![HazardEntity::Exterior]
+        );
+        assert!(collected_entities(cde, &free).is_empty());
+    }
+
+    #[test]
+    fn point_queries() {
+        let layout = layout_with_center_item();
+        let cde = layout.cde();
+
+        assert!(cde.detect_collision(&Point(50.0, 50.0), &NoFilter));
+        assert!(!cde.detect_collision(&Point(20.0, 20.0), &NoFilter));
+        assert!(cde.detect_collision(&Point(120.0, 20.0), &NoFilter));
+
+        let entities = collected_entities(cde, &Point(50.0, 50.0));
+        assert!(matches!(entities[..], [HazardEntity::PlacedItem { .. }]));
+        assert!(collected_entities(cde, &Point(20.0, 20.0)).is_empty());
+    }
+
+    #[test]
+    fn collision_witnesses() {
+        let layout = layout_with_center_item();
+        let cde = layout.cde();
+
+        //overlapping the right edge of the item (x = 60) between y = 45 and y = 50
+        let shape = SPolygon::from(rect(55.0, 45.0, 65.0, 50.0));
+        let witnesses = cde.collect_poly_collision_witnesses(&shape, &NoFilter);
+        let points = witnesses
+            .iter()
+            .map(|w| match w.kind {
+                WitnessKind::EdgeIntersection {
+                    shape_edge,
+                    haz_edge,
+                    point,
+                } => {
+                    assert!(matches!(w.entity, HazardEntity::PlacedItem { .. }));
+                    assert!(shape_edge.distance_to(&point) < 1e-4);
+                    assert!(haz_edge.distance_to(&point) < 1e-4);
+                    assert!(haz_edge.start.0 == 60.0 && haz_edge.end.0 == 60.0);
+                    point
+                }
+                WitnessKind::Containment { .. } => panic!("unexpected witness: {w:?}"),
+            })
+            .sorted_by_key(|p| OrderedFloat(p.1))
+            .collect_vec();
+        assert_eq!(points, vec![Point(60.0, 45.0), Point(60.0, 50.0)]);
+
+        //fully inside the item: a single point inside both
+        let shape = SPolygon::from(rect(45.0, 45.0, 50.0, 50.0));
+        let witnesses = cde.collect_poly_collision_witnesses(&shape, &NoFilter);
+        match witnesses[..] {
+            [
+                CollisionWitness {
+                    kind: WitnessKind::Containment { point },
+                    hkey,
+                    ..
+                },
+            ] => {
+                assert!(shape.collides_with(&point));
+                assert!(cde.hazards_map[hkey].shape.collides_with(&point));
+            }
+            _ => panic!("unexpected witnesses: {witnesses:?}"),
+        }
+
+        //crossing the border of the container at x = 100
+        let shape = SPolygon::from(rect(95.0, 10.0, 105.0, 20.0));
+        let witnesses = cde.collect_poly_collision_witnesses(&shape, &NoFilter);
+        assert_eq!(witnesses.len(), 2);
+        assert!(witnesses.iter().all(|w| w.entity == HazardEntity::Exterior
+            && matches!(w.kind, WitnessKind::EdgeIntersection { point, .. } if point.0 == 100.0)));
+
+        //a free shape has no witnesses
+        let shape = SPolygon::from(rect(10.0, 10.0, 20.0, 20.0));
+        assert!(
+            cde.collect_poly_collision_witnesses(&shape, &NoFilter)
+                .is_empty()
+        );
+    }
+
+    /// Container of 100 by 100 with a zone of quality 1 covering its left half
+    fn container_with_quality_zone() -> Container {
+        let zone = rect_shape(rect(0.0, 0.0, 50.0, 100.0), ShapeModifyMode::Inflate);
+        let quality_zones = vec![InferiorQualityZone::new(1, vec![zone]).unwrap()];
+        let outer = rect_shape(rect(0.0, 0.0, 100.0, 100.0), ShapeModifyMode::Deflate);
+        Container::new(0, outer, quality_zones, vec![], vec![], cde_config()).unwrap()
+    }
+
+    /// Region of 10 by 10 in the bottom left corner of an item
+    fn quality_region(min_quality: Option<usize>) -> ItemQualityRegion {
+        let shape = rect_shape(rect(0.0, 0.0, 10.0, 10.0), ShapeModifyMode::Inflate);
+        ItemQualityRegion::new(min_quality, shape).unwrap()
+    }
+
+    #[test]
+    fn quality_region_collisions() {
+        let container = container_with_quality_zone();
+        let cde = container.base_cde.as_ref();
+        let on_zone = Transformation::from_translation((20.0, 20.0));
+        let off_zone = Transformation::from_translation((70.0, 20.0));
+
+        let sufficient = [quality_region(Some(1))];
+        assert!(!cde.detect_quality_region_collision(&sufficient, &on_zone));
+
+        let stricter = [quality_region(Some(2))];
+        assert!(cde.detect_quality_region_collision(&stricter, &on_zone));
+        assert!(!cde.detect_quality_region_collision(&stricter, &off_zone));
+
+        let full_quality = [quality_region(None)];
+        assert!(cde.detect_quality_region_collision(&full_quality, &on_zone));
+
+        //a single violating region suffices
+        let mixed = [quality_region(Some(1)), quality_region(Some(2))];
+        assert!(cde.detect_quality_region_collision(&mixed, &on_zone));
+        assert!(!cde.detect_quality_region_collision(&[], &on_zone));
+
+        //other hazards are not the concern of the regions, but leaving the bounds of the CDE is
+        let mut layout = Layout::new(container.clone());
+        layout.place_item(
+            &item(0, 10.0, 10.0),
+            DTransformation::new(0.0, (70.0, 20.0)),
+        );
+        let cde = layout.cde();
+        assert!(!cde.detect_quality_region_collision(&full_quality, &off_zone));
+        let outside = Transformation::from_translation((95.0, 20.0));
+        assert!(cde.detect_quality_region_collision(&full_quality, &outside));
+    }
+
+    #[test]
+    fn grain_collisions() {
+        //the left half only allows horizontal grain, a small zone in the top right allows none at all
+        let grain_zones = vec![
+            GrainZone::new(
+                RotationRange::Discrete(vec![0.0, PI]),
+                rect_shape(rect(0.0, 0.0, 50.0, 100.0), ShapeModifyMode::Inflate),
+            )
+            .unwrap(),
+            GrainZone::new(
+                RotationRange::Discrete(vec![]),
+                rect_shape(rect(80.0, 80.0, 85.0, 85.0), ShapeModifyMode::Inflate),
+            )
+            .unwrap(),
+        ];
+        let outer = rect_shape(rect(0.0, 0.0, 100.0, 100.0), ShapeModifyMode::Deflate);
+        let container =
+            Container::new(0, outer, vec![], vec![], grain_zones, cde_config()).unwrap();
+        let cde = container.base_cde.as_ref();
+        let square = |x: f32, y: f32, size: f32| SPolygon::from(rect(x, y, x + size, y + size));
+
+        //entirely within the left zone, no edges intersect
+        assert!(!cde.detect_grain_collision(&square(20.0, 20.0, 10.0), 0.0));
+        assert!(!cde.detect_grain_collision(&square(20.0, 20.0, 10.0), PI));
+        assert!(cde.detect_grain_collision(&square(20.0, 20.0, 10.0), PI / 2.0));
+
+        //crossing the border of the left zone
+        assert!(cde.detect_grain_collision(&square(45.0, 20.0, 10.0), PI / 2.0));
+        assert!(!cde.detect_grain_collision(&square(55.0, 20.0, 10.0), PI / 2.0));
+
+        //entirely containing the small zone
+        assert!(cde.detect_grain_collision(&square(75.0, 75.0, 15.0), 0.0));
+        assert!(!cde.detect_grain_collision(&square(60.0, 75.0, 15.0), 0.0));
+    }
+
+    #[cfg(feature = "cde_stats")]
+    #[test]
+    fn stats_per_engine() {
+        let mut layout = layout_with_center_item();
+        let other = Layout::new(layout.container.clone());
+        assert_eq!(layout.cde().take_stats().n_registrations, 1);
+        assert_eq!(other.cde().stats(), CDEStats::default());
+
+        let cde = layout.cde();
+        //resolved by the bounding box of the CDE, by the edges of the polygon, and no collision
+        assert!(
+            cde.detect_poly_collision(&SPolygon::from(rect(95.0, 10.0, 105.0, 20.0)), &NoFilter)
+        );
+        assert!(
+            cde.detect_poly_collision(&SPolygon::from(rect(55.0, 45.0, 65.0, 50.0)), &NoFilter)
+        );
+        assert!(
+            !cde.detect_poly_collision(&SPolygon::from(rect(10.0, 10.0, 20.0, 20.0)), &NoFilter)
+        );
+        //the largest pole of the surrogate crosses the right edge of the placed item
+        let surrogate = item(1, 20.0, 20.0).shape_cd.surrogate().clone();
+        let transf = Transformation::from_translation((50.0, 40.0));
+        assert!(cde.detect_surrogate_collision(&surrogate, &transf, &NoFilter));
+        //collection queries are not counted as resolved
+        let mut collector = BasicHazardCollector::with_capacity(cde.hazards_map.len());
+        cde.collect_poly_collisions(
+            &SPolygon::from(rect(55.0, 45.0, 65.0, 50.0)),
+            &mut collector,
+        );
+
+        let stats = cde.stats();
+        assert_eq!(stats.n_queries, 5);
+        assert_eq!(stats.n_resolved_by_bbox, 1);
+        assert_eq!(stats.n_resolved_by_poly, 1);
+        assert_eq!(stats.n_resolved_by_poles, 1);
+        assert_eq!(stats.n_resolved_by_piers, 0);
+        assert!(stats.n_nodes_visited > 0 && stats.n_edge_tests > 0);
+
+        //queries issued within other queries count towards the same engine, clones start from zero
+        cde.collect_poly_collision_witnesses(
+            &SPolygon::from(rect(10.0, 10.0, 20.0, 20.0)),
+            &NoFilter,
+        );
+        assert_eq!(cde.stats().n_queries, 6);
+        assert_eq!(cde.clone().stats(), CDEStats::default());
+        assert_eq!(other.cde().stats(), CDEStats::default());
+
+        //the difference with stats from before a take saturates at zero
+        let before = cde.stats();
+        cde.take_stats();
+        assert_eq!(cde.stats() - before, CDEStats::default());
+
+        let pk = layout.placed_items.keys().next().unwrap();
+        layout.remove_item(pk);
+        assert_eq!(layout.cde().take_stats().n_deregistrations, 1);
+        assert_eq!(layout.cde().stats(), CDEStats::default());
+    }
+}
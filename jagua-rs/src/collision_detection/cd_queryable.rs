@@ -0,0 +1,67 @@
+use crate::collision_detection::quadtree::QTQueryable;
+use crate::geometry::geo_traits::CollidesWith;
+use crate::geometry::primitives::{Circle, Point, Rect};
+
+#[cfg(doc)]
+use crate::collision_detection::CDEngine;
+
+/// Common trait for all geometric primitives for which the [`CDEngine`] can resolve generic collision queries,
+/// including collisions caused by containment. These include: [Rect], [Circle] and [Point].
+pub trait CDQueryable: QTQueryable {
+    /// Axis-aligned bounding box of the primitive
+    fn bbox(&self) -> Rect;
+
+    /// A point which is guaranteed to lie inside the primitive
+    fn interior_point(&self) -> Point;
+
+    /// Whether `point` lies inside the primitive
+    fn contains(&self, point: &Point) -> bool;
+}
+
+impl CDQueryable for Rect {
+    fn bbox(&self) -> Rect {
+        *self
+    }
+
+    fn interior_point(&self) -> Point {
+        self.centroid()
+    }
+
+    fn contains(&self, point: &Point) -> bool {
+        self.collides_with(point)
+    }
+}
+
+impl CDQueryable for Circle {
+    fn bbox(&self) -> Rect {
+        Circle::bbox(self)
+    }
+
+    fn interior_point(&self) -> Point {
+        self.center
+    }
+
+    fn contains(&self, point: &Point) -> bool {
+        self.collides_with(point)
+    }
+}
+
+impl CDQueryable for Point {
+    fn bbox(&self) -> Rect {
+        //degenerate rectangle, cannot be created via `Rect::try_new`
+        Rect {
+            x_min: self.0,
+            y_min: self.1,
+            x_max: self.0,
+            y_max: self.1,
+        }
+    }
+
+    fn interior_point(&self) -> Point {
+        *self
+    }
+
+    fn contains(&self, point: &Point) -> bool {
+        self == point
+    }
+}
@@ -1,11 +1,39 @@
 use crate::collision_detection::hazards::collector::HazardCollector;
 use crate::collision_detection::hazards::{HazKey, Hazard, HazardEntity};
+use crate::entities::Item;
 use slotmap::{SecondaryMap, SlotMap};
 
 /// Trait for filters to ignore all [`Hazard`]s induced by specific [`HazardEntity`]s.
 /// Enables collision queries to ignore specific hazards during the check.
+/// Filters can be combined using [`HazardFilter::or`], [`HazardFilter::and`] and [`HazardFilter::not`].
 pub trait HazardFilter {
-    fn is_irrelevant(&self, haz_key: HazKey) -> bool;
+    /// Whether the hazard with key `haz_key`, induced by `entity`, should be ignored.
+    /// The entity is passed along so filters can decide without looking up the hazard.
+    fn is_irrelevant(&self, haz_key: HazKey, entity: &HazardEntity) -> bool;
+
+    /// Combines two filters: a hazard is irrelevant if either of the filters deems it irrelevant.
+    fn or<F: HazardFilter>(self, other: F) -> OrFilter<Self, F>
+    where
+        Self: Sized,
+    {
+        OrFilter(self, other)
+    }
+
+    /// Combines two filters: a hazard is irrelevant only if both filters deem it irrelevant.
+    fn and<F: HazardFilter>(self, other: F) -> AndFilter<Self, F>
+    where
+        Self: Sized,
+    {
+        AndFilter(self, other)
+    }
+
+    /// Inverts the filter: a hazard is irrelevant if the filter deems it relevant.
+    fn not(self) -> NotFilter<Self>
+    where
+        Self: Sized,
+    {
+        NotFilter(self)
+    }
 }
 
 /// Deems hazards with specific [`HazKey`]'s as irrelevant.
@@ -41,15 +69,29 @@ impl HazKeyFilter {
     }
 }
 
+impl HazKeyFilter {
+    /// Creates a filter that deems all hazards irrelevant which do not apply to the item, see [`ItemFilter`].
+    pub fn for_item(item: &Item, haz_map: &SlotMap<HazKey, Hazard>) -> Self {
+        let filter = ItemFilter::new(item, haz_map);
+        HazKeyFilter(
+            haz_map
+                .iter()
+                .filter(|(hkey, h)| filter.is_irrelevant(*hkey, &h.entity))
+                .map(|(hkey, _)| (hkey, ()))
+                .collect(),
+        )
+    }
+}
+
 impl HazardFilter for HazKeyFilter {
-    fn is_irrelevant(&self, haz_key: HazKey) -> bool {
+    fn is_irrelevant(&self, haz_key: HazKey, _entity: &HazardEntity) -> bool {
         self.0.contains_key(haz_key)
     }
 }
 
 /// Deems hazards induced by itself as irrelevant.
 impl HazardFilter for HazKey {
-    fn is_irrelevant(&self, hk: HazKey) -> bool {
+    fn is_irrelevant(&self, hk: HazKey, _entity: &HazardEntity) -> bool {
         *self == hk
     }
 }
@@ -59,18 +101,270 @@ impl HazardFilter for HazKey {
 pub struct NoFilter;
 
 impl HazardFilter for NoFilter {
-    fn is_irrelevant(&self, _haz_key: HazKey) -> bool {
+    fn is_irrelevant(&self, _haz_key: HazKey, _entity: &HazardEntity) -> bool {
         false
     }
 }
 
+/// Deems all hazards as irrelevant for which the predicate on their [`HazardEntity`] holds.
+#[derive(Clone, Debug)]
+pub struct EntityFilter<P: Fn(&HazardEntity) -> bool>(pub P);
+
+impl<P: Fn(&HazardEntity) -> bool> HazardFilter for EntityFilter<P> {
+    fn is_irrelevant(&self, _haz_key: HazKey, entity: &HazardEntity) -> bool {
+        (self.0)(entity)
+    }
+}
+
+/// Deems all hazards induced by placed items of a specific item id as irrelevant.
+#[derive(Clone, Copy, Debug)]
+pub struct ItemIdFilter(pub usize);
+
+impl HazardFilter for ItemIdFilter {
+    fn is_irrelevant(&self, _haz_key: HazKey, entity: &HazardEntity) -> bool {
+        matches!(entity, HazardEntity::PlacedItem { id, .. } if *id == self.0)
+    }
+}
+
+/// Deems all inferior quality zones at or above a certain quality as irrelevant.
+#[derive(Clone, Copy, Debug)]
+pub struct QZoneFilter(pub usize);
+
+impl HazardFilter for QZoneFilter {
+    fn is_irrelevant(&self, _haz_key: HazKey, entity: &HazardEntity) -> bool {
+        matches!(entity, HazardEntity::InferiorQualityZone { quality, .. } if *quality >= self.0)
+    }
+}
+
+/// Deems all hazards irrelevant which do not apply to a specific item:
+/// inferior quality zones sufficient for its [`Item::min_quality`] and custom zones it is allowed to use according to their [`ZoneItemRule`](crate::entities::ZoneItemRule).
+/// Unlike [`HazKeyFilter::for_item`], it does not allocate.
+#[derive(Clone, Copy, Debug)]
+pub struct ItemFilter<'a> {
+    pub item: &'a Item,
+    /// The hazards of the CDE to be queried, needed to look up the item rules of custom zones
+    pub haz_map: &'a SlotMap<HazKey, Hazard>,
+}
+
+impl<'a> ItemFilter<'a> {
+    pub fn new(item: &'a Item, haz_map: &'a SlotMap<HazKey, Hazard>) -> Self {
+        ItemFilter { item, haz_map }
+    }
+}
+
+impl HazardFilter for ItemFilter<'_> {
+    fn is_irrelevant(&self, haz_key: HazKey, entity: &HazardEntity) -> bool {
+        match entity {
+            HazardEntity::InferiorQualityZone { quality, .. } => {
+                self.item.min_quality.is_some_and(|q| *quality >= q)
+            }
+            HazardEntity::Custom { .. } => !self.haz_map[haz_key].item_rule.forbids(self.item),
+            _ => false,
+        }
+    }
+}
+
+/// Deems all custom zones as irrelevant, optionally only those with a specific tag.
+#[derive(Clone, Copy, Debug)]
+pub struct CustomZoneFilter(pub Option<usize>);
+
+impl HazardFilter for CustomZoneFilter {
+    fn is_irrelevant(&self, _haz_key: HazKey, entity: &HazardEntity) -> bool {
+        match entity {
+            HazardEntity::Custom { tag, .. } => self.0.is_none_or(|t| t == *tag),
+            _ => false,
+        }
+    }
+}
+
+/// See [`HazardFilter::or`].
+#[derive(Clone, Debug)]
+pub struct OrFilter<A: HazardFilter, B: HazardFilter>(pub A, pub B);
+
+impl<A: HazardFilter, B: HazardFilter> HazardFilter for OrFilter<A, B> {
+    fn is_irrelevant(&self, haz_key: HazKey, entity: &HazardEntity) -> bool {
+        self.0.is_irrelevant(haz_key, entity) || self.1.is_irrelevant(haz_key, entity)
+    }
+}
+
+/// See [`HazardFilter::and`].
+#[derive(Clone, Debug)]
+pub struct AndFilter<A: HazardFilter, B: HazardFilter>(pub A, pub B);
+
+impl<A: HazardFilter, B: HazardFilter> HazardFilter for AndFilter<A, B> {
+    fn is_irrelevant(&self, haz_key: HazKey, entity: &HazardEntity) -> bool {
+        self.0.is_irrelevant(haz_key, entity) && self.1.is_irrelevant(haz_key, entity)
+    }
+}
+
+/// See [`HazardFilter::not`].
+#[derive(Clone, Debug)]
+pub struct NotFilter<F: HazardFilter>(pub F);
+
+impl<F: HazardFilter> HazardFilter for NotFilter<F> {
+    fn is_irrelevant(&self, haz_key: HazKey, entity: &HazardEntity) -> bool {
+        !self.0.is_irrelevant(haz_key, entity)
+    }
+}
+
 /// Implements [`HazardFilter`] for any type that implements [`HazardCollector`].
 /// Any [`HazardEntity`]s that are already in the collector are considered irrelevant.
 impl<T> HazardFilter for T
 where
     T: HazardCollector,
 {
-    fn is_irrelevant(&self, hkey: HazKey) -> bool {
+    fn is_irrelevant(&self, hkey: HazKey, _entity: &HazardEntity) -> bool {
         self.contains_key(hkey)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{ItemSelector, PItemKey, ZoneItemRule};
+    use crate::geometry::DTransformation;
+    use crate::geometry::geo_enums::GeoPosition;
+    use crate::geometry::primitives::SPolygon;
+    use crate::util::test_util::{item, rect};
+    use slotmap::KeyData;
+
+    fn placed_item(id: usize) -> HazardEntity {
+        HazardEntity::PlacedItem {
+            id,
+            dt: DTransformation::empty(),
+            pk: PItemKey::from(KeyData::from_ffi(id as u64)),
+        }
+    }
+
+    fn qzone(quality: usize) -> HazardEntity {
+        HazardEntity::InferiorQualityZone { quality, idx: 0 }
+    }
+
+    fn custom(id: usize, tag: usize) -> HazardEntity {
+        HazardEntity::Custom {
+            id,
+            tag,
+            scope: GeoPosition::Interior,
+        }
+    }
+
+    /// A hazard map containing one hazard of every kind of entity, and their keys
+    fn hazards(custom_rule: ZoneItemRule) -> (SlotMap<HazKey, Hazard>, Vec<HazKey>) {
+        let shape = SPolygon::from(rect(0.0, 0.0, 1.0, 1.0));
+        let mut haz_map = SlotMap::with_key();
+        let keys = [
+            placed_item(0),
+            placed_item(1),
+            HazardEntity::Exterior,
+            HazardEntity::Hole { idx: 0 },
+            qzone(0),
+            qzone(2),
+            custom(0, 5),
+            custom(1, 6),
+        ]
+        .into_iter()
+        .map(|e| {
+            let hazard = Hazard::new(e, shape.clone(), false).with_item_rule(custom_rule.clone());
+            haz_map.insert(hazard)
+        })
+        .collect();
+        (haz_map, keys)
+    }
+
+    /// The entities deemed irrelevant by the filter
+    fn irrelevant(
+        filter: &impl HazardFilter,
+        haz_map: &SlotMap<HazKey, Hazard>,
+    ) -> Vec<HazardEntity> {
+        haz_map
+            .iter()
+            .filter(|(hkey, h)| filter.is_irrelevant(*hkey, &h.entity))
+            .map(|(_, h)| h.entity)
+            .collect()
+    }
+
+    #[test]
+    fn entity_filters() {
+        let (haz_map, keys) = hazards(ZoneItemRule::All);
+
+        assert_eq!(irrelevant(&NoFilter, &haz_map), vec![]);
+        assert_eq!(
+            irrelevant(&keys[3], &haz_map),
+            vec![HazardEntity::Hole { idx: 0 }]
+        );
+        assert_eq!(irrelevant(&ItemIdFilter(1), &haz_map), vec![placed_item(1)]);
+        assert_eq!(irrelevant(&QZoneFilter(1), &haz_map), vec![qzone(2)]);
+        assert_eq!(
+            irrelevant(&CustomZoneFilter(Some(6)), &haz_map),
+            vec![custom(1, 6)]
+        );
+        assert_eq!(
+            irrelevant(&CustomZoneFilter(None), &haz_map),
+            vec![custom(0, 5), custom(1, 6)]
+        );
+        let holes_and_exterior = EntityFilter(|e: &HazardEntity| {
+            matches!(e, HazardEntity::Exterior | HazardEntity::Hole { .. })
+        });
+        assert_eq!(
+            irrelevant(&holes_and_exterior, &haz_map),
+            vec![HazardEntity::Exterior, HazardEntity::Hole { idx: 0 }]
+        );
+    }
+
+    #[test]
+    fn combined_filters() {
+        let (haz_map, keys) = hazards(ZoneItemRule::All);
+
+        let or = ItemIdFilter(0).or(QZoneFilter(0));
+        assert_eq!(
+            irrelevant(&or, &haz_map),
+            vec![placed_item(0), qzone(0), qzone(2)]
+        );
+
+        let and = QZoneFilter(0).and(QZoneFilter(1));
+        assert_eq!(irrelevant(&and, &haz_map), vec![qzone(2)]);
+
+        //all custom zones, except the one with tag 6
+        let and_not = CustomZoneFilter(None).and(CustomZoneFilter(Some(6)).not());
+        assert_eq!(irrelevant(&and_not, &haz_map), vec![custom(0, 5)]);
+
+        //everything except the placed items
+        let not_or = ItemIdFilter(0).or(ItemIdFilter(1)).not();
+        assert_eq!(irrelevant(&not_or, &haz_map).len(), keys.len() - 2);
+
+        //a hazard key combined with an entity filter
+        let key_or_entity = keys[0].or(CustomZoneFilter(Some(5)));
+        assert_eq!(
+            irrelevant(&key_or_entity, &haz_map),
+            vec![placed_item(0), custom(0, 5)]
+        );
+    }
+
+    #[test]
+    fn item_filter() {
+        let forbidden_for_1 = ZoneItemRule::ForbiddenFor(ItemSelector {
+            item_ids: vec![1],
+            item_classes: vec![],
+        });
+        let (haz_map, _) = hazards(forbidden_for_1);
+
+        //requires full quality and is not affected by the custom zones
+        let item_0 = item(0, 1.0, 1.0);
+        assert_eq!(
+            irrelevant(&ItemFilter::new(&item_0, &haz_map), &haz_map),
+            vec![custom(0, 5), custom(1, 6)]
+        );
+
+        //quality 2 suffices, but the custom zones are forbidden
+        let item_1 = Item {
+            min_quality: Some(2),
+            ..item(1, 1.0, 1.0)
+        };
+        let filter = ItemFilter::new(&item_1, &haz_map);
+        assert_eq!(irrelevant(&filter, &haz_map), vec![qzone(2)]);
+
+        //equivalent to the allocating version
+        let key_filter = HazKeyFilter::for_item(&item_1, &haz_map);
+        assert_eq!(irrelevant(&key_filter, &haz_map), vec![qzone(2)]);
+    }
+}
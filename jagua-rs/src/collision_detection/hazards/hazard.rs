@@ -1,7 +1,8 @@
-use crate::entities::{PItemKey, PlacedItem};
+use crate::entities::{PItemKey, PlacedItem, ZoneItemRule};
 use crate::geometry::DTransformation;
 use crate::geometry::geo_enums::GeoPosition;
 use crate::geometry::primitives::SPolygon;
+use serde::{Deserialize, Serialize};
 use slotmap::new_key_type;
 use std::borrow::Borrow;
 
@@ -12,7 +13,7 @@ new_key_type! {
 
 /// Any spatial constraint affecting the feasibility of a placement of an Item.
 /// See [`HazardEntity`] for the different entities that can induce a hazard.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Hazard {
     /// The entity inducing the hazard
     pub entity: HazardEntity,
@@ -20,6 +21,8 @@ pub struct Hazard {
     pub shape: SPolygon,
     /// Whether the hazard is dynamic, meaning it can change over time (e.g., moving items)
     pub dynamic: bool,
+    /// The items for which the hazard is forbidden, see [`ZoneItemRule`]
+    pub item_rule: ZoneItemRule,
 }
 
 impl Hazard {
@@ -28,11 +31,17 @@ impl Hazard {
             entity,
             shape,
             dynamic,
+            item_rule: ZoneItemRule::All,
         }
     }
+
+    pub fn with_item_rule(mut self, item_rule: ZoneItemRule) -> Self {
+        self.item_rule = item_rule;
+        self
+    }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 /// Entity inducing a [`Hazard`].
 /// All entities are uniquely identified.
 pub enum HazardEntity {
@@ -48,6 +57,13 @@ pub enum HazardEntity {
     Hole { idx: usize },
     /// Represents a zone in the container with a specific quality level that is inferior to the base quality.
     InferiorQualityZone { quality: usize, idx: usize },
+    /// Represents a user-defined zone (e.g. clamps, suction cups or marker zones), defined by its id and tag.
+    /// Induces a hazard in either the interior or exterior of its shape.
+    Custom {
+        id: usize,
+        tag: usize,
+        scope: GeoPosition,
+    },
 }
 
 impl HazardEntity {
@@ -58,6 +74,7 @@ impl HazardEntity {
             HazardEntity::Exterior => GeoPosition::Exterior,
             HazardEntity::Hole { .. } => GeoPosition::Interior,
             HazardEntity::InferiorQualityZone { .. } => GeoPosition::Interior,
+            HazardEntity::Custom { scope, .. } => *scope,
         }
     }
 }
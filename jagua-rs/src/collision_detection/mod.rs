@@ -1,4 +1,6 @@
 mod cd_engine;
+mod cd_queryable;
+mod witness;
 
 /// Everything Hazard related
 pub mod hazards;
@@ -6,9 +8,38 @@ pub mod hazards;
 /// Everything Quadtree related.
 pub mod quadtree;
 
+/// Instrumentation counters of the CDE
+#[cfg(feature = "cde_stats")]
+pub mod stats;
+
 #[doc(inline)]
 pub use cd_engine::CDEConfig;
 #[doc(inline)]
 pub use cd_engine::CDESnapshot;
 #[doc(inline)]
 pub use cd_engine::CDEngine;
+#[doc(inline)]
+pub use cd_queryable::CDQueryable;
+#[doc(inline)]
+pub use witness::CollisionWitness;
+#[doc(inline)]
+pub use witness::WitnessKind;
+
+/// Increments a counter of the CDE instrumentation, compiles to nothing without the `cde_stats` feature.
+macro_rules! record_stat {
+    ($field:ident) => {
+        #[cfg(feature = "cde_stats")]
+        $crate::collision_detection::stats::record(|s| s.$field += 1);
+    };
+}
+pub(crate) use record_stat;
+
+/// Attributes all counters recorded until the end of the enclosing block to the given engine,
+/// compiles to nothing without the `cde_stats` feature.
+macro_rules! stats_scope {
+    ($cde:expr) => {
+        #[cfg(feature = "cde_stats")]
+        let _stats_scope = $cde.stats.scope();
+    };
+}
+pub(crate) use stats_scope;
@@ -59,7 +59,8 @@ impl QTHazardVec {
     /// Ignores any hazards that are deemed irrelevant by the filter.
     pub fn strongest(&self, filter: &impl HazardFilter) -> Option<&QTHazard> {
         debug_assert!(assert_caches_correct(self));
-        self.iter().find(|hz| !filter.is_irrelevant(hz.hkey))
+        self.iter()
+            .find(|hz| !filter.is_irrelevant(hz.hkey, &hz.entity))
     }
 
     pub fn is_empty(&self) -> bool {
@@ -5,6 +5,7 @@ use crate::collision_detection::quadtree::QTHazPresence;
 use crate::collision_detection::quadtree::QTHazard;
 use crate::collision_detection::quadtree::qt_hazard_vec::QTHazardVec;
 use crate::collision_detection::quadtree::qt_traits::QTQueryable;
+use crate::collision_detection::record_stat;
 use crate::geometry::geo_traits::CollidesWith;
 use crate::geometry::primitives::Rect;
 use slotmap::SlotMap;
@@ -101,6 +102,7 @@ impl QTNode {
         entity: &T,
         filter: &impl HazardFilter,
     ) -> Option<&HazardEntity> {
+        record_stat!(n_nodes_visited);
         match self.hazards.strongest(filter) {
             None => None,
             Some(strongest_hazard) => match strongest_hazard.presence {
@@ -128,7 +130,7 @@ impl QTNode {
                             let mut relevant_hazards = self
                                 .hazards
                                 .iter()
-                                .filter(|hz| !filter.is_irrelevant(hz.hkey));
+                                .filter(|hz| !filter.is_irrelevant(hz.hkey, &hz.entity));
 
                             relevant_hazards
                                 .find(|hz| match &hz.presence {
@@ -153,6 +155,7 @@ impl QTNode {
         entity: &T,
         collector: &mut impl HazardCollector,
     ) {
+        record_stat!(n_nodes_visited);
         // Condition to perform collision detection now or pass it to children:
         let perform_cd_now = self.hazards.n_active_edges() <= self.cd_threshold as usize;
 
@@ -1,4 +1,5 @@
 use crate::collision_detection::quadtree::qt_traits::QTQueryable;
+use crate::collision_detection::record_stat;
 use crate::geometry::geo_traits::CollidesWith;
 use crate::geometry::primitives::{Edge, Rect, SPolygon};
 
@@ -67,6 +68,10 @@ impl QTHazPartial {
 impl<T: QTQueryable> CollidesWith<T> for QTHazPartial {
     fn collides_with(&self, entity: &T) -> bool {
         // If the entity does not collide with the bounding box of the hazard, it cannot collide with the hazard
-        entity.collides_with(&self.ff_bbox) && self.edges.iter().any(|e| entity.collides_with(e))
+        entity.collides_with(&self.ff_bbox)
+            && self.edges.iter().any(|e| {
+                record_stat!(n_edge_tests);
+                entity.collides_with(e)
+            })
     }
 }
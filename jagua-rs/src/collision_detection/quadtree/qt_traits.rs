@@ -4,7 +4,7 @@ use crate::geometry::primitives::{Circle, Edge, Point};
 use std::cmp::Ordering;
 
 /// Common trait for all geometric primitives that can be directly queried in the quadtree
-/// for collisions with the edges of the registered hazards. These include: [Rect], [Edge] and [Circle].
+/// for collisions with the edges of the registered hazards. These include: [Rect], [Edge], [Circle] and [Point].
 pub trait QTQueryable: CollidesWith<Edge> + CollidesWith<Rect> {
     /// Checks
     fn collides_with_quadrants(&self, _r: &Rect, qs: [&Rect; 4]) -> [bool; 4] {
@@ -15,6 +15,7 @@ pub trait QTQueryable: CollidesWith<Edge> + CollidesWith<Rect> {
 
 impl QTQueryable for Circle {}
 impl QTQueryable for Rect {}
+impl QTQueryable for Point {}
 
 impl QTQueryable for Edge {
     fn collides_with_quadrants(&self, r: &Rect, qs: [&Rect; 4]) -> [bool; 4] {
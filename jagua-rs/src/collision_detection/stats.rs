@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::ops::{Add, Sub};
+use std::sync::Mutex;
+
+/// Snapshot of the instrumentation counters of a [`CDEngine`](crate::collision_detection::CDEngine),
+/// see [`CDEngine::stats`](crate::collision_detection::CDEngine::stats).
+/// Only available with the `cde_stats` feature enabled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CDEStats {
+    /// Number of collision queries (detection or collection) issued to the CDE
+    pub n_queries: u64,
+    /// Number of detection queries where a collision was detected because the entity does not fit in the bounding box of the CDE
+    pub n_resolved_by_bbox: u64,
+    /// Number of surrogate queries where a collision was detected by one of the poles
+    pub n_resolved_by_poles: u64,
+    /// Number of surrogate queries where a collision was detected by one of the piers
+    pub n_resolved_by_piers: u64,
+    /// Number of polygon detection queries where a collision was detected using the edges of the full polygon (or containment)
+    pub n_resolved_by_poly: u64,
+    /// Number of tests between a queried primitive (edge, pole, pier...) and an edge of a hazard
+    pub n_edge_tests: u64,
+    /// Number of quadtree nodes visited during queries
+    pub n_nodes_visited: u64,
+    /// Number of hazards registered in the CDE
+    pub n_registrations: u64,
+    /// Number of hazards deregistered from the CDE
+    pub n_deregistrations: u64,
+}
+
+impl Add for CDEStats {
+    type Output = CDEStats;
+
+    fn add(self, rhs: CDEStats) -> CDEStats {
+        CDEStats {
+            n_queries: self.n_queries + rhs.n_queries,
+            n_resolved_by_bbox: self.n_resolved_by_bbox + rhs.n_resolved_by_bbox,
+            n_resolved_by_poles: self.n_resolved_by_poles + rhs.n_resolved_by_poles,
+            n_resolved_by_piers: self.n_resolved_by_piers + rhs.n_resolved_by_piers,
+            n_resolved_by_poly: self.n_resolved_by_poly + rhs.n_resolved_by_poly,
+            n_edge_tests: self.n_edge_tests + rhs.n_edge_tests,
+            n_nodes_visited: self.n_nodes_visited + rhs.n_nodes_visited,
+            n_registrations: self.n_registrations + rhs.n_registrations,
+            n_deregistrations: self.n_deregistrations + rhs.n_deregistrations,
+        }
+    }
+}
+
+impl Sub for CDEStats {
+    type Output = CDEStats;
+
+    /// Difference between two snapshots, useful to log the counters per phase.
+    /// Counters which decreased in between, e.g. because the stats were taken, saturate at zero.
+    fn sub(self, rhs: CDEStats) -> CDEStats {
+        CDEStats {
+            n_queries: self.n_queries.saturating_sub(rhs.n_queries),
+            n_resolved_by_bbox: self
+                .n_resolved_by_bbox
+                .saturating_sub(rhs.n_resolved_by_bbox),
+            n_resolved_by_poles: self
+                .n_resolved_by_poles
+                .saturating_sub(rhs.n_resolved_by_poles),
+            n_resolved_by_piers: self
+                .n_resolved_by_piers
+                .saturating_sub(rhs.n_resolved_by_piers),
+            n_resolved_by_poly: self
+                .n_resolved_by_poly
+                .saturating_sub(rhs.n_resolved_by_poly),
+            n_edge_tests: self.n_edge_tests.saturating_sub(rhs.n_edge_tests),
+            n_nodes_visited: self.n_nodes_visited.saturating_sub(rhs.n_nodes_visited),
+            n_registrations: self.n_registrations.saturating_sub(rhs.n_registrations),
+            n_deregistrations: self.n_deregistrations.saturating_sub(rhs.n_deregistrations),
+        }
+    }
+}
+
+/// The counters of a single engine.
+/// A clone starts counting from zero, as it is a different engine.
+#[derive(Debug, Default)]
+pub(crate) struct EngineStats(Mutex<CDEStats>);
+
+impl EngineStats {
+    pub fn get(&self) -> CDEStats {
+        *self.0.lock().unwrap()
+    }
+
+    pub fn take(&self) -> CDEStats {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+
+    /// Starts attributing all counters recorded on the current thread to this engine, until the returned scope is dropped.
+    pub fn scope(&self) -> StatsScope<'_> {
+        let outer = ACTIVE.replace(Some(CDEStats::default()));
+        StatsScope {
+            engine: self,
+            outer,
+        }
+    }
+}
+
+impl Clone for EngineStats {
+    fn clone(&self) -> Self {
+        EngineStats::default()
+    }
+}
+
+thread_local! {
+    /// Counters recorded by the engine query currently running on this thread, if any.
+    /// The quadtree has no access to the engine it is part of, so it records here.
+    static ACTIVE: Cell<Option<CDEStats>> = const { Cell::new(None) };
+}
+
+/// See [`EngineStats::scope`]. Scopes can be nested (queries calling other queries),
+/// the counters of the outer scope are set aside until the inner one is dropped.
+pub(crate) struct StatsScope<'a> {
+    engine: &'a EngineStats,
+    outer: Option<CDEStats>,
+}
+
+impl Drop for StatsScope<'_> {
+    fn drop(&mut self) {
+        let recorded = ACTIVE.replace(self.outer.take()).unwrap_or_default();
+        let mut stats = self.engine.0.lock().unwrap();
+        *stats = *stats + recorded;
+    }
+}
+
+/// Records to the engine query currently running on this thread, ignored outside of any query.
+pub(crate) fn record(f: impl FnOnce(&mut CDEStats)) {
+    ACTIVE.with(|active| {
+        if let Some(mut stats) = active.get() {
+            f(&mut stats);
+            active.set(Some(stats));
+        }
+    });
+}
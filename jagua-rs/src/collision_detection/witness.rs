@@ -0,0 +1,24 @@
+use crate::collision_detection::hazards::{HazKey, HazardEntity};
+use crate::geometry::primitives::{Edge, Point};
+
+/// Evidence of a collision between a shape and a hazard, for diagnostic purposes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CollisionWitness {
+    pub hkey: HazKey,
+    pub entity: HazardEntity,
+    pub kind: WitnessKind,
+}
+
+/// Describes why a shape and a hazard are colliding.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WitnessKind {
+    /// An edge of the shape intersects an edge of the hazard at `point`
+    EdgeIntersection {
+        shape_edge: Edge,
+        haz_edge: Edge,
+        point: Point,
+    },
+    /// No edges intersect, but one is fully included in the other.
+    /// `point` lies inside the shape and inside the forbidden region of the hazard.
+    Containment { point: Point },
+}
@@ -5,10 +5,14 @@ use itertools::Itertools;
 use crate::collision_detection::hazards::Hazard;
 use crate::collision_detection::hazards::HazardEntity;
 use crate::collision_detection::{CDEConfig, CDEngine};
+use crate::entities::Item;
 use crate::geometry::OriginalShape;
+use crate::geometry::geo_enums::{GeoPosition, RotationRange};
 use crate::geometry::primitives::SPolygon;
+use crate::geometry::shape_modification::ShapeModifyMode;
 
 use anyhow::{Result, ensure};
+use serde::{Deserialize, Serialize};
 
 /// A container in which [`Item`](crate::entities::Item)'s can be placed.
 #[derive(Clone, Debug)]
@@ -18,8 +22,13 @@ pub struct Container {
     pub outer_orig: Arc<OriginalShape>,
     /// Contour of the container to be used for collision detection
     pub outer_cd: Arc<SPolygon>,
-    /// Zones of different qualities in the container, stored per quality.
-    pub quality_zones: [Option<InferiorQualityZone>; N_QUALITIES],
+    /// Zones of different qualities in the container, indexed by quality.
+    /// The number of quality levels is determined by the highest quality zone.
+    pub quality_zones: Vec<Option<InferiorQualityZone>>,
+    /// User-defined zones in the container
+    pub custom_zones: Vec<CustomZone>,
+    /// Zones with their own allowed rotations for grain sensitive items
+    pub grain_zones: Vec<GrainZone>,
     /// The initial state of the `CDEngine` for this container. (equivalent to an empty layout using this container)
     pub base_cde: Arc<CDEngine>,
 }
@@ -29,6 +38,8 @@ impl Container {
         id: usize,
         original_outer: OriginalShape,
         quality_zones: Vec<InferiorQualityZone>,
+        custom_zones: Vec<CustomZone>,
+        grain_zones: Vec<GrainZone>,
         cde_config: CDEConfig,
     ) -> Result<Self> {
         let outer = Arc::new(original_outer.convert_to_internal()?);
@@ -38,14 +49,12 @@ impl Container {
             "Quality zones must have unique qualities"
         );
         ensure!(
-            quality_zones
-                .iter()
-                .map(|qz| qz.quality)
-                .all(|q| q < N_QUALITIES),
-            "All quality zones must be below N_QUALITIES: {N_QUALITIES}"
+            custom_zones.len() == custom_zones.iter().map(|cz| cz.id).unique().count(),
+            "Custom zones must have unique ids"
         );
         let quality_zones = {
-            let mut qz = <[_; N_QUALITIES]>::default();
+            let n_qualities = quality_zones.iter().map(|qz| qz.quality + 1).max();
+            let mut qz = vec![None; n_qualities.unwrap_or(1)];
             for q in quality_zones {
                 let quality = q.quality;
                 qz[quality] = Some(q);
@@ -64,8 +73,13 @@ impl Container {
                 .flatten()
                 .flat_map(|qz| qz.to_hazards());
             hazards.extend(qz_hazards);
-            let base_cde = CDEngine::new(outer.bbox.inflate_to_square(), hazards, cde_config);
-            Arc::new(base_cde)
+            hazards.extend(custom_zones.iter().map(|cz| cz.to_hazard(false)));
+            Arc::new(CDEngine::new(
+                outer.bbox.inflate_to_square(),
+                hazards,
+                grain_zones.clone(),
+                cde_config,
+            ))
         };
 
         Ok(Self {
@@ -73,6 +87,8 @@ impl Container {
             outer_cd: outer,
             outer_orig,
             quality_zones,
+            custom_zones,
+            grain_zones,
             base_cde,
         })
     }
@@ -81,10 +97,12 @@ impl Container {
     pub fn area(&self) -> f32 {
         self.outer_orig.area() - self.quality_zones[0].as_ref().map_or(0.0, |qz| qz.area())
     }
-}
 
-/// Maximum number of qualities that can be used for quality zones in a container.
-pub const N_QUALITIES: usize = 10;
+    /// Number of quality levels in the container (including 0, for holes)
+    pub fn n_qualities(&self) -> usize {
+        self.quality_zones.len()
+    }
+}
 
 /// Represents a zone of inferior quality in the [`Container`]
 #[derive(Clone, Debug)]
@@ -99,10 +117,6 @@ pub struct InferiorQualityZone {
 
 impl InferiorQualityZone {
     pub fn new(quality: usize, original_shapes: Vec<OriginalShape>) -> Result<Self> {
-        assert!(
-            quality < N_QUALITIES,
-            "Quality must be in range of N_QUALITIES"
-        );
         let shapes: Result<Vec<Arc<SPolygon>>> = original_shapes
             .iter()
             .map(|orig| orig.convert_to_internal().map(Arc::new))
@@ -135,3 +149,126 @@ impl InferiorQualityZone {
         self.shapes_orig.iter().map(|shape| shape.area()).sum()
     }
 }
+
+/// Represents a user-defined zone (e.g. clamps, suction cups or marker zones) which induces a [`HazardEntity::Custom`].
+/// Can be part of the [`Container`] or registered at runtime in a [`Layout`](crate::entities::Layout).
+#[derive(Clone, Debug)]
+pub struct CustomZone {
+    /// Unique identifier of the zone
+    pub id: usize,
+    /// User-defined tag, e.g. to distinguish between different kinds of zones
+    pub tag: usize,
+    /// Whether the hazard is induced in the interior or the exterior of the zone's shape
+    pub scope: GeoPosition,
+    /// The items for which the zone is forbidden
+    pub item_rule: ZoneItemRule,
+    /// Contour of the zone as defined in the input
+    pub shape_orig: Arc<OriginalShape>,
+    /// Contour of the zone to be used for collision detection
+    pub shape_cd: Arc<SPolygon>,
+}
+
+impl CustomZone {
+    pub fn new(
+        id: usize,
+        tag: usize,
+        scope: GeoPosition,
+        item_rule: ZoneItemRule,
+        original_shape: OriginalShape,
+    ) -> Result<Self> {
+        let expected_mode = match scope {
+            GeoPosition::Interior => ShapeModifyMode::Inflate,
+            GeoPosition::Exterior => ShapeModifyMode::Deflate,
+        };
+        ensure!(
+            original_shape.modify_mode == expected_mode,
+            "Custom zones with scope {scope:?} should be modified in {expected_mode:?} mode"
+        );
+        let shape_cd = Arc::new(original_shape.convert_to_internal()?);
+
+        Ok(Self {
+            id,
+            tag,
+            scope,
+            item_rule,
+            shape_orig: Arc::new(original_shape),
+            shape_cd,
+        })
+    }
+
+    pub fn entity(&self) -> HazardEntity {
+        HazardEntity::Custom {
+            id: self.id,
+            tag: self.tag,
+            scope: self.scope,
+        }
+    }
+
+    /// Returns the hazard induced by this zone.
+    pub fn to_hazard(&self, dynamic: bool) -> Hazard {
+        Hazard::new(self.entity(), self.shape_cd.as_ref().clone(), dynamic)
+            .with_item_rule(self.item_rule.clone())
+    }
+}
+
+/// Defines for which items a [`CustomZone`] is forbidden.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum ZoneItemRule {
+    /// Forbidden for all items
+    #[default]
+    All,
+    /// Forbidden only for the selected items, all others can use the zone freely
+    ForbiddenFor(ItemSelector),
+    /// Reserved for the selected items, forbidden for all others
+    AllowedFor(ItemSelector),
+}
+
+impl ZoneItemRule {
+    /// Whether the zone is forbidden for the item
+    pub fn forbids(&self, item: &Item) -> bool {
+        match self {
+            ZoneItemRule::All => true,
+            ZoneItemRule::ForbiddenFor(selector) => selector.selects(item),
+            ZoneItemRule::AllowedFor(selector) => !selector.selects(item),
+        }
+    }
+}
+
+/// Selects items by their id or by their class.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ItemSelector {
+    pub item_ids: Vec<usize>,
+    pub item_classes: Vec<usize>,
+}
+
+impl ItemSelector {
+    pub fn selects(&self, item: &Item) -> bool {
+        self.item_ids.contains(&item.id)
+            || item
+                .class
+                .is_some_and(|class| self.item_classes.contains(&class))
+    }
+}
+
+/// Represents a region of the [`Container`] with its own set of allowed rotations, e.g. due to the local grain direction of the material.
+/// Only affects items which are [grain sensitive](crate::entities::Item::grain_sensitive).
+#[derive(Clone, Debug)]
+pub struct GrainZone {
+    /// Rotations allowed for grain sensitive items overlapping this zone
+    pub allowed_rotation: RotationRange,
+    /// Contour of the zone as defined in the input
+    pub shape_orig: Arc<OriginalShape>,
+    /// Contour of the zone to be used for collision detection
+    pub shape_cd: Arc<SPolygon>,
+}
+
+impl GrainZone {
+    pub fn new(allowed_rotation: RotationRange, original_shape: OriginalShape) -> Result<Self> {
+        let shape_cd = Arc::new(original_shape.convert_to_internal()?);
+        Ok(GrainZone {
+            allowed_rotation,
+            shape_orig: Arc::new(original_shape),
+            shape_cd,
+        })
+    }
+}
@@ -19,8 +19,16 @@ pub struct Item {
     pub allowed_rotation: RotationRange,
     /// The minimum quality the item should be produced out of, if `None` the item requires full quality
     pub min_quality: Option<usize>,
+    /// Optional class of the item, used to select items in [`ZoneItemRule`](crate::entities::ZoneItemRule)s
+    pub class: Option<usize>,
+    /// Regions of the item with stricter quality requirements than [`Item::min_quality`]
+    pub quality_regions: Vec<ItemQualityRegion>,
+    /// Whether the item has to respect the allowed rotations of the [`GrainZone`](crate::entities::GrainZone)s it overlaps
+    pub grain_sensitive: bool,
     /// Configuration for the surrogate generation
     pub surrogate_config: SPSurrogateConfig,
+    /// Weight of the item, counts towards the weight capacity of the container it is placed in (if any)
+    pub weight: f32,
 }
 
 impl Item {
@@ -29,6 +37,8 @@ impl Item {
         original_shape: OriginalShape,
         allowed_rotation: RotationRange,
         min_quality: Option<usize>,
+        class: Option<usize>,
+        quality_regions: Vec<ItemQualityRegion>,
         surrogate_config: SPSurrogateConfig,
     ) -> Result<Item> {
         let shape_orig = Arc::new(original_shape);
@@ -43,11 +53,50 @@ impl Item {
             shape_cd: shape_int,
             allowed_rotation,
             min_quality,
+            class,
+            quality_regions,
+            grain_sensitive: false,
             surrogate_config,
+            weight: 0.0,
         })
     }
 
+    /// Marks the item as (in)sensitive to the [`GrainZone`](crate::entities::GrainZone)s of the container
+    pub fn with_grain_sensitive(mut self, grain_sensitive: bool) -> Self {
+        self.grain_sensitive = grain_sensitive;
+        self
+    }
+
+    /// Sets the weight of the item
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
     pub fn area(&self) -> f32 {
         self.shape_orig.area()
     }
 }
+
+/// A region of an [`Item`] with its own quality requirement, e.g. the visible face of a leather part.
+/// Its shape is defined in the same reference frame as the shape of the item.
+#[derive(Clone, Debug)]
+pub struct ItemQualityRegion {
+    /// The minimum quality required underneath this region, if `None` the region requires full quality
+    pub min_quality: Option<usize>,
+    /// Original contour of the region as defined in the input
+    pub shape_orig: Arc<OriginalShape>,
+    /// Contour of the region to be used for collision detection
+    pub shape_cd: Arc<SPolygon>,
+}
+
+impl ItemQualityRegion {
+    pub fn new(min_quality: Option<usize>, original_shape: OriginalShape) -> Result<Self> {
+        let shape_cd = Arc::new(original_shape.convert_to_internal()?);
+        Ok(ItemQualityRegion {
+            min_quality,
+            shape_orig: Arc::new(original_shape),
+            shape_cd,
+        })
+    }
+}
@@ -1,23 +1,31 @@
-use crate::collision_detection::hazards::Hazard;
+use crate::collision_detection::hazards::{Hazard, HazardEntity};
 use crate::collision_detection::{CDESnapshot, CDEngine};
 use crate::entities::Item;
-use crate::entities::{Container, Instance};
-use crate::entities::{PItemKey, PlacedItem};
+use crate::entities::remnant::find_largest_remnant;
+use crate::entities::{Container, CustomZone, Instance};
+use crate::entities::{PItemKey, PlacedItem, Remnant, RemnantConfig};
 use crate::geometry::DTransformation;
 use crate::util::assertions;
-use slotmap::SlotMap;
+use anyhow::{Result, bail, ensure};
+use serde::de::{DeserializeSeed, Error};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use slotmap::{SecondaryMap, SlotMap};
+use std::ops::Index;
 
 /// A [`Layout`] is a dynamic representation of items that have been placed in a container at specific positions.
 /// Items can be placed and removed. The container can be swapped. Snapshots can be taken and restored to.
+/// Alternatively, changes can be grouped in a transaction ([`Layout::begin`]), which can be undone using [`Layout::rollback`].
 /// Each layout maintains a [`CDEngine`], which can be used to check for collisions before placing items.
 #[derive(Clone)]
 pub struct Layout {
     /// The container used for this layout
     pub container: Container,
     /// All the items that have been placed in this layout, indexed by a unique key
-    pub placed_items: SlotMap<PItemKey, PlacedItem>,
+    pub placed_items: PlacedItems,
     /// The collision detection engine for this layout
     cde: CDEngine,
+    /// The open transaction, if any
+    tx: Option<Transaction>,
 }
 
 impl Layout {
@@ -25,8 +33,9 @@ impl Layout {
         let cde = container.base_cde.as_ref().clone();
         Layout {
             container,
-            placed_items: SlotMap::with_key(),
+            placed_items: PlacedItems::default(),
             cde,
+            tx: None,
         }
     }
 
@@ -38,6 +47,10 @@ impl Layout {
 
     /// Replaces the current container with a new one, rebuilding the collision detection engine accordingly.
     pub fn swap_container(&mut self, container: Container) {
+        assert!(
+            self.tx.is_none(),
+            "cannot swap container during a transaction"
+        );
         let cde_snapshot = self.cde.save();
         // rebuild the CDE
         self.container = container;
@@ -52,7 +65,7 @@ impl Layout {
     pub fn save(&self) -> LayoutSnapshot {
         LayoutSnapshot {
             container: self.container.clone(),
-            placed_items: self.placed_items.clone(),
+            placed_items: self.placed_items.to_slotmap(),
             cde_snapshot: self.cde.save(),
         }
     }
@@ -60,7 +73,11 @@ impl Layout {
     /// Restores the layout to a previous state using a snapshot.
     pub fn restore(&mut self, layout_snapshot: &LayoutSnapshot) {
         assert_eq!(self.container.id, layout_snapshot.container.id);
-        self.placed_items = layout_snapshot.placed_items.clone();
+        assert!(
+            self.tx.is_none(),
+            "cannot restore a snapshot during a transaction"
+        );
+        self.placed_items = PlacedItems::from(layout_snapshot.placed_items.clone());
         self.cde.restore(&layout_snapshot.cde_snapshot);
 
         debug_assert!(assertions::layout_qt_matches_fresh_qt(self));
@@ -70,9 +87,29 @@ impl Layout {
     /// Places an item in the layout at a specific position by applying a transformation.
     /// Returns the unique key for the placed item.
     pub fn place_item(&mut self, item: &Item, d_transformation: DTransformation) -> PItemKey {
-        let pk = self
-            .placed_items
-            .insert(PlacedItem::new(item, d_transformation));
+        let pk = self.insert_placed_item(PlacedItem::new(item, d_transformation));
+        if let Some(tx) = self.tx.as_mut() {
+            tx.placed.push(pk);
+        }
+        pk
+    }
+
+    /// Places an immovable item in the layout, e.g. a part that was placed by hand beforehand.
+    /// Returns the unique key for the placed item.
+    pub fn place_fixed_item(&mut self, item: &Item, d_transformation: DTransformation) -> PItemKey {
+        let placed_item = PlacedItem {
+            fixed: true,
+            ..PlacedItem::new(item, d_transformation)
+        };
+        let pk = self.insert_placed_item(placed_item);
+        if let Some(tx) = self.tx.as_mut() {
+            tx.placed.push(pk);
+        }
+        pk
+    }
+
+    fn insert_placed_item(&mut self, placed_item: PlacedItem) -> PItemKey {
+        let pk = self.placed_items.slots.insert(placed_item);
         let pi = &self.placed_items[pk];
         let hazard = Hazard::new((pk, pi).into(), pi.shape.clone(), true);
 
@@ -87,11 +124,30 @@ impl Layout {
     /// If `commit_instant` is true, the removal is immediately fully executed to the collision detection engine.
     /// If false, the item is disabled in the collision detection engine, but not yet fully removed.
     /// Useful for scenarios with high probability of reverting the removal.
+    /// During a transaction, items present at its start keep their slot until [`Layout::commit`].
     pub fn remove_item(&mut self, pk: PItemKey) -> PlacedItem {
-        let pi = self
-            .placed_items
-            .remove(pk)
-            .expect("key is not valid anymore");
+        assert!(
+            self.placed_items.contains_key(pk),
+            "key is not valid anymore"
+        );
+        let placed_during_tx =
+            self.tx
+                .as_mut()
+                .map(|tx| match tx.placed.iter().position(|&p| p == pk) {
+                    Some(idx) => {
+                        tx.placed.swap_remove(idx);
+                        true
+                    }
+                    None => false,
+                });
+        let pi = match placed_during_tx {
+            //present before the transaction, its slot is kept until the transaction is committed
+            Some(false) => {
+                self.placed_items.removed.insert(pk, ());
+                self.placed_items.slots[pk].clone()
+            }
+            _ => self.placed_items.slots.remove(pk).unwrap(),
+        };
 
         // update the collision detection engine
         self.cde.deregister_hazard_by_entity((pk, &pi).into());
@@ -101,6 +157,80 @@ impl Layout {
         pi
     }
 
+    /// Registers a user-defined zone in the layout at runtime.
+    /// Unlike the zones of the container, it is included in snapshots and can be deregistered again.
+    pub fn register_custom_zone(&mut self, zone: &CustomZone) -> Result<()> {
+        assert!(
+            self.tx.is_none(),
+            "cannot register custom zones during a transaction"
+        );
+        ensure!(
+            !self
+                .cde
+                .hazards()
+                .any(|h| matches!(h.entity, HazardEntity::Custom { id, .. } if id == zone.id)),
+            "custom zone with id {} already registered",
+            zone.id
+        );
+        self.cde.register_hazard(zone.to_hazard(true));
+        Ok(())
+    }
+
+    /// Deregisters a user-defined zone, previously registered with [`Layout::register_custom_zone`].
+    /// Returns the hazard it induced.
+    pub fn deregister_custom_zone(&mut self, id: usize) -> Result<Hazard> {
+        assert!(
+            self.tx.is_none(),
+            "cannot deregister custom zones during a transaction"
+        );
+        let entity = self
+            .cde
+            .hazards()
+            .filter(|h| h.dynamic)
+            .map(|h| h.entity)
+            .find(|e| matches!(e, HazardEntity::Custom { id: cz_id, .. } if *cz_id == id));
+        match entity {
+            Some(entity) => Ok(self.cde.deregister_hazard_by_entity(entity)),
+            None => bail!("no custom zone with id {id} registered at runtime"),
+        }
+    }
+
+    /// Starts a transaction: all subsequent placements and removals are tracked until
+    /// [`Layout::commit`] or [`Layout::rollback`] is called. Transactions cannot be nested.
+    pub fn begin(&mut self) {
+        assert!(self.tx.is_none(), "transaction already in progress");
+        self.tx = Some(Transaction::default());
+    }
+
+    /// Ends the open transaction, keeping all changes made since [`Layout::begin`].
+    pub fn commit(&mut self) {
+        assert!(self.tx.is_some(), "no transaction in progress");
+        self.tx = None;
+        for (pk, _) in self.placed_items.removed.drain() {
+            self.placed_items.slots.remove(pk);
+        }
+    }
+
+    /// Ends the open transaction, undoing all changes made since [`Layout::begin`] in O(changes).
+    /// Items present at the start of the transaction keep their [`PItemKey`].
+    pub fn rollback(&mut self) {
+        let tx = self.tx.take().expect("no transaction in progress");
+        for pk in tx.placed {
+            self.remove_item(pk);
+        }
+        for (pk, _) in self.placed_items.removed.drain() {
+            let pi = &self.placed_items.slots[pk];
+            let hazard = Hazard::new((pk, pi).into(), pi.shape.clone(), true);
+            self.cde.register_hazard(hazard);
+        }
+        debug_assert!(assertions::layout_qt_matches_fresh_qt(self));
+    }
+
+    /// True if a transaction is in progress
+    pub fn in_transaction(&self) -> bool {
+        self.tx.is_some()
+    }
+
     /// True if no items are placed
     pub fn is_empty(&self) -> bool {
         self.placed_items.is_empty()
@@ -121,6 +251,25 @@ impl Layout {
             .sum::<f32>()
     }
 
+    /// The sum of the weights of the items placed in the layout.
+    pub fn placed_item_weight(&self, instance: &impl Instance) -> f32 {
+        self.placed_items
+            .values()
+            .map(|pi| instance.item(pi.item_id).weight)
+            .sum::<f32>()
+    }
+
+    /// Returns the largest unused part of the container which can be reused, if any.
+    /// Placed items, holes and zones are approximated by their bounding box, see [`Remnant`].
+    pub fn largest_remnant(&self, config: RemnantConfig) -> Option<Remnant> {
+        find_largest_remnant(
+            self.container.outer_cd.bbox,
+            self.cde.hazards(),
+            &self.cde,
+            config,
+        )
+    }
+
     /// Returns the collision detection engine for this layout
     pub fn cde(&self) -> &CDEngine {
         &self.cde
@@ -138,8 +287,87 @@ impl Layout {
     }
 }
 
+/// The changes made to a [`Layout`] during a transaction, needed to undo them.
+/// Removals of items present at the start of the transaction are tracked in [`PlacedItems`].
+#[derive(Clone, Debug, Default)]
+struct Transaction {
+    /// Items placed during the transaction, which have not been removed since
+    placed: Vec<PItemKey>,
+}
+
+/// The items placed in a [`Layout`], indexed by their [`PItemKey`].
+/// Items removed during an open transaction are hidden, but keep their slot until the transaction is committed.
+#[derive(Clone, Debug, Default)]
+pub struct PlacedItems {
+    slots: SlotMap<PItemKey, PlacedItem>,
+    /// Items present at the start of the open transaction which have been removed since
+    removed: SecondaryMap<PItemKey, ()>,
+}
+
+impl PlacedItems {
+    pub fn get(&self, pk: PItemKey) -> Option<&PlacedItem> {
+        match self.removed.contains_key(pk) {
+            true => None,
+            false => self.slots.get(pk),
+        }
+    }
+
+    pub fn contains_key(&self, pk: PItemKey) -> bool {
+        self.get(pk).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (PItemKey, &PlacedItem)> {
+        self.slots
+            .iter()
+            .filter(|(pk, _)| !self.removed.contains_key(*pk))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = PItemKey> {
+        self.iter().map(|(pk, _)| pk)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &PlacedItem> {
+        self.iter().map(|(_, pi)| pi)
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.removed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Copy of all (non-hidden) items, under the same keys
+    pub fn to_slotmap(&self) -> SlotMap<PItemKey, PlacedItem> {
+        let mut slots = self.slots.clone();
+        self.removed.keys().for_each(|pk| {
+            slots.remove(pk);
+        });
+        slots
+    }
+}
+
+impl From<SlotMap<PItemKey, PlacedItem>> for PlacedItems {
+    fn from(slots: SlotMap<PItemKey, PlacedItem>) -> Self {
+        PlacedItems {
+            slots,
+            removed: SecondaryMap::new(),
+        }
+    }
+}
+
+impl Index<PItemKey> for PlacedItems {
+    type Output = PlacedItem;
+
+    fn index(&self, pk: PItemKey) -> &PlacedItem {
+        self.get(pk).expect("invalid PItemKey")
+    }
+}
+
 /// Immutable and compact representation of a [`Layout`].
 /// Can be used to restore a [`Layout`] back to a previous state.
+/// Serializes without its container (only the id), deserialize using [`LayoutSnapshotSeed`].
 #[derive(Clone, Debug)]
 pub struct LayoutSnapshot {
     /// A copy of the container used in the layout
@@ -164,4 +392,419 @@ impl LayoutSnapshot {
             .map(|item| item.area())
             .sum::<f32>()
     }
+
+    /// Equivalent to [`Layout::largest_remnant`]
+    pub fn largest_remnant(&self, config: RemnantConfig) -> Option<Remnant> {
+        let base_cde = self.container.base_cde.as_ref();
+        find_largest_remnant(
+            self.container.outer_cd.bbox,
+            base_cde
+                .hazards()
+                .chain(self.cde_snapshot.dynamic_hazards.iter()),
+            base_cde,
+            config,
+        )
+    }
+
+    /// Equivalent to [`Layout::placed_item_weight`]
+    pub fn placed_item_weight(&self, instance: &impl Instance) -> f32 {
+        self.placed_items
+            .values()
+            .map(|pi| instance.item(pi.item_id).weight)
+            .sum::<f32>()
+    }
+
+    /// Rebuilds a snapshot from its deserialized representation against the `instance` it belongs to.
+    /// The shapes of the placed items are regenerated from the items of the instance,
+    /// after which the snapshot is verified.
+    pub(crate) fn rebuild(
+        data: LayoutSnapshotData,
+        container: Container,
+        instance: &impl Instance,
+    ) -> Result<Self> {
+        let LayoutSnapshotData {
+            container_id,
+            mut placed_items,
+            cde_snapshot,
+        } = data;
+        ensure!(
+            container_id == container.id,
+            "snapshot refers to container {container_id}, but container {} was provided",
+            container.id
+        );
+        let n_items = instance.items().count();
+        for pi in placed_items.values_mut() {
+            ensure!(
+                pi.item_id < n_items,
+                "snapshot refers to unknown item {}",
+                pi.item_id
+            );
+            *pi = PlacedItem {
+                fixed: pi.fixed,
+                ..PlacedItem::new(instance.item(pi.item_id), pi.d_transf)
+            };
+        }
+        let snapshot = LayoutSnapshot {
+            container,
+            placed_items,
+            cde_snapshot,
+        };
+        ensure!(
+            assertions::snapshot_hazards_match_placed_items(&snapshot),
+            "hazards of the CDE snapshot do not match the placed items"
+        );
+
+        let layout = Layout::from_snapshot(&snapshot);
+        ensure!(
+            assertions::layouts_match(&layout, &snapshot),
+            "layout rebuilt from snapshot does not match the snapshot"
+        );
+        ensure!(
+            assertions::layout_qt_matches_fresh_qt(&layout),
+            "quadtree rebuilt from snapshot is inconsistent"
+        );
+
+        Ok(snapshot)
+    }
+}
+
+impl Serialize for LayoutSnapshot {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct LayoutSnapshotRef<'a> {
+            container_id: usize,
+            placed_items: &'a SlotMap<PItemKey, PlacedItem>,
+            cde_snapshot: &'a CDESnapshot,
+        }
+        LayoutSnapshotRef {
+            container_id: self.container.id,
+            placed_items: &self.placed_items,
+            cde_snapshot: &self.cde_snapshot,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserialized representation of a [`LayoutSnapshot`], still to be rebuilt against an instance.
+#[derive(Deserialize)]
+pub(crate) struct LayoutSnapshotData {
+    pub container_id: usize,
+    pub placed_items: SlotMap<PItemKey, PlacedItem>,
+    pub cde_snapshot: CDESnapshot,
+}
+
+/// Deserializes a [`LayoutSnapshot`] and rebuilds it against the instance it belongs to.
+pub struct LayoutSnapshotSeed<'a, I: Instance> {
+    pub instance: &'a I,
+    /// The container the snapshot was taken of
+    pub container: Container,
+}
+
+impl<'de, I: Instance> DeserializeSeed<'de> for LayoutSnapshotSeed<'_, I> {
+    type Value = LayoutSnapshot;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let data = LayoutSnapshotData::deserialize(deserializer)?;
+        LayoutSnapshot::rebuild(data, self.container, self.instance)
+            .map_err(|e| D::Error::custom(format!("{e:#}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision_detection::hazards::filter::NoFilter;
+    use crate::entities::ZoneItemRule;
+    use crate::geometry::geo_enums::GeoPosition;
+    use crate::geometry::geo_traits::Transformable;
+    use crate::geometry::primitives::Rect;
+    use crate::geometry::shape_modification::ShapeModifyMode;
+    use crate::util::test_util::{cde_config, container, item, rect, rect_shape};
+
+    /// Layout with two items placed before any transaction, and the keys of those items
+    fn layout_with_two_items() -> (Layout, [PItemKey; 2]) {
+        let mut layout = Layout::new(container(0, 100.0, 100.0));
+        let pk_a = layout.place_item(&item(0, 10.0, 10.0), DTransformation::new(0.0, (0.0, 0.0)));
+        let pk_b = layout.place_item(&item(1, 10.0, 10.0), DTransformation::new(0.0, (20.0, 0.0)));
+        (layout, [pk_a, pk_b])
+    }
+
+    /// The placed items (by key) and their positions
+    fn contents(layout: &Layout) -> Vec<(PItemKey, usize, DTransformation)> {
+        let mut contents = layout
+            .placed_items
+            .iter()
+            .map(|(pk, pi)| (pk, pi.item_id, pi.d_transf))
+            .collect::<Vec<_>>();
+        contents.sort_by_key(|(_, id, _)| *id);
+        contents
+    }
+
+    fn assert_restored(layout: &Layout, before: &[(PItemKey, usize, DTransformation)]) {
+        assert!(!layout.in_transaction());
+        assert_eq!(contents(layout), before);
+        assert!(assertions::layout_qt_matches_fresh_qt(layout));
+    }
+
+    #[test]
+    fn rollback_placement() {
+        let (mut layout, _) = layout_with_two_items();
+        let before = contents(&layout);
+
+        layout.begin();
+        layout.place_item(&item(2, 10.0, 10.0), DTransformation::new(0.0, (40.0, 0.0)));
+        layout.rollback();
+        assert_restored(&layout, &before);
+    }
+
+    #[test]
+    fn rollback_removal() {
+        let (mut layout, [pk_a, pk_b]) = layout_with_two_items();
+        let before = contents(&layout);
+
+        layout.begin();
+        layout.remove_item(pk_a);
+        layout.remove_item(pk_b);
+        layout.rollback();
+        assert_restored(&layout, &before);
+
+        //the keys held before the transaction remain valid
+        assert_eq!(layout.remove_item(pk_a).item_id, 0);
+    }
+
+    #[test]
+    fn rollback_removal_of_existing_item() {
+        let (mut layout, [pk_a, _]) = layout_with_two_items();
+        let before = contents(&layout);
+
+        layout.begin();
+        layout.remove_item(pk_a);
+        //hidden from the layout and its CDE while the transaction is open
+        assert!(!layout.placed_items.contains_key(pk_a));
+        assert_eq!(layout.placed_items.len(), 1);
+        assert!(layout.cde().haz_key_from_pi_key(pk_a).is_none());
+        assert!(!collides(&layout, (1.0, 1.0)));
+        assert!(assertions::layout_qt_matches_fresh_qt(&layout));
+
+        layout.rollback();
+        assert_restored(&layout, &before);
+        assert_eq!(layout.placed_items[pk_a].item_id, 0);
+        assert!(layout.cde().haz_key_from_pi_key(pk_a).is_some());
+        assert!(collides(&layout, (1.0, 1.0)));
+    }
+
+    #[test]
+    fn commit_removal_of_existing_item() {
+        let (mut layout, [pk_a, _]) = layout_with_two_items();
+
+        layout.begin();
+        layout.remove_item(pk_a);
+        layout.commit();
+        assert!(!layout.placed_items.contains_key(pk_a));
+        assert_eq!(layout.placed_items.len(), 1);
+        assert_eq!(layout.save().placed_items.len(), 1);
+        assert!(!collides(&layout, (1.0, 1.0)));
+        assert!(assertions::layout_qt_matches_fresh_qt(&layout));
+    }
+
+    #[test]
+    fn rollback_placement_and_removal() {
+        let (mut layout, [pk_a, _]) = layout_with_two_items();
+        let before = contents(&layout);
+
+        //an item placed and removed again within the same transaction
+        layout.begin();
+        let pk_c = layout.place_item(&item(2, 10.0, 10.0), DTransformation::new(0.0, (40.0, 0.0)));
+        layout.remove_item(pk_c);
+        layout.rollback();
+        assert_restored(&layout, &before);
+
+        //interleaved with the removal of an item present before the transaction
+        layout.begin();
+        let pk_c = layout.place_item(&item(2, 10.0, 10.0), DTransformation::new(0.0, (40.0, 0.0)));
+        layout.remove_item(pk_a);
+        let pk_d = layout.place_item(&item(3, 10.0, 10.0), DTransformation::new(0.0, (0.0, 0.0)));
+        layout.remove_item(pk_c);
+        assert_eq!(layout.placed_items.len(), 2);
+        assert!(layout.placed_items.contains_key(pk_d));
+        layout.rollback();
+        assert_restored(&layout, &before);
+    }
+
+    #[test]
+    fn repeated_transactions() {
+        let (mut layout, [pk_a, _]) = layout_with_two_items();
+        let before = contents(&layout);
+
+        layout.begin();
+        layout.remove_item(pk_a);
+        layout.rollback();
+
+        //committed changes are kept, and are the new starting point of the next transaction
+        layout.begin();
+        let pk_c = layout.place_item(&item(2, 10.0, 10.0), DTransformation::new(0.0, (40.0, 0.0)));
+        layout.commit();
+        let committed = contents(&layout);
+        assert_eq!(committed.len(), before.len() + 1);
+
+        layout.begin();
+        layout.remove_item(pk_c);
+        layout.remove_item(pk_a);
+        layout.rollback();
+        assert_restored(&layout, &committed);
+    }
+
+    #[test]
+    #[should_panic(expected = "transaction already in progress")]
+    fn nested_transaction() {
+        let (mut layout, _) = layout_with_two_items();
+        layout.begin();
+        layout.begin();
+    }
+
+    fn custom_zone(id: usize, scope: GeoPosition, r: Rect) -> CustomZone {
+        let modify_mode = match scope {
+            GeoPosition::Interior => ShapeModifyMode::Inflate,
+            GeoPosition::Exterior => ShapeModifyMode::Deflate,
+        };
+        CustomZone::new(id, 0, scope, ZoneItemRule::All, rect_shape(r, modify_mode)).unwrap()
+    }
+
+    /// Whether a 10 by 10 square at `pos` collides with anything in the layout
+    fn collides(layout: &Layout, pos: (f32, f32)) -> bool {
+        let shape = item(0, 10.0, 10.0)
+            .shape_cd
+            .transform_clone(&DTransformation::new(0.0, pos).compose());
+        layout.cde().detect_poly_collision(&shape, &NoFilter)
+    }
+
+    #[test]
+    fn runtime_custom_zones() {
+        let mut layout = Layout::new(container(0, 100.0, 100.0));
+        let empty = layout.save();
+
+        //a clamp in the bottom left corner and a suction area covering only the right half
+        let clamp = custom_zone(0, GeoPosition::Interior, rect(0.0, 0.0, 20.0, 20.0));
+        let suction = custom_zone(1, GeoPosition::Exterior, rect(50.0, 0.0, 100.0, 100.0));
+        layout.register_custom_zone(&clamp).unwrap();
+        assert!(collides(&layout, (5.0, 5.0)));
+        assert!(!collides(&layout, (30.0, 5.0)));
+        layout.register_custom_zone(&suction).unwrap();
+        assert!(collides(&layout, (30.0, 5.0)));
+        assert!(!collides(&layout, (60.0, 5.0)));
+
+        //ids are unique
+        assert!(layout.register_custom_zone(&clamp).is_err());
+
+        //runtime zones are part of snapshots
+        let with_zones = layout.save();
+        layout.restore(&empty);
+        assert!(!collides(&layout, (5.0, 5.0)));
+        layout.restore(&with_zones);
+        assert!(collides(&layout, (5.0, 5.0)));
+
+        let hazard = layout.deregister_custom_zone(0).unwrap();
+        assert_eq!(hazard.entity, clamp.entity());
+        layout.deregister_custom_zone(1).unwrap();
+        assert!(!collides(&layout, (5.0, 5.0)));
+        assert!(layout.deregister_custom_zone(0).is_err());
+        assert!(assertions::layout_qt_matches_fresh_qt(&layout));
+    }
+
+    #[test]
+    fn container_custom_zones_not_deregistrable() {
+        let zone = custom_zone(0, GeoPosition::Interior, rect(0.0, 0.0, 20.0, 20.0));
+        let shape = rect_shape(rect(0.0, 0.0, 100.0, 100.0), ShapeModifyMode::Deflate);
+        let container = Container::new(0, shape, vec![], vec![zone], vec![], cde_config()).unwrap();
+        let mut layout = Layout::new(container);
+        assert!(collides(&layout, (5.0, 5.0)));
+        assert!(layout.deregister_custom_zone(0).is_err());
+        assert!(collides(&layout, (5.0, 5.0)));
+    }
+
+    struct TestInstance {
+        items: Vec<Item>,
+        container: Container,
+    }
+
+    impl Instance for TestInstance {
+        fn items(&self) -> impl Iterator<Item = &Item> {
+            self.items.iter()
+        }
+
+        fn containers(&self) -> impl Iterator<Item = &Container> {
+            std::iter::once(&self.container)
+        }
+
+        fn item(&self, id: usize) -> &Item {
+            &self.items[id]
+        }
+
+        fn container(&self, _id: usize) -> &Container {
+            &self.container
+        }
+    }
+
+    #[test]
+    fn snapshot_serde_round_trip() {
+        let instance = TestInstance {
+            items: vec![item(0, 10.0, 10.0), item(1, 20.0, 5.0)],
+            container: container(0, 100.0, 100.0),
+        };
+        let mut layout = Layout::new(instance.container.clone());
+        layout.place_item(instance.item(0), DTransformation::new(0.0, (0.0, 0.0)));
+        layout.place_fixed_item(instance.item(1), DTransformation::new(0.5, (50.0, 50.0)));
+        let pk = layout.place_item(instance.item(0), DTransformation::new(0.0, (80.0, 0.0)));
+        //leaves a hole in the slotmap, the keys should survive regardless
+        layout.remove_item(pk);
+        layout.place_item(instance.item(1), DTransformation::new(1.0, (30.0, 70.0)));
+        let snapshot = layout.save();
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let seed = LayoutSnapshotSeed {
+            instance: &instance,
+            container: instance.container.clone(),
+        };
+        let deserialized = seed
+            .deserialize(&mut serde_json::Deserializer::from_str(&json))
+            .unwrap();
+
+        assert_eq!(deserialized.container.id, snapshot.container.id);
+        assert_eq!(deserialized.placed_items.len(), snapshot.placed_items.len());
+        for (pk, pi) in snapshot.placed_items.iter() {
+            let de_pi = &deserialized.placed_items[pk];
+            assert_eq!(de_pi.item_id, pi.item_id);
+            assert_eq!(de_pi.d_transf, pi.d_transf);
+            assert_eq!(de_pi.fixed, pi.fixed);
+            assert_eq!(de_pi.shape.vertices, pi.shape.vertices);
+        }
+        let entities = |ls: &LayoutSnapshot| {
+            ls.cde_snapshot
+                .dynamic_hazards
+                .iter()
+                .map(|h| h.entity)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(entities(&deserialized), entities(&snapshot));
+        //serializing again yields the same representation
+        assert_eq!(serde_json::to_string(&deserialized).unwrap(), json);
+    }
+
+    #[test]
+    fn snapshot_of_other_container_rejected() {
+        let instance = TestInstance {
+            items: vec![item(0, 10.0, 10.0)],
+            container: container(0, 100.0, 100.0),
+        };
+        let mut layout = Layout::new(instance.container.clone());
+        layout.place_item(instance.item(0), DTransformation::new(0.0, (0.0, 0.0)));
+        let json = serde_json::to_string(&layout.save()).unwrap();
+
+        let seed = LayoutSnapshotSeed {
+            instance: &instance,
+            container: container(1, 100.0, 100.0),
+        };
+        let result = seed.deserialize(&mut serde_json::Deserializer::from_str(&json));
+        assert!(result.is_err());
+    }
 }
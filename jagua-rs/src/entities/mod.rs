@@ -3,6 +3,7 @@ mod instance;
 mod item;
 mod layout;
 mod placed_item;
+mod remnant;
 
 #[doc(inline)]
 pub use instance::Instance;
@@ -10,15 +11,39 @@ pub use instance::Instance;
 #[doc(inline)]
 pub use item::Item;
 
+#[doc(inline)]
+pub use item::ItemQualityRegion;
+
 #[doc(inline)]
 pub use layout::Layout;
 
 #[doc(inline)]
 pub use layout::LayoutSnapshot;
 
+#[doc(inline)]
+pub use layout::LayoutSnapshotSeed;
+
+#[cfg(any(feature = "spp", feature = "bpp", feature = "kp", feature = "odp"))]
+pub(crate) use layout::LayoutSnapshotData;
+
 #[doc(inline)]
 pub use placed_item::PlacedItem;
 
+#[doc(inline)]
+pub use layout::PlacedItems;
+
+#[doc(inline)]
+pub use remnant::Remnant;
+
+#[doc(inline)]
+pub use remnant::RemnantAxis;
+
+#[doc(inline)]
+pub use remnant::RemnantConfig;
+
+#[doc(inline)]
+pub use remnant::RemnantShape;
+
 #[doc(inline)]
 pub use placed_item::PItemKey;
 
@@ -29,4 +54,13 @@ pub use container::Container;
 pub use container::InferiorQualityZone;
 
 #[doc(inline)]
-pub use container::N_QUALITIES;
+pub use container::CustomZone;
+
+#[doc(inline)]
+pub use container::GrainZone;
+
+#[doc(inline)]
+pub use container::ZoneItemRule;
+
+#[doc(inline)]
+pub use container::ItemSelector;
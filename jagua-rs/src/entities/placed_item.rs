@@ -6,6 +6,7 @@ use slotmap::new_key_type;
 
 #[cfg(doc)]
 use crate::entities::Layout;
+use serde::{Deserialize, Serialize};
 
 new_key_type! {
     /// Unique key for each [`PlacedItem`] in a layout.
@@ -13,7 +14,7 @@ new_key_type! {
 }
 
 /// Represents an [`Item`] that has been placed in a [`Layout`]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PlacedItem {
     /// ID of the type of `Item` that was placed
     pub item_id: usize,
@@ -21,6 +22,9 @@ pub struct PlacedItem {
     pub d_transf: DTransformation,
     /// The shape of the `Item` after it has been transformed and placed in a `Layout`
     pub shape: SPolygon,
+    /// Whether the item was pre-placed and should not be moved or removed
+    #[serde(default)]
+    pub fixed: bool,
 }
 
 impl PlacedItem {
@@ -32,6 +36,7 @@ impl PlacedItem {
             item_id: item.id,
             d_transf,
             shape,
+            fixed: false,
         }
     }
 }
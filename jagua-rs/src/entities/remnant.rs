@@ -0,0 +1,431 @@
+use crate::collision_detection::CDEngine;
+use crate::collision_detection::hazards::Hazard;
+use crate::collision_detection::hazards::filter::NoFilter;
+use crate::geometry::geo_enums::GeoPosition;
+use crate::geometry::primitives::{Point, Rect, SPolygon};
+use itertools::Itertools;
+use ordered_float::OrderedFloat;
+
+/// Unused part of a container which is large enough to be reused, e.g. as a bin in a next job.
+/// Defined in the coordinates of the container.
+///
+/// Items, holes and quality zones are avoided by their bounding box, not by their exact shape.
+/// The free space around non-rectangular shapes is therefore not included in the remnant.
+#[derive(Debug, Clone)]
+pub struct Remnant {
+    /// Contour of the remnant
+    pub shape: SPolygon,
+    /// The remnant as an axis-aligned rectangle, `None` if it is not rectangular
+    pub rect: Option<Rect>,
+}
+
+impl Remnant {
+    pub fn area(&self) -> f32 {
+        self.shape.area
+    }
+}
+
+/// The form in which a [`Remnant`] is cut out of a container
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemnantShape {
+    /// An axis-aligned rectangle
+    Rectangle,
+    /// A staircase-shaped polygon covering the unused end of the container along [`RemnantConfig::axis`].
+    /// Falls back to a rectangle if that one is larger.
+    Polygon,
+}
+
+/// The axis along which a container is filled, the unused end lies in its positive direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RemnantAxis {
+    #[default]
+    X,
+    Y,
+}
+
+/// Configuration for the extraction of a [`Remnant`] from a layout
+#[derive(Debug, Clone, Copy)]
+pub struct RemnantConfig {
+    pub shape: RemnantShape,
+    /// Axis along which a [`RemnantShape::Polygon`] remnant is searched
+    pub axis: RemnantAxis,
+    /// Minimum width of the remnant in both directions, narrower parts are cut off
+    pub min_width: f32,
+}
+
+/// Finds the largest remnant within `bbox`, the bounding box of the container.
+/// Hazards affecting the interior of their shape (items, holes, zones) are avoided as a whole (by their bounding box),
+/// all candidates are verified against `cde` to make sure they lie within the container.
+pub(crate) fn find_largest_remnant<'a>(
+    bbox: Rect,
+    hazards: impl Iterator<Item = &'a Hazard>,
+    cde: &CDEngine,
+    config: RemnantConfig,
+) -> Option<Remnant> {
+    let obstacles = hazards
+        .filter(|h| h.entity.scope() == GeoPosition::Interior)
+        .filter_map(|h| Rect::intersection(h.shape.bbox, bbox))
+        .collect_vec();
+
+    //candidates are slightly shrunk before verification, touching the boundary of the container is not a collision
+    let eps = bbox.diameter() * 1e-4;
+    let is_free = |r: &Rect| match r.resize_by(-eps, -eps) {
+        Some(r) => !cde.detect_poly_collision(&SPolygon::from(r), &NoFilter),
+        None => false,
+    };
+
+    let rect = largest_empty_rect(bbox, &obstacles, config.min_width, is_free);
+    let polygon = match config.shape {
+        RemnantShape::Rectangle => None,
+        RemnantShape::Polygon => match config.axis {
+            RemnantAxis::X => largest_free_tail(bbox, &obstacles, config.min_width, is_free),
+            RemnantAxis::Y => {
+                //mirror everything along the diagonal, so the tail can be searched along the x-axis
+                let obstacles = obstacles.iter().map(transpose).collect_vec();
+                largest_free_tail(transpose(&bbox), &obstacles, config.min_width, |r| {
+                    is_free(&transpose(r))
+                })
+                .and_then(|p| {
+                    SPolygon::new(p.vertices.iter().map(|Point(x, y)| Point(*y, *x)).collect()).ok()
+                })
+            }
+        },
+    };
+
+    match (rect, polygon) {
+        (Some(rect), Some(polygon)) if polygon.area <= rect.area() => Some(Remnant {
+            shape: SPolygon::from(rect),
+            rect: Some(rect),
+        }),
+        (_, Some(polygon)) => Some(Remnant {
+            shape: polygon,
+            rect: None,
+        }),
+        (Some(rect), None) => Some(Remnant {
+            shape: SPolygon::from(rect),
+            rect: Some(rect),
+        }),
+        (None, None) => None,
+    }
+}
+
+/// Largest axis-aligned rectangle within `bbox` which does not overlap any of the obstacles
+fn largest_empty_rect(
+    bbox: Rect,
+    obstacles: &[Rect],
+    min_width: f32,
+    is_free: impl Fn(&Rect) -> bool,
+) -> Option<Rect> {
+    let xs = obstacles
+        .iter()
+        .flat_map(|o| [o.x_min, o.x_max])
+        .chain([bbox.x_min, bbox.x_max])
+        .sorted_by_key(|x| OrderedFloat(*x))
+        .dedup()
+        .collect_vec();
+
+    let mut best: Option<Rect> = None;
+    let best_area = |best: &Option<Rect>| best.map_or(0.0, |r| r.area());
+
+    //every maximal empty rectangle is bounded left and right by an obstacle or the container
+    for (i, &x_min) in xs.iter().enumerate() {
+        for &x_max in xs[i + 1..].iter() {
+            let width = x_max - x_min;
+            if width < min_width || width * bbox.height() <= best_area(&best) {
+                continue;
+            }
+            //the vertical gaps between the obstacles overlapping this range
+            let blocking = obstacles
+                .iter()
+                .filter(|o| o.x_min < x_max && o.x_max > x_min)
+                .map(|o| (o.y_min, o.y_max))
+                .sorted_by_key(|(y_min, _)| OrderedFloat(*y_min));
+            let mut gaps = vec![];
+            let mut y_free = bbox.y_min;
+            for (y_min, y_max) in blocking {
+                gaps.push((y_free, y_min));
+                y_free = y_free.max(y_max);
+            }
+            gaps.push((y_free, bbox.y_max));
+
+            for (y_min, y_max) in gaps {
+                if y_max - y_min < min_width || width * (y_max - y_min) <= best_area(&best) {
+                    continue;
+                }
+                if let Ok(rect) = Rect::try_new(x_min, y_min, x_max, y_max)
+                    && is_free(&rect)
+                {
+                    best = Some(rect);
+                }
+            }
+        }
+    }
+    best
+}
+
+/// Largest staircase-shaped region at the end of the container along the x-axis, beyond all obstacles
+fn largest_free_tail(
+    bbox: Rect,
+    obstacles: &[Rect],
+    min_width: f32,
+    is_free: impl Fn(&Rect) -> bool,
+) -> Option<SPolygon> {
+    //divide the container in horizontal bands, each free from its frontier up to as far as the container allows
+    let mut bands = obstacles
+        .iter()
+        .flat_map(|o| [o.y_min, o.y_max])
+        .chain([bbox.y_min, bbox.y_max])
+        .sorted_by_key(|y| OrderedFloat(*y))
+        .dedup()
+        .tuple_windows()
+        .map(|(y_min, y_max)| {
+            let frontier = obstacles
+                .iter()
+                .filter(|o| o.y_min < y_max && o.y_max > y_min)
+                .map(|o| o.x_max)
+                .fold(bbox.x_min, f32::max);
+            let free_up_to =
+                |x: f32| Rect::try_new(frontier, y_min, x, y_max).is_ok_and(|r| is_free(&r));
+            //bands which are too narrow to be of use (or not free at all) are left empty
+            let x = match (free_up_to(frontier + min_width), free_up_to(bbox.x_max)) {
+                (false, _) => None,
+                (true, true) => Some((frontier, bbox.x_max)),
+                (true, false) => {
+                    //cut off by the container, search how far the band reaches
+                    let (mut lo, mut hi) = (frontier + min_width, bbox.x_max);
+                    while hi - lo > bbox.width() * 1e-3 {
+                        let mid = (lo + hi) / 2.0;
+                        match free_up_to(mid) {
+                            true => lo = mid,
+                            false => hi = mid,
+                        }
+                    }
+                    Some((frontier, lo))
+                }
+            };
+            Band { y_min, y_max, x }
+        })
+        .collect_vec();
+
+    //trim notches which are not high enough, until none remain (shrinking a band keeps it free)
+    loop {
+        bands.dedup_by(|next, prev| match next.x == prev.x {
+            true => {
+                prev.y_max = next.y_max;
+                true
+            }
+            false => false,
+        });
+        let notch = (0..bands.len()).find_map(|i| {
+            let (x_min, x_max) = bands[i].x?;
+            let neighbours = [i.checked_sub(1), Some(i + 1)]
+                .into_iter()
+                .flatten()
+                .filter_map(|j| bands.get(j).and_then(|b| b.x))
+                .collect_vec();
+            let x_min_lim = neighbours.iter().map(|n| n.0).fold(f32::INFINITY, f32::min);
+            let x_max_lim = neighbours
+                .iter()
+                .map(|n| n.1)
+                .fold(f32::NEG_INFINITY, f32::max);
+            match bands[i].height() < min_width && (x_min < x_min_lim || x_max > x_max_lim) {
+                true if !neighbours.is_empty() => {
+                    let (x_min, x_max) = (x_min.max(x_min_lim), x_max.min(x_max_lim));
+                    Some((i, (x_max - x_min >= min_width).then_some((x_min, x_max))))
+                }
+                _ => None,
+            }
+        });
+        match notch {
+            Some((i, x)) => bands[i].x = x,
+            None => break,
+        }
+    }
+
+    //split the bands in runs of non-empty bands which sufficiently overlap each other
+    let mut runs: Vec<Vec<(f32, f32, f32, f32)>> = vec![];
+    let mut prev_x: Option<(f32, f32)> = None;
+    for band in bands.iter() {
+        let Some((x_min, x_max)) = band.x else {
+            prev_x = None;
+            continue;
+        };
+        match prev_x {
+            Some((p_min, p_max)) if p_max.min(x_max) - p_min.max(x_min) >= min_width => {}
+            _ => runs.push(vec![]),
+        }
+        runs.last_mut()?
+            .push((band.y_min, band.y_max, x_min, x_max));
+        prev_x = band.x;
+    }
+
+    //the largest run forms the remnant
+    let band_area =
+        |(y_min, y_max, x_min, x_max): &(f32, f32, f32, f32)| (y_max - y_min) * (x_max - x_min);
+    let run = runs
+        .into_iter()
+        .filter(|run| run[run.len() - 1].1 - run[0].0 >= min_width)
+        .max_by_key(|run| OrderedFloat(run.iter().map(band_area).sum::<f32>()))?;
+
+    //up along the far side of the bands, then down along the staircase
+    let mut points = vec![];
+    for (y_min, y_max, _, x_max) in run.iter() {
+        points.push(Point(*x_max, *y_min));
+        points.push(Point(*x_max, *y_max));
+    }
+    for (y_min, y_max, x_min, _) in run.iter().rev() {
+        points.push(Point(*x_min, *y_max));
+        points.push(Point(*x_min, *y_min));
+    }
+    points.dedup();
+    SPolygon::new(points).ok()
+}
+
+/// Mirrors a rectangle along the diagonal, swapping its x and y coordinates
+fn transpose(r: &Rect) -> Rect {
+    Rect {
+        x_min: r.y_min,
+        y_min: r.x_min,
+        x_max: r.y_max,
+        y_max: r.x_max,
+    }
+}
+
+/// Horizontal band of the container, with the free range along the x-axis (if any)
+#[derive(Debug, Clone, Copy)]
+struct Band {
+    y_min: f32,
+    y_max: f32,
+    x: Option<(f32, f32)>,
+}
+
+impl Band {
+    fn height(&self) -> f32 {
+        self.y_max - self.y_min
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::Layout;
+    use crate::geometry::DTransformation;
+    use crate::geometry::geo_traits::CollidesWith;
+    use crate::util::test_util::{container, item, rect};
+
+    fn free(_: &Rect) -> bool {
+        true
+    }
+
+    #[test]
+    fn empty_rect_without_obstacles() {
+        let bbox = rect(0.0, 0.0, 100.0, 50.0);
+        assert_eq!(largest_empty_rect(bbox, &[], 0.0, free), Some(bbox));
+    }
+
+    #[test]
+    fn empty_rect_beside_obstacle() {
+        let bbox = rect(0.0, 0.0, 100.0, 100.0);
+        let obstacles = [rect(0.0, 0.0, 30.0, 100.0)];
+        assert_eq!(
+            largest_empty_rect(bbox, &obstacles, 0.0, free),
+            Some(rect(30.0, 0.0, 100.0, 100.0))
+        );
+    }
+
+    #[test]
+    fn empty_rect_around_obstacle() {
+        let bbox = rect(0.0, 0.0, 100.0, 100.0);
+        let obstacles = [rect(40.0, 20.0, 60.0, 50.0)];
+        //the part above the obstacle beats the ones left and right of it
+        assert_eq!(
+            largest_empty_rect(bbox, &obstacles, 0.0, free),
+            Some(rect(0.0, 50.0, 100.0, 100.0))
+        );
+    }
+
+    #[test]
+    fn empty_rect_between_obstacles() {
+        let bbox = rect(0.0, 0.0, 100.0, 100.0);
+        let obstacles = [rect(0.0, 0.0, 50.0, 50.0), rect(50.0, 50.0, 100.0, 100.0)];
+        let best = largest_empty_rect(bbox, &obstacles, 0.0, free).unwrap();
+        assert_eq!(best.area(), 2500.0);
+        assert!(
+            obstacles
+                .iter()
+                .all(|o| Rect::intersection(*o, best).is_none_or(|i| i.area() == 0.0))
+        );
+    }
+
+    #[test]
+    fn empty_rect_min_width() {
+        let bbox = rect(0.0, 0.0, 100.0, 100.0);
+        let obstacles = [rect(0.0, 0.0, 95.0, 100.0)];
+        assert_eq!(
+            largest_empty_rect(bbox, &obstacles, 0.0, free),
+            Some(rect(95.0, 0.0, 100.0, 100.0))
+        );
+        assert_eq!(largest_empty_rect(bbox, &obstacles, 10.0, free), None);
+    }
+
+    #[test]
+    fn empty_rect_verified() {
+        let bbox = rect(0.0, 0.0, 100.0, 100.0);
+        let obstacles = [rect(50.0, 0.0, 60.0, 10.0)];
+        //everything beyond x = 60 is rejected by the verification
+        let is_free = |r: &Rect| r.x_max <= 60.0;
+        assert_eq!(
+            largest_empty_rect(bbox, &obstacles, 0.0, is_free),
+            Some(rect(0.0, 10.0, 60.0, 100.0))
+        );
+    }
+
+    /// Container of 100x100 with a 60x30 item in the bottom left corner and a 20x20 item at (0, 60)
+    fn layout() -> Layout {
+        let mut layout = Layout::new(container(0, 100.0, 100.0));
+        layout.place_item(&item(0, 60.0, 30.0), DTransformation::new(0.0, (0.0, 0.0)));
+        layout.place_item(&item(1, 20.0, 20.0), DTransformation::new(0.0, (0.0, 60.0)));
+        layout
+    }
+
+    fn remnant(layout: &Layout, shape: RemnantShape, axis: RemnantAxis) -> Remnant {
+        let config = RemnantConfig {
+            shape,
+            axis,
+            min_width: 10.0,
+        };
+        layout.largest_remnant(config).unwrap()
+    }
+
+    #[test]
+    fn rect_remnant() {
+        let remnant = remnant(&layout(), RemnantShape::Rectangle, RemnantAxis::X);
+        assert_eq!(remnant.rect, Some(rect(20.0, 30.0, 100.0, 100.0)));
+    }
+
+    #[test]
+    fn tail_remnant_along_x() {
+        let remnant = remnant(&layout(), RemnantShape::Polygon, RemnantAxis::X);
+        //everything to the right of the items, including the gap between them
+        assert_eq!(remnant.rect, None);
+        assert_eq!(remnant.area(), 7800.0);
+        assert_eq!(remnant.shape.bbox, rect(0.0, 0.0, 100.0, 100.0));
+        assert!(remnant.shape.collides_with(&Point(10.0, 40.0)));
+        assert!(!remnant.shape.collides_with(&Point(10.0, 70.0)));
+    }
+
+    #[test]
+    fn tail_remnant_along_y() {
+        let remnant = remnant(&layout(), RemnantShape::Polygon, RemnantAxis::Y);
+        //everything above the items, the gap between them lies below the second item
+        assert_eq!(remnant.rect, None);
+        assert_eq!(remnant.area(), 7200.0);
+        assert_eq!(remnant.shape.bbox, rect(0.0, 0.0, 100.0, 100.0));
+        for point in [Point(10.0, 40.0), Point(10.0, 70.0), Point(40.0, 20.0)] {
+            assert!(!remnant.shape.collides_with(&point));
+        }
+        for point in [Point(10.0, 90.0), Point(40.0, 40.0), Point(80.0, 10.0)] {
+            assert!(remnant.shape.collides_with(&point));
+        }
+    }
+}
@@ -4,8 +4,9 @@ use std::fmt::Display;
 
 use crate::geometry::Transformation;
 use ordered_float::NotNan;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Copy, Default)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Copy, Default, Serialize, Deserialize)]
 /// [Proper rigid transformation](https://en.wikipedia.org/wiki/Rigid_transformation),
 /// decomposed into a rotation followed by a translation.
 pub struct DTransformation {
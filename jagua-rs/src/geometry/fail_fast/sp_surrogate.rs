@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 
 use anyhow::Result;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 /// Surrogate representation of a [`SPolygon`] - a 'light-weight' representation that
 /// is fully contained in the original [`SPolygon`].
 /// Used for *fail-fast* collision detection.
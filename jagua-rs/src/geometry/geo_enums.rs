@@ -1,4 +1,8 @@
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+use crate::geometry::normalize_rotation;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum GeoPosition {
     Exterior,
     Interior,
@@ -27,3 +31,21 @@ pub enum RotationRange {
     /// Discrete set of rotations allowed
     Discrete(Vec<f32>),
 }
+
+impl RotationRange {
+    /// Whether the rotation (in radians) is part of the range, up to a small tolerance
+    pub fn contains(&self, rotation: f32) -> bool {
+        let matches = |r: f32| {
+            let diff = normalize_rotation(rotation - r);
+            diff < ROTATION_TOLERANCE || diff > 2.0 * PI - ROTATION_TOLERANCE
+        };
+        match self {
+            RotationRange::None => matches(0.0),
+            RotationRange::Continuous => true,
+            RotationRange::Discrete(rotations) => rotations.iter().any(|r| matches(*r)),
+        }
+    }
+}
+
+/// Tolerance (in radians) used to compare rotations
+const ROTATION_TOLERANCE: f32 = 1e-4;
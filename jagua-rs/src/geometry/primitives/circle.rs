@@ -8,11 +8,12 @@ use crate::geometry::primitives::Point;
 use crate::geometry::primitives::Rect;
 use anyhow::Result;
 use anyhow::ensure;
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::f32::consts::PI;
 
 /// Circle
-#[derive(Clone, Debug, PartialEq, Copy)]
+#[derive(Clone, Debug, PartialEq, Copy, Serialize, Deserialize)]
 pub struct Circle {
     pub center: Point,
     pub radius: f32,
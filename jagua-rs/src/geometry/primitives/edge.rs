@@ -1,12 +1,14 @@
 use crate::geometry::Transformation;
 use crate::geometry::geo_traits::{CollidesWith, DistanceTo, Transformable, TransformableFrom};
+use crate::geometry::normalize_rotation;
 use crate::geometry::primitives::Point;
 use crate::geometry::primitives::Rect;
 use anyhow::Result;
 use anyhow::ensure;
+use serde::{Deserialize, Serialize};
 
 /// Line segment between two [`Point`]s
-#[derive(Clone, Debug, PartialEq, Copy)]
+#[derive(Clone, Debug, PartialEq, Copy, Serialize, Deserialize)]
 pub struct Edge {
     pub start: Point,
     pub end: Point,
@@ -59,6 +61,40 @@ impl Edge {
         }
     }
 
+    /// Returns the (counterclockwise) rotation angles in `[0, 2π)` at which `point`, rotated around `pivot`, comes into contact with the edge.
+    /// The circle traced by `point` can intersect the edge at most twice.
+    pub fn rotational_contact_angles(&self, point: Point, pivot: Point) -> [Option<f32>; 2] {
+        let Point(px, py) = pivot;
+        let (vx, vy) = (point.0 - px, point.1 - py);
+        let sq_radius = vx * vx + vy * vy;
+
+        //intersect the line through the edge with the circle traced by the point
+        //based on: https://mathworld.wolfram.com/Circle-LineIntersection.html
+        let (sx, sy) = (self.start.0 - px, self.start.1 - py);
+        let (dx, dy) = (self.end.0 - self.start.0, self.end.1 - self.start.1);
+        let a = dx * dx + dy * dy;
+        let b = 2.0 * (sx * dx + sy * dy);
+        let c = sx * sx + sy * sy - sq_radius;
+        let discriminant = b * b - 4.0 * a * c;
+
+        if a == 0.0 || discriminant < 0.0 {
+            return [None, None];
+        }
+        let sqrt_d = discriminant.sqrt();
+
+        [(-b - sqrt_d) / (2.0 * a), (-b + sqrt_d) / (2.0 * a)].map(|t| {
+            match (0.0..=1.0).contains(&t) {
+                true => {
+                    //angle between the point and the contact point, as seen from the pivot
+                    let (qx, qy) = (sx + t * dx, sy + t * dy);
+                    let angle = f32::atan2(vx * qy - vy * qx, vx * qx + vy * qy);
+                    Some(normalize_rotation(angle))
+                }
+                false => None,
+            }
+        })
+    }
+
     /// Returns the closest point which lies on the edge to the given point
     pub fn closest_point_on_edge(&self, point: &Point) -> Point {
         //from https://stackoverflow.com/a/6853926
@@ -169,6 +205,14 @@ impl CollidesWith<Edge> for Edge {
     }
 }
 
+impl CollidesWith<Point> for Edge {
+    #[inline(always)]
+    fn collides_with(&self, point: &Point) -> bool {
+        //the point has to lie exactly on the edge
+        self.sq_distance_to(point) == 0.0
+    }
+}
+
 impl CollidesWith<Rect> for Edge {
     #[inline(always)]
     fn collides_with(&self, other: &Rect) -> bool {
@@ -2,9 +2,10 @@ use std::hash::{Hash, Hasher};
 
 use crate::geometry::Transformation;
 use crate::geometry::geo_traits::{CollidesWith, DistanceTo, Transformable, TransformableFrom};
+use serde::{Deserialize, Serialize};
 
 /// A Point in 2D space with x and y coordinates
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Copy, Serialize, Deserialize)]
 pub struct Point(pub f32, pub f32);
 
 impl Transformable for Point {
@@ -8,9 +8,10 @@ use crate::util::FPA;
 use anyhow::Result;
 use anyhow::ensure;
 use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
 
 ///Axis-aligned rectangle
-#[derive(Clone, Debug, PartialEq, Copy)]
+#[derive(Clone, Debug, PartialEq, Copy, Serialize, Deserialize)]
 pub struct Rect {
     pub x_min: f32,
     pub y_min: f32,
@@ -16,11 +16,12 @@ use crate::geometry::primitives::Point;
 use crate::geometry::primitives::Rect;
 use crate::util::FPA;
 use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
 
 /// A Simple Polygon is a polygon that does not intersect itself and contains no holes.
 /// It is a closed shape with a finite number of vertices and edges.
 /// [read more](https://en.wikipedia.org/wiki/Simple_polygon)
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SPolygon {
     /// Set of points that form the polygon
     pub vertices: Vec<Point>,
@@ -1,6 +1,6 @@
-use crate::entities::{Instance, LayoutSnapshot};
+use crate::entities::{Instance, LayoutSnapshot, Remnant};
 use crate::geometry::{DTransformation, Transformation};
-use crate::io::ext_repr::{ExtLayout, ExtPlacedItem};
+use crate::io::ext_repr::{ExtContainer, ExtLayout, ExtPlacedItem, ExtSPolygon, ExtShape};
 
 /// Exports a layout to an external representation.
 pub fn export_layout_snapshot(layout: &LayoutSnapshot, instance: &impl Instance) -> ExtLayout {
@@ -16,6 +16,7 @@ pub fn export_layout_snapshot(layout: &LayoutSnapshot, instance: &impl Instance)
             ExtPlacedItem {
                 item_id: pi.item_id as u64,
                 transformation: abs_transf.into(),
+                fixed: pi.fixed,
             }
         })
         .collect();
@@ -27,6 +28,29 @@ pub fn export_layout_snapshot(layout: &LayoutSnapshot, instance: &impl Instance)
     }
 }
 
+/// Exports a remnant as a container with the given id, so it can be used in a next instance.
+pub fn export_remnant(remnant: &Remnant, id: usize) -> ExtContainer {
+    let shape = match remnant.rect {
+        Some(rect) => ExtShape::Rectangle {
+            x_min: rect.x_min,
+            y_min: rect.y_min,
+            width: rect.width(),
+            height: rect.height(),
+        },
+        None => ExtShape::SimplePolygon(ExtSPolygon(
+            remnant.shape.vertices.iter().map(|p| (p.0, p.1)).collect(),
+        )),
+    };
+    ExtContainer {
+        id: id as u64,
+        shape,
+        zones: vec![],
+        quality_map: None,
+        custom_zones: vec![],
+        grain_zones: vec![],
+    }
+}
+
 /// Converts an internal (used within `jagua-rs`) transformation to an external transformation (applicable to the original shapes).
 ///
 /// * `int_transf` - The internal transformation.
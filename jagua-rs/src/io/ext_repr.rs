@@ -15,6 +15,28 @@ pub struct ExtItem {
     /// The minimum required quality of the item.
     /// Maximum quality required if not specified.
     pub min_quality: Option<usize>,
+    /// Optional class of the item, custom zones can be restricted to (or reserved for) classes of items
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub class: Option<u64>,
+    /// Regions of the item with a stricter quality requirement than `min_quality`, defined in the same coordinates as `shape`
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub quality_regions: Vec<ExtQualityRegion>,
+    /// Whether the item has to respect the allowed orientations of the grain zones of the container
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub grain_sensitive: bool,
+    /// Weight of the item, weightless if not specified
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub weight: Option<f32>,
+}
+
+/// A region of an item with its own quality requirement
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtQualityRegion {
+    /// The minimum required quality underneath the region.
+    /// Maximum quality required if not specified.
+    pub min_quality: Option<usize>,
+    /// Shape of the region
+    pub shape: ExtShape,
 }
 
 /// External representation of a [`Container`](crate::entities::Container).
@@ -28,6 +50,15 @@ pub struct ExtContainer {
     /// Zones within the container with varying quality. Holes in the container shape are treated as zones with quality 0.
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub zones: Vec<ExtQualityZone>,
+    /// Raster of qualities covering (part of) the container, vectorized into quality zones upon import
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub quality_map: Option<ExtQualityMap>,
+    /// User-defined zones within the container (e.g. clamps, suction cups or marker zones)
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub custom_zones: Vec<ExtCustomZone>,
+    /// Zones within the container restricting the orientations of grain sensitive items
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub grain_zones: Vec<ExtGrainZone>,
 }
 
 /// Various ways to represent a shape
@@ -74,6 +105,74 @@ pub struct ExtQualityZone {
     pub shape: ExtShape,
 }
 
+/// A raster of quality values, e.g. obtained from a scanner.
+/// Cells are squares, the first row starts at (x_min, y_min) and rows are stacked along the y-axis.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtQualityMap {
+    pub x_min: f32,
+    pub y_min: f32,
+    /// Width and height of each cell
+    pub cell_size: f32,
+    pub n_rows: usize,
+    pub n_cols: usize,
+    /// Quality of each cell in row-major order. `null` represents full quality (no zone).
+    pub values: Vec<Option<usize>>,
+    /// Distance by which the vectorized zones are inflated, half the cell size if not specified
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub inflation: Option<f32>,
+}
+
+/// A user-defined zone which has to be avoided by the items it applies to:
+/// all items, unless restricted by `forbidden_for` or `allowed_for`
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtCustomZone {
+    /// Unique identifier of the zone
+    pub id: u64,
+    /// User-defined tag, e.g. to distinguish between different kinds of zones
+    #[serde(default)]
+    pub tag: u64,
+    /// Whether items are forbidden inside (interior) or outside (exterior) the shape of the zone
+    #[serde(default)]
+    pub scope: ExtScope,
+    /// The zone is only forbidden for these items, all others can use it freely
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub forbidden_for: Option<ExtItemSelector>,
+    /// The zone is reserved for these items, it is forbidden for all others
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub allowed_for: Option<ExtItemSelector>,
+    /// The polygon shape of this zone
+    pub shape: ExtShape,
+}
+
+/// A zone with its own set of allowed orientations for grain sensitive items
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtGrainZone {
+    /// List of allowed orientation angles (in degrees) for grain sensitive items overlapping the zone.
+    /// Continuous rotation if not specified
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_orientations: Option<Vec<f32>>,
+    /// The polygon shape of this zone
+    pub shape: ExtShape,
+}
+
+/// Selection of items by their ids and/or classes
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct ExtItemSelector {
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub item_ids: Vec<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub item_classes: Vec<u64>,
+}
+
+/// Region of a shape in which a zone is active
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtScope {
+    #[default]
+    Interior,
+    Exterior,
+}
+
 /// External representation of a [`Layout`](crate::entities::Layout).
 /// A layout consists of a container with items placed in a specific configuration.
 #[derive(Serialize, Deserialize, Clone)]
@@ -83,6 +182,7 @@ pub struct ExtLayout {
     /// The items placed in the container and where they were placed
     pub placed_items: Vec<ExtPlacedItem>,
     /// Some statistics about the layout
+    #[serde(default)]
     pub density: f32,
 }
 
@@ -94,6 +194,9 @@ pub struct ExtPlacedItem {
     pub item_id: u64,
     /// The transformation applied to the item to place it in the container
     pub transformation: ExtTransformation,
+    /// Whether the item was pre-placed and cannot be moved or removed
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub fixed: bool,
 }
 
 /// Represents a proper rigid transformation defined as a rotation followed by translation
@@ -105,6 +208,12 @@ pub struct ExtTransformation {
     pub translation: (f32, f32),
 }
 
+impl From<&ExtTransformation> for DTransformation {
+    fn from(et: &ExtTransformation) -> Self {
+        DTransformation::new(et.rotation, et.translation)
+    }
+}
+
 impl From<DTransformation> for ExtTransformation {
     fn from(dt: DTransformation) -> Self {
         ExtTransformation {
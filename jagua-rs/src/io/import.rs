@@ -1,15 +1,24 @@
-use crate::collision_detection::CDEConfig;
-use crate::entities::Item;
-use crate::entities::{Container, InferiorQualityZone, N_QUALITIES};
+use crate::collision_detection::hazards::HazardEntity;
+use crate::collision_detection::hazards::collector::BasicHazardCollector;
+use crate::collision_detection::hazards::filter::HazKeyFilter;
+use crate::collision_detection::{CDEConfig, CDEngine};
+use crate::entities::{
+    Container, CustomZone, GrainZone, InferiorQualityZone, ItemSelector, ZoneItemRule,
+};
+use crate::entities::{Instance, Item, ItemQualityRegion, Layout, PItemKey};
 use crate::geometry::OriginalShape;
-use crate::geometry::geo_enums::RotationRange;
+use crate::geometry::geo_enums::{GeoPosition, GeoRelation, RotationRange};
+use crate::geometry::geo_traits::Transformable;
 use crate::geometry::primitives::Point;
 use crate::geometry::primitives::Rect;
 use crate::geometry::primitives::SPolygon;
 use crate::geometry::shape_modification::{ShapeModifyConfig, ShapeModifyMode};
-use crate::geometry::{DTransformation, Transformation};
-use crate::io::ext_repr::{ExtContainer, ExtItem, ExtSPolygon, ExtShape};
-use anyhow::{Result, bail};
+use crate::geometry::{DTransformation, Transformation, normalize_rotation};
+use crate::io::ext_repr::{
+    ExtContainer, ExtItem, ExtItemSelector, ExtPlacedItem, ExtQualityMap, ExtSPolygon, ExtScope,
+    ExtShape,
+};
+use anyhow::{Result, bail, ensure};
 use float_cmp::approx_eq;
 use itertools::Itertools;
 use log::{debug, warn};
@@ -77,32 +86,47 @@ impl Importer {
 
         let base_quality = ext_item.min_quality;
 
-        let allowed_orientations = match ext_item.allowed_orientations.as_ref() {
-            Some(a_o) => {
-                if a_o.is_empty() || (a_o.len() == 1 && a_o[0] == 0.0) {
-                    RotationRange::None
-                } else {
-                    RotationRange::Discrete(a_o.iter().map(|angle| angle.to_radians()).collect())
-                }
-            }
-            None => RotationRange::Continuous,
-        };
+        //quality regions share the reference frame (and thus the pre-transformation) of the item
+        let quality_regions = ext_item
+            .quality_regions
+            .iter()
+            .map(|region| {
+                let shape = import_simple_shape(&region.shape, "quality regions")?;
+                let original_shape = OriginalShape {
+                    shape,
+                    pre_transform: original_shape.pre_transform,
+                    modify_mode: ShapeModifyMode::Inflate,
+                    modify_config: self.shape_modify_config,
+                };
+                ItemQualityRegion::new(region.min_quality, original_shape)
+            })
+            .collect::<Result<Vec<ItemQualityRegion>>>()?;
+
+        let allowed_orientations = import_rotation_range(ext_item.allowed_orientations.as_deref());
+
+        let weight = ext_item.weight.unwrap_or(0.0);
+        ensure!(
+            weight.is_finite() && weight >= 0.0,
+            "item {} has an invalid weight: {weight}",
+            ext_item.id
+        );
 
         Item::new(
             ext_item.id as usize,
             original_shape,
             allowed_orientations,
             base_quality,
+            ext_item.class.map(|c| c as usize),
+            quality_regions,
             self.cde_config.item_surrogate_config,
         )
+        .map(|item| {
+            item.with_grain_sensitive(ext_item.grain_sensitive)
+                .with_weight(weight)
+        })
     }
 
     pub fn import_container(&self, ext_cont: &ExtContainer) -> Result<Container> {
-        assert!(
-            ext_cont.zones.iter().all(|zone| zone.quality < N_QUALITIES),
-            "All quality zones must have lower quality than N_QUALITIES, set N_QUALITIES to a higher value if required"
-        );
-
         let original_outer = {
             let outer = match &ext_cont.shape {
                 ExtShape::Rectangle {
@@ -139,7 +163,20 @@ impl Importer {
             }
         };
 
-        let mut shapes_inferior_qzones = (0..N_QUALITIES)
+        let raster_zones = match &ext_cont.quality_map {
+            Some(qm) => import_quality_map(qm)?,
+            None => vec![],
+        };
+
+        let n_qualities = ext_cont
+            .zones
+            .iter()
+            .map(|zone| zone.quality)
+            .chain(raster_zones.iter().map(|(q, _)| *q))
+            .max()
+            .map_or(1, |q| q + 1);
+
+        let mut shapes_inferior_qzones = (0..n_qualities)
             .map(|q| {
                 ext_cont
                     .zones
@@ -168,6 +205,11 @@ impl Importer {
         //merge the container holes with quality == 0
         shapes_inferior_qzones[0].extend(holes);
 
+        //merge the zones vectorized from the quality map
+        for (q, shape) in raster_zones {
+            shapes_inferior_qzones[q].push(shape);
+        }
+
         //convert the shapes to inferior quality zones
         let quality_zones = shapes_inferior_qzones
             .into_iter()
@@ -186,15 +228,152 @@ impl Importer {
             })
             .collect::<Result<Vec<InferiorQualityZone>>>()?;
 
+        let custom_zones = ext_cont
+            .custom_zones
+            .iter()
+            .map(|zone| {
+                let shape = import_simple_shape(&zone.shape, "custom zones")?;
+                let (scope, modify_mode) = match zone.scope {
+                    ExtScope::Interior => (GeoPosition::Interior, ShapeModifyMode::Inflate),
+                    ExtScope::Exterior => (GeoPosition::Exterior, ShapeModifyMode::Deflate),
+                };
+                let original_shape = OriginalShape {
+                    shape,
+                    pre_transform: DTransformation::empty(),
+                    modify_mode,
+                    modify_config: self.shape_modify_config,
+                };
+                let item_rule = match (&zone.forbidden_for, &zone.allowed_for) {
+                    (None, None) => ZoneItemRule::All,
+                    (Some(sel), None) => ZoneItemRule::ForbiddenFor(import_item_selector(sel)),
+                    (None, Some(sel)) => ZoneItemRule::AllowedFor(import_item_selector(sel)),
+                    (Some(_), Some(_)) => bail!(
+                        "custom zone {} cannot define both forbidden_for and allowed_for",
+                        zone.id
+                    ),
+                };
+                CustomZone::new(
+                    zone.id as usize,
+                    zone.tag as usize,
+                    scope,
+                    item_rule,
+                    original_shape,
+                )
+            })
+            .collect::<Result<Vec<CustomZone>>>()?;
+
+        let grain_zones = ext_cont
+            .grain_zones
+            .iter()
+            .map(|zone| {
+                let shape = import_simple_shape(&zone.shape, "grain zones")?;
+                let original_shape = OriginalShape {
+                    shape,
+                    pre_transform: DTransformation::empty(),
+                    modify_mode: ShapeModifyMode::Inflate,
+                    modify_config: self.shape_modify_config,
+                };
+                let allowed_rotation = import_rotation_range(zone.allowed_orientations.as_deref());
+                GrainZone::new(allowed_rotation, original_shape)
+            })
+            .collect::<Result<Vec<GrainZone>>>()?;
+
         Container::new(
             ext_cont.id as usize,
             original_outer,
             quality_zones,
+            custom_zones,
+            grain_zones,
             self.cde_config,
         )
     }
 }
 
+/// Converts a list of allowed orientations (in degrees) into a [`RotationRange`]
+fn import_rotation_range(allowed_orientations: Option<&[f32]>) -> RotationRange {
+    match allowed_orientations {
+        Some(a_o) => {
+            if a_o.is_empty() || (a_o.len() == 1 && a_o[0] == 0.0) {
+                RotationRange::None
+            } else {
+                RotationRange::Discrete(a_o.iter().map(|angle| angle.to_radians()).collect())
+            }
+        }
+        None => RotationRange::Continuous,
+    }
+}
+
+/// Vectorizes a raster quality map into (quality, shape) pairs.
+/// Cells of equal quality are merged into rectangles, which are conservatively inflated to account for the resolution of the raster.
+pub fn import_quality_map(qm: &ExtQualityMap) -> Result<Vec<(usize, SPolygon)>> {
+    ensure!(
+        qm.cell_size > 0.0,
+        "cell size of quality map must be positive"
+    );
+    ensure!(
+        qm.values.len() == qm.n_rows * qm.n_cols,
+        "quality map contains {} values, expected {} rows x {} columns",
+        qm.values.len(),
+        qm.n_rows,
+        qm.n_cols
+    );
+    let inflation = qm.inflation.unwrap_or(qm.cell_size / 2.0);
+    ensure!(
+        inflation >= 0.0,
+        "inflation of quality map cannot be negative"
+    );
+
+    let value = |row: usize, col: usize| qm.values[row * qm.n_cols + col];
+
+    //greedily merge cells of equal quality into maximal horizontal runs, then extend these runs upward
+    let mut covered = vec![false; qm.values.len()];
+    let mut zones = vec![];
+    for row in 0..qm.n_rows {
+        for col in 0..qm.n_cols {
+            let Some(q) = value(row, col) else { continue };
+            if covered[row * qm.n_cols + col] {
+                continue;
+            }
+            let run_end = (col..qm.n_cols)
+                .take_while(|&c| value(row, c) == Some(q) && !covered[row * qm.n_cols + c])
+                .last()
+                .unwrap()
+                + 1;
+            let rows_end = (row..qm.n_rows)
+                .take_while(|&r| {
+                    (col..run_end).all(|c| value(r, c) == Some(q) && !covered[r * qm.n_cols + c])
+                })
+                .last()
+                .unwrap()
+                + 1;
+            for r in row..rows_end {
+                for c in col..run_end {
+                    covered[r * qm.n_cols + c] = true;
+                }
+            }
+            let rect = Rect::try_new(
+                qm.x_min + col as f32 * qm.cell_size - inflation,
+                qm.y_min + row as f32 * qm.cell_size - inflation,
+                qm.x_min + run_end as f32 * qm.cell_size + inflation,
+                qm.y_min + rows_end as f32 * qm.cell_size + inflation,
+            )?;
+            zones.push((q, SPolygon::from(rect)));
+        }
+    }
+    debug!(
+        "[IMPORT] vectorized quality map into {} rectangular zones",
+        zones.len()
+    );
+    Ok(zones)
+}
+
+fn import_item_selector(sel: &ExtItemSelector) -> ItemSelector {
+    ItemSelector {
+        item_ids: sel.item_ids.iter().map(|id| *id as usize).collect(),
+        item_classes: sel.item_classes.iter().map(|c| *c as usize).collect(),
+    }
+}
+
 pub fn import_simple_polygon(sp: &ExtSPolygon) -> Result<SPolygon> {
     let mut points = sp.0.iter().map(|(x, y)| Point(*x, *y)).collect_vec();
     //Strip the last vertex if it is the same as the first one
@@ -210,6 +389,22 @@ pub fn import_simple_polygon(sp: &ExtSPolygon) -> Result<SPolygon> {
     SPolygon::new(points)
 }
 
+/// Imports a shape which has to be a rectangle or a simple polygon, `what` describes its use in the error message
+pub(crate) fn import_simple_shape(shape: &ExtShape, what: &str) -> Result<SPolygon> {
+    match shape {
+        ExtShape::Rectangle {
+            x_min,
+            y_min,
+            width,
+            height,
+        } => Ok(Rect::try_new(*x_min, *y_min, x_min + width, y_min + height)?.into()),
+        ExtShape::SimplePolygon(esp) => import_simple_polygon(esp),
+        ExtShape::Polygon(_) | ExtShape::MultiPolygon(_) => {
+            bail!("No support for (multi)polygon shapes in {what} yet")
+        }
+    }
+}
+
 /// Returns a transformation that translates the shape's centroid to the origin.
 pub fn centering_transformation(shape: &SPolygon) -> DTransformation {
     let Point(cx, cy) = shape.centroid();
@@ -233,6 +428,170 @@ pub fn ext_to_int_transformation(
         .decompose()
 }
 
+/// A placement of an imported solution which violates at least one constraint
+#[derive(Clone, Debug)]
+pub struct InfeasiblePlacement {
+    /// Position of the placement within its external layout
+    pub index: usize,
+    /// ID of the placed item
+    pub item_id: usize,
+    /// Key of the item in the layout, `None` if it lies outside the bounds of the container and was not placed
+    pub pk: Option<PItemKey>,
+    /// All hazards the item collides with
+    pub colliding: Vec<HazardEntity>,
+    /// Whether a quality region of the item overlaps a zone of insufficient quality
+    pub quality_region_violated: bool,
+    /// Whether the item overlaps a grain zone which does not allow its rotation
+    pub grain_violated: bool,
+    /// Whether the rotation is not allowed for the item
+    pub rotation_violated: bool,
+    /// Whether the item exceeds the remaining weight capacity of its container, in which case it was not placed
+    pub weight_capacity_violated: bool,
+    /// Whether the item is not allowed in its container, in which case it was not placed
+    pub eligibility_violated: bool,
+    /// Whether the item would split a group which has to be kept in a single container, in which case it was not placed
+    pub group_violated: bool,
+}
+
+impl InfeasiblePlacement {
+    /// Checks a placement of `item` in the layout, returns `None` if it is feasible.
+    /// If `pk` is `None`, the item is not part of the layout.
+    pub fn check(
+        layout: &Layout,
+        item: &Item,
+        d_transf: DTransformation,
+        index: usize,
+        pk: Option<PItemKey>,
+    ) -> Option<Self> {
+        let report = Self::evaluate(layout, item, d_transf, index, pk);
+        match report.is_feasible() {
+            true => None,
+            false => Some(report),
+        }
+    }
+
+    /// Same as [`InfeasiblePlacement::check`], but always returns the report, even if the placement is feasible.
+    pub fn evaluate(
+        layout: &Layout,
+        item: &Item,
+        d_transf: DTransformation,
+        index: usize,
+        pk: Option<PItemKey>,
+    ) -> Self {
+        let cde = layout.cde();
+        let transf = d_transf.compose();
+        let shape = item.shape_cd.transform_clone(&transf);
+
+        //hazards irrelevant to the item (and its own hazard) are inserted upfront, so they are not reported
+        let mut ignored = HazKeyFilter::for_item(item, &cde.hazards_map);
+        if let Some(hkey) = pk.and_then(|pk| cde.haz_key_from_pi_key(pk)) {
+            ignored.0.insert(hkey, ());
+        }
+        let mut collector: BasicHazardCollector = ignored
+            .0
+            .keys()
+            .map(|hkey| (hkey, cde.hazards_map[hkey].entity))
+            .collect();
+        cde.collect_poly_collisions(&shape, &mut collector);
+        let colliding = collector
+            .iter()
+            .filter(|(hkey, _)| !ignored.0.contains_key(*hkey))
+            .map(|(_, entity)| *entity)
+            .collect_vec();
+
+        InfeasiblePlacement {
+            index,
+            item_id: item.id,
+            pk,
+            colliding,
+            quality_region_violated: cde
+                .detect_quality_region_collision(&item.quality_regions, &transf),
+            grain_violated: item.grain_sensitive
+                && cde.detect_grain_collision(&shape, d_transf.rotation()),
+            rotation_violated: !item.allowed_rotation.contains(d_transf.rotation()),
+            weight_capacity_violated: false,
+            eligibility_violated: false,
+            group_violated: false,
+        }
+    }
+
+    /// Whether the placement does not violate any constraint
+    pub fn is_feasible(&self) -> bool {
+        self.colliding.is_empty()
+            && !self.quality_region_violated
+            && !self.grain_violated
+            && !self.rotation_violated
+            && !self.weight_capacity_violated
+            && !self.eligibility_violated
+            && !self.group_violated
+    }
+}
+
+/// Relative tolerance used when comparing the transformation of a fixed placement to the one in the instance
+const FIXED_PLACEMENT_TOLERANCE: f32 = 1e-4;
+
+/// Matches the placements of an external layout marked as `fixed` one-to-one to the fixed items of `layout`, by item id and transformation.
+/// Fails if a placement marked as fixed matches none of the fixed items, or if a fixed item is missing from the external layout.
+pub fn match_fixed_placements(
+    instance: &impl Instance,
+    layout: &Layout,
+    ext_placed_items: &[ExtPlacedItem],
+) -> Result<()> {
+    let mut unmatched = layout
+        .placed_items
+        .values()
+        .filter(|pi| pi.fixed)
+        .map(|pi| (pi.item_id, pi.d_transf))
+        .collect_vec();
+
+    for (index, ext_pi) in ext_placed_items
+        .iter()
+        .enumerate()
+        .filter(|(_, pi)| pi.fixed)
+    {
+        let item_id = ext_pi.item_id as usize;
+        let Some(item) = instance.items().find(|item| item.id == item_id) else {
+            bail!("placement {index} refers to unknown item {item_id}");
+        };
+        let d_transf = ext_to_int_transformation(
+            &(&ext_pi.transformation).into(),
+            &item.shape_orig.pre_transform,
+        );
+        let Some(pos) = unmatched
+            .iter()
+            .position(|(id, dt)| *id == item_id && transformations_match(dt, &d_transf))
+        else {
+            bail!(
+                "placement {index} is marked as fixed, but does not match any fixed placement of item {item_id}"
+            );
+        };
+        unmatched.swap_remove(pos);
+    }
+    ensure!(
+        unmatched.is_empty(),
+        "{} fixed item(s) are missing from the layout",
+        unmatched.len()
+    );
+    Ok(())
+}
+
+fn transformations_match(a: &DTransformation, b: &DTransformation) -> bool {
+    let close = |x: f32, y: f32| {
+        (x - y).abs() <= FIXED_PLACEMENT_TOLERANCE * f32::max(1.0, f32::max(x.abs(), y.abs()))
+    };
+    let d_rot = normalize_rotation(a.rotation() - b.rotation());
+    let d_rot = f32::min(d_rot, 2.0 * std::f32::consts::PI - d_rot);
+    let ((ax, ay), (bx, by)) = (a.translation(), b.translation());
+    close(d_rot, 0.0) && close(ax, bx) && close(ay, by)
+}
+
+/// Checks whether an item placed with the transformation lies within the bounds of the collision detection engine,
+/// and can thus be registered in it.
+pub fn placement_in_bounds(cde: &CDEngine, item: &Item, d_transf: DTransformation) -> bool {
+    let shape = item.shape_cd.transform_clone(&d_transf.compose());
+    cde.bbox().relation_to(shape.bbox) == GeoRelation::Surrounding
+}
+
 pub fn eliminate_degenerate_vertices(points: &mut Vec<Point>) {
     let mut indices_to_remove = vec![];
     let n_points = points.len();
@@ -258,3 +617,227 @@ pub fn eliminate_degenerate_vertices(points: &mut Vec<Point>) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::ext_repr::{ExtCustomZone, ExtQualityMap};
+    use crate::util::test_util::cde_config;
+
+    fn importer() -> Importer {
+        Importer::new(cde_config(), None, None, None)
+    }
+
+    fn ext_rect(x_min: f32, y_min: f32, width: f32, height: f32) -> ExtShape {
+        ExtShape::Rectangle {
+            x_min,
+            y_min,
+            width,
+            height,
+        }
+    }
+
+    fn ext_container(
+        quality_map: Option<ExtQualityMap>,
+        custom_zones: Vec<ExtCustomZone>,
+    ) -> ExtContainer {
+        ExtContainer {
+            id: 0,
+            shape: ext_rect(0.0, 0.0, 100.0, 100.0),
+            zones: vec![],
+            quality_map,
+            custom_zones,
+            grain_zones: vec![],
+        }
+    }
+
+    /// Square item of 10 by 10, its reference point is in the center
+    fn ext_item(id: u64, min_quality: Option<usize>, class: Option<u64>) -> ExtItem {
+        ExtItem {
+            id,
+            allowed_orientations: None,
+            shape: ext_rect(0.0, 0.0, 10.0, 10.0),
+            min_quality,
+            class,
+            quality_regions: vec![],
+            grain_sensitive: false,
+            weight: None,
+        }
+    }
+
+    fn quality_map(values: Vec<Option<usize>>, inflation: Option<f32>) -> ExtQualityMap {
+        ExtQualityMap {
+            x_min: 0.0,
+            y_min: 0.0,
+            cell_size: 10.0,
+            n_rows: 2,
+            n_cols: 3,
+            values,
+            inflation,
+        }
+    }
+
+    fn is_feasible(layout: &Layout, item: &Item, pos: (f32, f32)) -> bool {
+        InfeasiblePlacement::check(layout, item, DTransformation::new(0.0, pos), 0, None).is_none()
+    }
+
+    #[test]
+    fn quality_map_cells_merged_into_rects() {
+        // row 0: 1 1 -
+        // row 1: 1 1 0
+        let qm = quality_map(
+            vec![Some(1), Some(1), None, Some(1), Some(1), Some(0)],
+            Some(0.0),
+        );
+        let zones = import_quality_map(&qm).unwrap();
+        let zones = zones
+            .iter()
+            .map(|(q, shape)| (*q, shape.bbox))
+            .collect_vec();
+        assert_eq!(
+            zones,
+            vec![
+                (1, Rect::try_new(0.0, 0.0, 20.0, 20.0).unwrap()),
+                (0, Rect::try_new(20.0, 10.0, 30.0, 20.0).unwrap())
+            ]
+        );
+    }
+
+    #[test]
+    fn quality_map_zones_inflated() {
+        let qm = quality_map(vec![None, Some(2), None, None, None, None], None);
+        let zones = import_quality_map(&qm).unwrap();
+        assert_eq!(zones.len(), 1);
+        //inflated by half a cell by default
+        assert_eq!(
+            zones[0].1.bbox,
+            Rect::try_new(5.0, -5.0, 25.0, 15.0).unwrap()
+        );
+
+        let qm = ExtQualityMap {
+            inflation: Some(1.0),
+            ..qm
+        };
+        let zones = import_quality_map(&qm).unwrap();
+        assert_eq!(
+            zones[0].1.bbox,
+            Rect::try_new(9.0, -1.0, 21.0, 11.0).unwrap()
+        );
+    }
+
+    #[test]
+    fn quality_map_malformed() {
+        assert!(import_quality_map(&quality_map(vec![Some(0); 5], None)).is_err());
+        let qm = ExtQualityMap {
+            cell_size: 0.0,
+            ..quality_map(vec![Some(0); 6], None)
+        };
+        assert!(import_quality_map(&qm).is_err());
+        let qm = quality_map(vec![Some(0); 6], Some(-1.0));
+        assert!(import_quality_map(&qm).is_err());
+    }
+
+    #[test]
+    fn quality_map_imported_as_quality_zones() {
+        let qm = ExtQualityMap {
+            x_min: 50.0,
+            cell_size: 20.0,
+            ..quality_map(
+                vec![Some(0), Some(0), None, Some(1), Some(1), None],
+                Some(0.0),
+            )
+        };
+        let container = importer()
+            .import_container(&ext_container(Some(qm), vec![]))
+            .unwrap();
+        //qualities 0 and 1 are present in the raster
+        assert_eq!(container.quality_zones.len(), 2);
+        assert!(container.quality_zones.iter().all(|qz| qz.is_some()));
+
+        let layout = Layout::new(container);
+        let min_quality_1 = importer().import_item(&ext_item(0, Some(1), None)).unwrap();
+        let min_quality_2 = importer().import_item(&ext_item(1, Some(2), None)).unwrap();
+        let full_quality = importer().import_item(&ext_item(2, None, None)).unwrap();
+
+        //on top of the quality 1 cells (y: 20..40)
+        let pos = (70.0, 30.0);
+        assert!(is_feasible(&layout, &min_quality_1, pos));
+        assert!(!is_feasible(&layout, &min_quality_2, pos));
+        assert!(!is_feasible(&layout, &full_quality, pos));
+
+        //quality 0 cells (y: 0..20) are unusable for all items, like holes
+        let pos = (70.0, 10.0);
+        assert!(!is_feasible(&layout, &min_quality_1, pos));
+
+        //outside of the raster
+        assert!(is_feasible(&layout, &full_quality, (20.0, 50.0)));
+    }
+
+    #[test]
+    fn custom_zone_item_rules() {
+        let zone = |id: u64, forbidden_for, allowed_for| ExtCustomZone {
+            id,
+            tag: 0,
+            scope: ExtScope::Interior,
+            forbidden_for,
+            allowed_for,
+            shape: ext_rect(id as f32 * 50.0, 0.0, 20.0, 20.0),
+        };
+        let forbidden_for_item_0 = ExtItemSelector {
+            item_ids: vec![0],
+            item_classes: vec![],
+        };
+        let allowed_for_class_7 = ExtItemSelector {
+            item_ids: vec![],
+            item_classes: vec![7],
+        };
+        let ext_cont = ext_container(
+            None,
+            vec![
+                zone(0, Some(forbidden_for_item_0), None),
+                zone(1, None, Some(allowed_for_class_7)),
+            ],
+        );
+        let layout = Layout::new(importer().import_container(&ext_cont).unwrap());
+        let item_0 = importer().import_item(&ext_item(0, None, Some(7))).unwrap();
+        let item_1 = importer().import_item(&ext_item(1, None, None)).unwrap();
+
+        //zone 0 is only forbidden for item 0
+        assert!(!is_feasible(&layout, &item_0, (10.0, 10.0)));
+        assert!(is_feasible(&layout, &item_1, (10.0, 10.0)));
+
+        //zone 1 is reserved for class 7, which only item 0 belongs to
+        assert!(is_feasible(&layout, &item_0, (60.0, 10.0)));
+        assert!(!is_feasible(&layout, &item_1, (60.0, 10.0)));
+
+        //the collision is reported as the custom zone
+        let report = InfeasiblePlacement::check(
+            &layout,
+            &item_0,
+            DTransformation::new(0.0, (10.0, 10.0)),
+            0,
+            None,
+        )
+        .unwrap();
+        assert!(matches!(
+            report.colliding[..],
+            [HazardEntity::Custom { id: 0, .. }]
+        ));
+    }
+
+    #[test]
+    fn custom_zone_with_both_rules_rejected() {
+        let ext_cont = ext_container(
+            None,
+            vec![ExtCustomZone {
+                id: 0,
+                tag: 0,
+                scope: ExtScope::Interior,
+                forbidden_for: Some(ExtItemSelector::default()),
+                allowed_for: Some(ExtItemSelector::default()),
+                shape: ext_rect(0.0, 0.0, 20.0, 20.0),
+            }],
+        );
+        assert!(importer().import_container(&ext_cont).is_err());
+    }
+}
@@ -2,6 +2,7 @@ use crate::collision_detection::hazards::HazardEntity;
 use crate::collision_detection::hazards::collector::BasicHazardCollector;
 use crate::collision_detection::hazards::filter::NoFilter;
 use crate::entities::{Instance, Layout, LayoutSnapshot};
+use crate::geometry::geo_enums::GeoPosition;
 use crate::geometry::geo_traits::Transformable;
 use crate::geometry::primitives::{Circle, Edge};
 use crate::geometry::{DTransformation, Transformation};
@@ -104,7 +105,7 @@ pub fn layout_to_svg(
 
         //quality zones
         for qz in container.quality_zones.iter().rev().flatten() {
-            let color = theme.qz_fill[qz.quality];
+            let color = theme.qz_color(qz.quality);
             let stroke_color = svg_util::change_brightness(color, 0.5);
             for (orig_qz_shape, intern_qz_shape) in qz.shapes_orig.iter().zip(qz.shapes_cd.iter()) {
                 qz_group = qz_group.add(
@@ -129,6 +130,70 @@ pub fn layout_to_svg(
                 );
             }
         }
+
+        //custom zones, both from the container and registered at runtime
+        let static_czs = container.custom_zones.iter().map(|cz| {
+            let data =
+                svg_util::original_shape_data(&cz.shape_orig, &cz.shape_cd, options.draw_cd_shapes);
+            (cz.entity(), data)
+        });
+        let dynamic_czs = layout
+            .cde()
+            .hazards()
+            .filter(|h| h.dynamic && matches!(h.entity, HazardEntity::Custom { .. }))
+            .map(|h| (h.entity, svg_util::simple_polygon_data(&h.shape)));
+        for (entity, data) in static_czs.chain(dynamic_czs) {
+            let HazardEntity::Custom { id, tag, scope } = entity else {
+                unreachable!()
+            };
+            let fill_opacity = match scope {
+                GeoPosition::Interior => "0.50",
+                GeoPosition::Exterior => "0.0",
+            };
+            qz_group = qz_group.add(
+                svg_util::data_to_path(
+                    data,
+                    &[
+                        ("fill", &*format!("{}", theme.hole_fill)),
+                        ("fill-opacity", fill_opacity),
+                        ("stroke", &*format!("{}", theme.hole_fill)),
+                        ("stroke-width", &*format!("{}", 2.0 * stroke_width)),
+                        ("stroke-dasharray", &*format!("{}", 5.0 * stroke_width)),
+                        ("stroke-linecap", "round"),
+                        ("stroke-linejoin", "round"),
+                    ],
+                )
+                .add(Title::new(format!(
+                    "custom zone, id: {id}, tag: {tag}, scope: {scope:?}"
+                ))),
+            );
+        }
+
+        //grain zones
+        for gz in container.grain_zones.iter() {
+            let data =
+                svg_util::original_shape_data(&gz.shape_orig, &gz.shape_cd, options.draw_cd_shapes);
+            qz_group = qz_group.add(
+                svg_util::data_to_path(
+                    data,
+                    &[
+                        ("fill", "none"),
+                        ("stroke", &*format!("{}", theme.hole_fill)),
+                        ("stroke-width", &*format!("{}", stroke_width)),
+                        (
+                            "stroke-dasharray",
+                            &*format!("{} {}", stroke_width, 3.0 * stroke_width),
+                        ),
+                        ("stroke-linecap", "round"),
+                        ("stroke-linejoin", "round"),
+                    ],
+                )
+                .add(Title::new(format!(
+                    "grain zone, allowed rotations: {:?}",
+                    gz.allowed_rotation
+                ))),
+            );
+        }
         qz_group
     };
 
@@ -140,7 +205,7 @@ pub fn layout_to_svg(
         for item in instance.items() {
             let color = match item.min_quality {
                 None => theme.item_fill.to_owned(),
-                Some(q) => svg_util::blend_colors(theme.item_fill, theme.qz_fill[q]),
+                Some(q) => svg_util::blend_colors(theme.item_fill, theme.qz_color(q)),
             };
             item_defs = item_defs.add(Group::new().set("id", format!("item_{}", item.id)).add(
                 svg_util::data_to_path(
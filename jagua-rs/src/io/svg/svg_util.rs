@@ -1,6 +1,5 @@
 use crate::collision_detection::hazards::filter::HazardFilter;
 use crate::collision_detection::quadtree::{QTHazPresence, QTNode};
-use crate::entities::N_QUALITIES;
 use crate::geometry;
 use crate::geometry::OriginalShape;
 use crate::geometry::primitives::{Edge, Point, SPolygon};
@@ -45,7 +44,8 @@ pub struct SvgLayoutTheme {
     pub container_fill: Color,
     pub item_fill: Color,
     pub hole_fill: Color,
-    pub qz_fill: [Color; N_QUALITIES],
+    /// Fill colors of the quality zones, qualities beyond the palette use the last color
+    pub qz_fill: [Color; N_QZ_COLORS],
     pub qz_stroke_opac: f32,
     pub collision_highlight_color: Color,
 }
@@ -56,7 +56,14 @@ impl Default for SvgLayoutTheme {
     }
 }
 
+/// Number of colors in the quality zone palette of a [`SvgLayoutTheme`]
+pub const N_QZ_COLORS: usize = 10;
+
 impl SvgLayoutTheme {
+    pub fn qz_color(&self, quality: usize) -> Color {
+        self.qz_fill[quality.min(N_QZ_COLORS - 1)]
+    }
+
     pub const EARTH_TONES: SvgLayoutTheme = SvgLayoutTheme {
         stroke_width_multiplier: 2.0,
         container_fill: Color(0xCC, 0x82, 0x4A),
@@ -11,6 +11,8 @@ pub struct Bin {
     pub stock: usize,
     /// The cost of using a bin of this type
     pub cost: u64,
+    /// Maximum total weight of the items placed in a bin of this type, unlimited if `None`
+    pub weight_capacity: Option<f32>,
 }
 
 impl Bin {
@@ -21,6 +23,13 @@ impl Bin {
             container,
             stock,
             cost,
+            weight_capacity: None,
         }
     }
+
+    /// Limits the total weight of the items which can be placed in a bin of this type
+    pub fn with_weight_capacity(mut self, weight_capacity: Option<f32>) -> Self {
+        self.weight_capacity = weight_capacity;
+        self
+    }
 }
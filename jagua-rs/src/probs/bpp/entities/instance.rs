@@ -1,22 +1,117 @@
 use crate::entities::Instance;
 use crate::entities::{Container, Item};
+use crate::geometry::DTransformation;
 use crate::probs::bpp::entities::bin::Bin;
-use crate::probs::bpp::util::assertions::instance_item_bin_ids_correct;
+use crate::probs::bpp::entities::{GroupPolicy, ItemGroup};
+use crate::probs::bpp::util::assertions::{
+    allowed_bins_correct, fixed_layouts_within_stock_and_demand,
+    fixed_layouts_within_weight_capacity, instance_item_bin_ids_correct, item_groups_correct,
+    min_demands_correct,
+};
 
 #[derive(Debug, Clone)]
 /// Instance of the Bin Packing Problem.
 pub struct BPInstance {
-    /// The items to be packed and their demands
+    /// The items to be packed and their (maximum) demands
     pub items: Vec<(Item, usize)>,
+    /// The minimum demand of each item, equal to its demand unless part of it is optional
+    pub item_min_qtys: Vec<usize>,
     /// Set of bins available to pack the items
     pub bins: Vec<Bin>,
+    /// Bins which are already in use at the start, containing items which cannot be removed
+    pub fixed_layouts: Vec<BPFixedLayout>,
+    /// Per item, the ids of the bins it may be placed in, `None` if it is allowed in all bins
+    pub allowed_bins: Vec<Option<Vec<usize>>>,
+    /// Groups of items which should be kept together, an item belongs to at most one group
+    pub item_groups: Vec<ItemGroup>,
+    /// Per item, the id of the group it belongs to
+    item_group_ids: Vec<Option<usize>>,
 }
 
 impl BPInstance {
-    pub fn new(items: Vec<(Item, usize)>, bins: Vec<Bin>) -> Self {
+    pub fn new(
+        items: Vec<(Item, usize)>,
+        bins: Vec<Bin>,
+        fixed_layouts: Vec<BPFixedLayout>,
+    ) -> Self {
         assert!(instance_item_bin_ids_correct(&items, &bins));
+        assert!(
+            fixed_layouts_within_stock_and_demand(&items, &bins, &fixed_layouts),
+            "Fixed layouts should refer to existing bins and items and not exceed their stock or demand"
+        );
+        assert!(
+            fixed_layouts_within_weight_capacity(&items, &bins, &fixed_layouts),
+            "Fixed layouts should not exceed the weight capacity of their bin"
+        );
 
-        Self { items, bins }
+        let item_min_qtys = items.iter().map(|(_, qty)| *qty).collect();
+        let allowed_bins = vec![None; items.len()];
+        let item_group_ids = vec![None; items.len()];
+
+        Self {
+            items,
+            item_min_qtys,
+            bins,
+            fixed_layouts,
+            allowed_bins,
+            item_groups: vec![],
+            item_group_ids,
+        }
+    }
+
+    /// Adds groups of items which should be kept together, see [`GroupPolicy`]
+    pub fn with_item_groups(mut self, item_groups: Vec<ItemGroup>) -> Self {
+        assert!(
+            item_groups_correct(&item_groups, &self.items, &self.fixed_layouts),
+            "Item groups should have consecutive ids, refer to existing items without overlapping, and not split fixed items of a same-bin group"
+        );
+        self.item_group_ids = vec![None; self.items.len()];
+        for group in item_groups.iter() {
+            for item_id in group.item_ids.iter() {
+                self.item_group_ids[*item_id] = Some(group.id);
+            }
+        }
+        self.item_groups = item_groups;
+        self
+    }
+
+    /// Returns the group the item belongs to, if any
+    pub fn item_group(&self, item_id: usize) -> Option<&ItemGroup> {
+        self.item_group_ids[item_id].map(|g_id| &self.item_groups[g_id])
+    }
+
+    /// Whether the item belongs to a group which has to be placed in a single bin
+    pub fn item_in_same_bin_group(&self, item_id: usize) -> bool {
+        self.item_group(item_id)
+            .is_some_and(|g| g.policy == GroupPolicy::SameBin)
+    }
+
+    /// Makes part of the demand of the items optional, only `min_qtys` (indexed by item id) have to be placed
+    pub fn with_min_demands(mut self, min_qtys: Vec<usize>) -> Self {
+        assert!(
+            min_demands_correct(&self.items, &min_qtys),
+            "Minimum demands should be defined for every item and not exceed its demand"
+        );
+        self.item_min_qtys = min_qtys;
+        self
+    }
+
+    /// Restricts the bins in which each item may be placed, indexed by item id
+    pub fn with_allowed_bins(mut self, allowed_bins: Vec<Option<Vec<usize>>>) -> Self {
+        assert!(
+            allowed_bins_correct(&allowed_bins, &self.items, &self.bins, &self.fixed_layouts),
+            "Allowed bins should refer to existing bins and include the bins of the fixed layouts"
+        );
+        self.allowed_bins = allowed_bins;
+        self
+    }
+
+    /// Whether the item may be placed in a bin of the given type
+    pub fn item_allowed_in_bin(&self, item_id: usize, bin_id: usize) -> bool {
+        match &self.allowed_bins[item_id] {
+            Some(bin_ids) => bin_ids.contains(&bin_id),
+            None => true,
+        }
     }
 
     pub fn item_area(&self) -> f32 {
@@ -41,6 +136,14 @@ impl BPInstance {
     pub fn total_item_qty(&self) -> usize {
         self.items.iter().map(|(_, qty)| *qty).sum()
     }
+
+    pub fn item_min_qty(&self, id: usize) -> usize {
+        self.item_min_qtys[id]
+    }
+
+    pub fn total_item_min_qty(&self) -> usize {
+        self.item_min_qtys.iter().sum()
+    }
 }
 
 impl Instance for BPInstance {
@@ -60,3 +163,13 @@ impl Instance for BPInstance {
         &self.bins[id].container
     }
 }
+
+/// A bin which is in use from the start, e.g. a partially used sheet.
+/// Its items are pre-placed, cannot be removed and count towards the demand of their item.
+#[derive(Debug, Clone)]
+pub struct BPFixedLayout {
+    /// The id of the bin that is in use
+    pub bin_id: usize,
+    /// The pre-placed items, as pairs of item id and transformation
+    pub placements: Vec<(usize, DTransformation)>,
+}
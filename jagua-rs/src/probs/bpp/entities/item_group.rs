@@ -0,0 +1,29 @@
+/// A set of items which belong together, e.g. the parts of the same customer order.
+#[derive(Debug, Clone)]
+pub struct ItemGroup {
+    /// Unique identifier for the group
+    pub id: usize,
+    /// The ids of the items in the group, all their copies count as members
+    pub item_ids: Vec<usize>,
+    /// How strictly the group should be kept together
+    pub policy: GroupPolicy,
+}
+
+impl ItemGroup {
+    pub fn new(id: usize, item_ids: Vec<usize>, policy: GroupPolicy) -> Self {
+        Self {
+            id,
+            item_ids,
+            policy,
+        }
+    }
+}
+
+/// Determines how the members of an [`ItemGroup`] are to be distributed over the bins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupPolicy {
+    /// Hard constraint: all members have to be placed in the same bin
+    SameBin,
+    /// Soft constraint: the members should be spread over as few bins as possible
+    MinimizeBins,
+}
@@ -1,13 +1,20 @@
 mod bin;
 mod instance;
+mod item_group;
 mod problem;
 mod solution;
 
 #[doc(inline)]
 pub use bin::Bin;
 #[doc(inline)]
+pub use instance::BPFixedLayout;
+#[doc(inline)]
 pub use instance::BPInstance;
 #[doc(inline)]
+pub use item_group::GroupPolicy;
+#[doc(inline)]
+pub use item_group::ItemGroup;
+#[doc(inline)]
 pub use problem::BPLayoutType;
 #[doc(inline)]
 pub use problem::BPPlacement;
@@ -17,3 +24,5 @@ pub use problem::BPProblem;
 pub use problem::LayKey;
 #[doc(inline)]
 pub use solution::BPSolution;
+#[doc(inline)]
+pub use solution::BPSolutionSeed;
@@ -5,9 +5,10 @@ use crate::entities::{PItemKey, PlacedItem};
 use crate::geometry::DTransformation;
 use crate::probs::bpp::entities::BPInstance;
 use crate::probs::bpp::entities::BPSolution;
+use crate::probs::bpp::entities::GroupPolicy;
 use crate::probs::bpp::util::assertions::problem_matches_solution;
 use itertools::Itertools;
-use slotmap::{SlotMap, new_key_type};
+use slotmap::{SecondaryMap, SlotMap, new_key_type};
 
 new_key_type! {
     /// Unique key for each [`Layout`] in a [`BPProblem`] and [`BPSolution`]
@@ -19,30 +20,74 @@ new_key_type! {
 pub struct BPProblem {
     pub instance: BPInstance,
     pub layouts: SlotMap<LayKey, Layout>,
+    /// Number of copies of each item which can still be placed before its (maximum) demand is reached
     pub item_demand_qtys: Vec<usize>,
     pub bin_stock_qtys: Vec<usize>,
+    /// Weight which can still be added to each layout, `f32::INFINITY` if its bin has no weight capacity
+    pub remaining_weight_capacities: SecondaryMap<LayKey, f32>,
+    /// Per [`ItemGroup`](crate::probs::bpp::entities::ItemGroup), the number of its members placed in each layout it spans
+    pub group_layout_qtys: Vec<SecondaryMap<LayKey, usize>>,
 }
 
 impl BPProblem {
     pub fn new(instance: BPInstance) -> Self {
         let item_demand_qtys = instance.items.iter().map(|(_, qty)| *qty).collect_vec();
         let bin_stock_qtys = instance.bins.iter().map(|bin| bin.stock).collect_vec();
+        let group_layout_qtys = vec![SecondaryMap::new(); instance.item_groups.len()];
 
-        Self {
+        let mut problem = Self {
             instance,
             layouts: SlotMap::with_key(),
             item_demand_qtys,
             bin_stock_qtys,
+            remaining_weight_capacities: SecondaryMap::new(),
+            group_layout_qtys,
+        };
+
+        //open the bins which are in use from the start, with their fixed items
+        for fl in problem.instance.fixed_layouts.clone() {
+            let mut layout = Layout::new(problem.instance.bins[fl.bin_id].container.clone());
+            for (item_id, d_transf) in fl.placements {
+                layout.place_fixed_item(problem.instance.item(item_id), d_transf);
+            }
+            problem.register_layout(layout);
         }
+
+        problem
     }
 
     /// Removes a layout from the problem. The bin used by the layout will be closed and all items placed inside it will be deregistered.
+    /// Panics if the layout contains fixed items.
     pub fn remove_layout(&mut self, key: LayKey) {
+        assert!(
+            self.layouts[key].placed_items.values().all(|pi| !pi.fixed),
+            "layouts containing fixed items cannot be removed"
+        );
         self.deregister_layout(key);
     }
 
     /// Places an item according to the provided [`BPPlacement`] in the problem.
+    /// The placement has to satisfy the constraints imposed by the problem, which the caller should check beforehand
+    /// with [`BPProblem::placement_allowed`]. Panics if the item is not allowed in the bin of the layout,
+    /// exceeds its remaining weight capacity or would split a [`GroupPolicy::SameBin`] group.
     pub fn place_item(&mut self, p_opt: BPPlacement) -> (LayKey, PItemKey) {
+        assert!(
+            self.group_allows(p_opt.layout_id, p_opt.item_id),
+            "item {} would split its group over multiple bins",
+            p_opt.item_id
+        );
+        assert!(
+            self.item_allowed_in(p_opt.layout_id, p_opt.item_id),
+            "item {} is not allowed in {:?}",
+            p_opt.item_id,
+            p_opt.layout_id
+        );
+        assert!(
+            self.fits_weight(p_opt.layout_id, p_opt.item_id),
+            "item {} exceeds the remaining weight capacity of {:?}",
+            p_opt.item_id,
+            p_opt.layout_id
+        );
         let lkey = match p_opt.layout_id {
             BPLayoutType::Open(lkey) => lkey,
             BPLayoutType::Closed { bin_id } => {
@@ -57,21 +102,30 @@ impl BPProblem {
         let pik = layout.place_item(item, p_opt.d_transf);
 
         self.register_included_item(p_opt.item_id);
+        self.register_group_member(lkey, p_opt.item_id);
+        self.update_remaining_weight_capacity(lkey);
 
         (lkey, pik)
     }
 
     /// Removes an item from a layout. If the layout is empty, it will be closed.
     /// Set `commit_instantly` to false if there's a high chance that this modification will be reverted.
+    /// Panics if the item is fixed.
     pub fn remove_item(&mut self, lkey: LayKey, pik: PItemKey) -> BPPlacement {
+        assert!(
+            !self.layouts[lkey].placed_items[pik].fixed,
+            "fixed items cannot be removed"
+        );
         let pi = self.layouts[lkey].remove_item(pik);
         self.deregister_included_item(pi.item_id);
+        self.deregister_group_member(lkey, pi.item_id);
         if self.layouts[lkey].is_empty() {
             //if layout is empty, close it
             let bin_id = self.layouts[lkey].container.id;
             self.deregister_layout(lkey);
             BPPlacement::from_placed_item(BPLayoutType::Closed { bin_id }, &pi)
         } else {
+            self.update_remaining_weight_capacity(lkey);
             BPPlacement::from_placed_item(BPLayoutType::Open(lkey), &pi)
         }
     }
@@ -124,7 +178,7 @@ impl BPProblem {
             }
         }
 
-        //Restore the item demands and bin stocks
+        //Restore the item demands, bin stocks, remaining weight capacities and group spans
         {
             self.item_demand_qtys
                 .iter_mut()
@@ -147,6 +201,21 @@ impl BPProblem {
                     .values()
                     .for_each(|pi| self.item_demand_qtys[pi.item_id] -= 1);
             });
+
+            self.remaining_weight_capacities.clear();
+            let lkeys = self.layouts.keys().collect_vec();
+            self.group_layout_qtys.iter_mut().for_each(|g| g.clear());
+            for lkey in lkeys {
+                self.update_remaining_weight_capacity(lkey);
+                let item_ids = self.layouts[lkey]
+                    .placed_items
+                    .values()
+                    .map(|pi| pi.item_id)
+                    .collect_vec();
+                item_ids
+                    .into_iter()
+                    .for_each(|item_id| self.register_group_member(lkey, item_id));
+            }
         }
 
         debug_assert!(problem_matches_solution(self, solution));
@@ -168,6 +237,17 @@ impl BPProblem {
         total_item_area / total_bin_area
     }
 
+    /// Returns the number of copies of the item which still have to be placed to meet its minimum demand
+    pub fn item_min_demand_qty(&self, item_id: usize) -> usize {
+        let placed = self.instance.item_qty(item_id) - self.item_demand_qtys[item_id];
+        self.instance.item_min_qty(item_id).saturating_sub(placed)
+    }
+
+    /// Whether the minimum demand of every item is met
+    pub fn min_demands_met(&self) -> bool {
+        (0..self.instance.items.len()).all(|id| self.item_min_demand_qty(id) == 0)
+    }
+
     pub fn item_placed_qtys(&self) -> impl Iterator<Item = usize> {
         self.item_demand_qtys
             .iter()
@@ -190,22 +270,121 @@ impl BPProblem {
             .sum()
     }
 
+    /// Whether placing an item in a layout satisfies all constraints imposed by the problem,
+    /// see [`BPProblem::item_allowed_in`], [`BPProblem::group_allows`] and [`BPProblem::fits_weight`].
+    /// These are the preconditions of [`BPProblem::place_item`].
+    pub fn placement_allowed(&self, layout_id: BPLayoutType, item_id: usize) -> bool {
+        self.item_allowed_in(layout_id, item_id)
+            && self.group_allows(layout_id, item_id)
+            && self.fits_weight(layout_id, item_id)
+    }
+
+    /// Whether an item may be placed in a layout, according to [`BPInstance::allowed_bins`].
+    pub fn item_allowed_in(&self, layout_id: BPLayoutType, item_id: usize) -> bool {
+        let bin_id = match layout_id {
+            BPLayoutType::Open(lkey) => self.layouts[lkey].container.id,
+            BPLayoutType::Closed { bin_id } => bin_id,
+        };
+        self.instance.item_allowed_in_bin(item_id, bin_id)
+    }
+
+    /// Whether an item can be added to a layout without splitting a [`GroupPolicy::SameBin`](crate::probs::bpp::entities::GroupPolicy::SameBin) group.
+    pub fn group_allows(&self, layout_id: BPLayoutType, item_id: usize) -> bool {
+        let Some(group) = self.instance.item_group(item_id) else {
+            return true;
+        };
+        let spanned = &self.group_layout_qtys[group.id];
+        match (group.policy, layout_id) {
+            (GroupPolicy::MinimizeBins, _) => true,
+            (GroupPolicy::SameBin, BPLayoutType::Open(lkey)) => {
+                spanned.is_empty() || spanned.contains_key(lkey)
+            }
+            (GroupPolicy::SameBin, BPLayoutType::Closed { .. }) => spanned.is_empty(),
+        }
+    }
+
+    /// Returns the number of layouts in which members of the group are placed
+    pub fn group_bins_spanned(&self, group_id: usize) -> usize {
+        self.group_layout_qtys[group_id].len()
+    }
+
+    /// Returns the total number of layouts spanned by the groups beyond the first one each.
+    /// Zero if every group is kept together.
+    pub fn group_excess_bins(&self) -> usize {
+        self.group_layout_qtys
+            .iter()
+            .map(|g| g.len().saturating_sub(1))
+            .sum()
+    }
+
+    /// Returns the weight which can still be added to a layout, `f32::INFINITY` if its bin has no weight capacity.
+    pub fn remaining_weight_capacity(&self, layout_id: BPLayoutType) -> f32 {
+        match layout_id {
+            BPLayoutType::Open(lkey) => self.remaining_weight_capacities[lkey],
+            BPLayoutType::Closed { bin_id } => self.instance.bins[bin_id]
+                .weight_capacity
+                .unwrap_or(f32::INFINITY),
+        }
+    }
+
+    /// Whether an item can be added to a layout without exceeding the weight capacity of its bin.
+    pub fn fits_weight(&self, layout_id: BPLayoutType, item_id: usize) -> bool {
+        self.instance.item(item_id).weight <= self.remaining_weight_capacity(layout_id)
+    }
+
     fn register_layout(&mut self, layout: Layout) -> LayKey {
         self.open_bin(layout.container.id);
-        layout
+        let item_ids = layout
             .placed_items
             .values()
-            .for_each(|pi| self.register_included_item(pi.item_id));
-        self.layouts.insert(layout)
+            .map(|pi| pi.item_id)
+            .collect_vec();
+        let lkey = self.layouts.insert(layout);
+        for item_id in item_ids {
+            self.register_included_item(item_id);
+            self.register_group_member(lkey, item_id);
+        }
+        self.update_remaining_weight_capacity(lkey);
+        lkey
     }
 
     fn deregister_layout(&mut self, key: LayKey) {
+        self.remaining_weight_capacities.remove(key);
         let layout = self.layouts.remove(key).expect("layout key not present");
         self.close_bin(layout.container.id);
-        layout
-            .placed_items
-            .values()
-            .for_each(|pi| self.deregister_included_item(pi.item_id));
+        for pi in layout.placed_items.values() {
+            self.deregister_included_item(pi.item_id);
+            self.deregister_group_member(key, pi.item_id);
+        }
+    }
+
+    /// Recomputes the remaining weight capacity of a layout from scratch, to avoid accumulating rounding errors
+    fn update_remaining_weight_capacity(&mut self, lkey: LayKey) {
+        let layout = &self.layouts[lkey];
+        let remaining = match self.instance.bins[layout.container.id].weight_capacity {
+            Some(capacity) => capacity - layout.placed_item_weight(&self.instance),
+            None => f32::INFINITY,
+        };
+        self.remaining_weight_capacities.insert(lkey, remaining);
+    }
+
+    fn register_group_member(&mut self, lkey: LayKey, item_id: usize) {
+        if let Some(group) = self.instance.item_group(item_id) {
+            *self.group_layout_qtys[group.id]
+                .entry(lkey)
+                .expect("layout key not present")
+                .or_insert(0) += 1;
+        }
+    }
+
+    fn deregister_group_member(&mut self, lkey: LayKey, item_id: usize) {
+        if let Some(group) = self.instance.item_group(item_id) {
+            let qtys = &mut self.group_layout_qtys[group.id];
+            qtys[lkey] -= 1;
+            if qtys[lkey] == 0 {
+                qtys.remove(lkey);
+            }
+        }
     }
 
     fn register_included_item(&mut self, item_id: usize) {
@@ -254,3 +433,322 @@ pub enum BPLayoutType {
     /// A layout that does not yet exist, but can be created by 'opening' a new bin
     Closed { bin_id: usize },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::probs::bpp::entities::{BPFixedLayout, Bin, ItemGroup};
+    use crate::util::test_util::{container, item};
+
+    /// Two bins of type 0, one of which is in use from the start with a fixed copy of item 1
+    fn problem_with_fixed_layout() -> BPProblem {
+        let fixed_layout = BPFixedLayout {
+            bin_id: 0,
+            placements: vec![(1, DTransformation::new(0.0, (10.0, 10.0)))],
+        };
+        BPProblem::new(BPInstance::new(
+            vec![(item(0, 10.0, 10.0), 2), (item(1, 20.0, 20.0), 2)],
+            vec![Bin::new(container(0, 50.0, 50.0), 2, 10)],
+            vec![fixed_layout],
+        ))
+    }
+
+    fn fixed_keys(problem: &BPProblem) -> (LayKey, PItemKey) {
+        let (lkey, layout) = problem.layouts.iter().next().unwrap();
+        let (pk, pi) = layout.placed_items.iter().next().unwrap();
+        assert!(pi.fixed);
+        (lkey, pk)
+    }
+
+    #[test]
+    fn fixed_items_placed_and_counted() {
+        let problem = problem_with_fixed_layout();
+        assert_eq!(problem.layouts.len(), 1);
+        let (lkey, pk) = fixed_keys(&problem);
+        assert_eq!(problem.layouts[lkey].placed_items[pk].item_id, 1);
+        assert_eq!(problem.item_demand_qtys, vec![2, 1]);
+        assert_eq!(problem.bin_stock_qtys, vec![1]);
+        assert_eq!(problem.bin_cost(), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "fixed items cannot be removed")]
+    fn fixed_items_not_removable() {
+        let mut problem = problem_with_fixed_layout();
+        let (lkey, pk) = fixed_keys(&problem);
+        problem.remove_item(lkey, pk);
+    }
+
+    #[test]
+    #[should_panic(expected = "layouts containing fixed items cannot be removed")]
+    fn fixed_layouts_not_removable() {
+        let mut problem = problem_with_fixed_layout();
+        let (lkey, _) = fixed_keys(&problem);
+        problem.remove_layout(lkey);
+    }
+
+    #[test]
+    fn fixed_items_survive_restore() {
+        let mut problem = problem_with_fixed_layout();
+        let initial = problem.save();
+        let (lkey, _) = fixed_keys(&problem);
+
+        //add an item next to the fixed one and open a second bin
+        problem.place_item(BPPlacement {
+            layout_id: BPLayoutType::Open(lkey),
+            item_id: 0,
+            d_transf: DTransformation::new(0.0, (35.0, 10.0)),
+        });
+        problem.place_item(BPPlacement {
+            layout_id: BPLayoutType::Closed { bin_id: 0 },
+            item_id: 1,
+            d_transf: DTransformation::new(0.0, (10.0, 10.0)),
+        });
+        let extended = problem.save();
+        assert_eq!(problem.item_demand_qtys, vec![1, 0]);
+
+        //remove the item next to the fixed one again
+        let pk = problem.layouts[lkey]
+            .placed_items
+            .iter()
+            .find(|(_, pi)| !pi.fixed)
+            .map(|(pk, _)| pk)
+            .unwrap();
+        problem.remove_item(lkey, pk);
+        problem.restore(&extended);
+        assert_eq!(problem.layouts.len(), 2);
+        assert_eq!(problem.layouts[lkey].placed_items.len(), 2);
+        assert_eq!(
+            problem.layouts[lkey]
+                .placed_items
+                .values()
+                .filter(|pi| pi.fixed)
+                .count(),
+            1
+        );
+        assert_eq!(problem.item_demand_qtys, vec![1, 0]);
+
+        problem.restore(&initial);
+        assert_eq!(problem.layouts.len(), 1);
+        assert_eq!(fixed_keys(&problem).0, lkey);
+        assert_eq!(problem.item_demand_qtys, vec![2, 1]);
+        assert_eq!(problem.bin_stock_qtys, vec![1]);
+    }
+
+    /// Places an item with its bottom left corner at `pos`
+    fn place(
+        problem: &mut BPProblem,
+        layout_id: BPLayoutType,
+        item_id: usize,
+        pos: (f32, f32),
+    ) -> (LayKey, PItemKey) {
+        problem.place_item(BPPlacement {
+            layout_id,
+            item_id,
+            d_transf: DTransformation::new(0.0, pos),
+        })
+    }
+
+    /// Two bins of type 0 which can carry 10, and three copies of item 0 weighing 4 each
+    fn problem_with_weights() -> BPProblem {
+        let mut heavy = item(0, 10.0, 10.0);
+        heavy.weight = 4.0;
+        BPProblem::new(BPInstance::new(
+            vec![(heavy, 3)],
+            vec![Bin::new(container(0, 50.0, 50.0), 2, 10).with_weight_capacity(Some(10.0))],
+            vec![],
+        ))
+    }
+
+    #[test]
+    fn weight_capacity() {
+        let mut problem = problem_with_weights();
+        let closed = BPLayoutType::Closed { bin_id: 0 };
+        assert_eq!(problem.remaining_weight_capacity(closed), 10.0);
+
+        let (lkey, pk) = place(&mut problem, closed, 0, (5.0, 5.0));
+        place(&mut problem, BPLayoutType::Open(lkey), 0, (20.0, 5.0));
+        let full = problem.save();
+        assert_eq!(
+            problem.remaining_weight_capacity(BPLayoutType::Open(lkey)),
+            2.0
+        );
+
+        //the capacity is binding for the open layout, but not for a new one
+        assert!(!problem.fits_weight(BPLayoutType::Open(lkey), 0));
+        assert!(!problem.placement_allowed(BPLayoutType::Open(lkey), 0));
+        assert!(problem.fits_weight(closed, 0));
+
+        //removing an item frees up its weight, restoring claims it again
+        problem.remove_item(lkey, pk);
+        assert_eq!(
+            problem.remaining_weight_capacity(BPLayoutType::Open(lkey)),
+            6.0
+        );
+        assert!(problem.fits_weight(BPLayoutType::Open(lkey), 0));
+        problem.restore(&full);
+        assert_eq!(
+            problem.remaining_weight_capacity(BPLayoutType::Open(lkey)),
+            2.0
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "item 0 exceeds the remaining weight capacity")]
+    fn overweight_placement_rejected() {
+        let mut problem = problem_with_weights();
+        let (lkey, _) = place(
+            &mut problem,
+            BPLayoutType::Closed { bin_id: 0 },
+            0,
+            (5.0, 5.0),
+        );
+        place(&mut problem, BPLayoutType::Open(lkey), 0, (20.0, 5.0));
+        place(&mut problem, BPLayoutType::Open(lkey), 0, (35.0, 5.0));
+    }
+
+    /// Bins of type 0 and 1, item 0 is only allowed in bins of type 1
+    fn problem_with_allowed_bins() -> BPProblem {
+        BPProblem::new(
+            BPInstance::new(
+                vec![(item(0, 10.0, 10.0), 2), (item(1, 10.0, 10.0), 2)],
+                vec![
+                    Bin::new(container(0, 50.0, 50.0), 2, 10),
+                    Bin::new(container(1, 50.0, 50.0), 2, 10),
+                ],
+                vec![],
+            )
+            .with_allowed_bins(vec![Some(vec![1]), None]),
+        )
+    }
+
+    #[test]
+    fn allowed_bins() {
+        let mut problem = problem_with_allowed_bins();
+        let (bin_0, bin_1) = (
+            BPLayoutType::Closed { bin_id: 0 },
+            BPLayoutType::Closed { bin_id: 1 },
+        );
+        assert!(!problem.item_allowed_in(bin_0, 0) && problem.item_allowed_in(bin_1, 0));
+        assert!(problem.item_allowed_in(bin_0, 1) && problem.item_allowed_in(bin_1, 1));
+
+        //the restriction also holds for the open layouts of the bin type
+        let (lkey, _) = place(&mut problem, bin_0, 1, (5.0, 5.0));
+        assert!(!problem.item_allowed_in(BPLayoutType::Open(lkey), 0));
+        assert!(!problem.placement_allowed(BPLayoutType::Open(lkey), 0));
+        assert!(problem.placement_allowed(BPLayoutType::Open(lkey), 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "item 0 is not allowed in")]
+    fn ineligible_bin_rejected() {
+        let mut problem = problem_with_allowed_bins();
+        place(
+            &mut problem,
+            BPLayoutType::Closed { bin_id: 0 },
+            0,
+            (5.0, 5.0),
+        );
+    }
+
+    /// Items 0 and 1 have to share a bin, items 2 and 3 should be spread over as few bins as possible
+    fn problem_with_groups() -> BPProblem {
+        BPProblem::new(
+            BPInstance::new(
+                (0..4).map(|id| (item(id, 10.0, 10.0), 2)).collect(),
+                vec![Bin::new(container(0, 50.0, 50.0), 3, 10)],
+                vec![],
+            )
+            .with_item_groups(vec![
+                ItemGroup::new(0, vec![0, 1], GroupPolicy::SameBin),
+                ItemGroup::new(1, vec![2, 3], GroupPolicy::MinimizeBins),
+            ]),
+        )
+    }
+
+    #[test]
+    fn same_bin_group() {
+        let mut problem = problem_with_groups();
+        let closed = BPLayoutType::Closed { bin_id: 0 };
+        assert!(problem.group_allows(closed, 1));
+
+        //once a member is placed, the others have to follow it
+        let (lkey, pk) = place(&mut problem, closed, 0, (5.0, 5.0));
+        assert!(!problem.group_allows(closed, 1));
+        assert!(!problem.placement_allowed(closed, 1));
+        assert!(problem.group_allows(BPLayoutType::Open(lkey), 1));
+        assert!(problem.group_allows(closed, 2));
+        place(&mut problem, BPLayoutType::Open(lkey), 1, (20.0, 5.0));
+        assert_eq!(problem.group_bins_spanned(0), 1);
+        assert_eq!(problem.group_excess_bins(), 0);
+
+        //the group is free again once its members are removed
+        problem.remove_item(lkey, pk);
+        assert!(!problem.group_allows(closed, 0));
+        let pk = problem.layouts[lkey].placed_items.keys().next().unwrap();
+        problem.remove_item(lkey, pk);
+        assert_eq!(problem.group_bins_spanned(0), 0);
+        assert!(problem.group_allows(closed, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "item 1 would split its group over multiple bins")]
+    fn split_same_bin_group_rejected() {
+        let mut problem = problem_with_groups();
+        place(
+            &mut problem,
+            BPLayoutType::Closed { bin_id: 0 },
+            0,
+            (5.0, 5.0),
+        );
+        place(
+            &mut problem,
+            BPLayoutType::Closed { bin_id: 0 },
+            1,
+            (5.0, 5.0),
+        );
+    }
+
+    #[test]
+    fn minimize_bins_group() {
+        let mut problem = problem_with_groups();
+        let closed = BPLayoutType::Closed { bin_id: 0 };
+
+        //members may be spread, but every bin beyond the first is counted
+        let (lkey, _) = place(&mut problem, closed, 2, (5.0, 5.0));
+        assert!(problem.group_allows(closed, 3));
+        place(&mut problem, closed, 3, (5.0, 5.0));
+        assert_eq!(problem.group_bins_spanned(1), 2);
+        assert_eq!(problem.group_excess_bins(), 1);
+        let spread = problem.save();
+
+        place(&mut problem, BPLayoutType::Open(lkey), 3, (20.0, 5.0));
+        assert_eq!(problem.group_bins_spanned(1), 2);
+        problem.restore(&spread);
+        assert_eq!(problem.group_bins_spanned(1), 2);
+        assert_eq!(problem.group_layout_qtys[1][lkey], 1);
+    }
+
+    #[test]
+    fn min_demands() {
+        //one copy of item 1 is fixed, a second one is optional, and both copies of item 0 are mandatory
+        let mut problem = problem_with_fixed_layout();
+        problem.instance = problem.instance.clone().with_min_demands(vec![2, 1]);
+        let (lkey, _) = fixed_keys(&problem);
+        assert_eq!(problem.item_min_demand_qty(0), 2);
+        assert_eq!(problem.item_min_demand_qty(1), 0);
+
+        //the mandatory copies may be spread over multiple bins
+        place(&mut problem, BPLayoutType::Open(lkey), 0, (35.0, 5.0));
+        assert!(!problem.min_demands_met());
+        place(
+            &mut problem,
+            BPLayoutType::Closed { bin_id: 0 },
+            0,
+            (5.0, 5.0),
+        );
+        assert!(problem.min_demands_met());
+        assert!(problem.save().min_demands_met(&problem.instance));
+        assert_eq!(problem.item_demand_qtys, vec![0, 1]);
+    }
+}
@@ -1,10 +1,13 @@
 use crate::Instant;
-use crate::entities::LayoutSnapshot;
+use crate::entities::{Instance, LayoutSnapshot, LayoutSnapshotData, Remnant, RemnantConfig};
 use crate::probs::bpp::entities::{BPInstance, LayKey};
+use serde::de::{DeserializeSeed, Error};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use slotmap::SecondaryMap;
 
 /// Snapshot of [`BPProblem`](crate::probs::bpp::entities::BPProblem) at a specific moment.
 /// Can be used to restore to a previous state.
+/// Serializes without its time stamp, deserialize using [`BPSolutionSeed`].
 #[derive(Debug, Clone)]
 pub struct BPSolution {
     /// A map of the layout snapshots, identified by the same keys as in the problem
@@ -30,6 +33,53 @@ impl BPSolution {
         total_item_area / total_bin_area
     }
 
+    /// Returns, per [`ItemGroup`](crate::probs::bpp::entities::ItemGroup), the number of layouts in which its members are placed
+    pub fn group_bins_spanned(&self, instance: &BPInstance) -> Vec<usize> {
+        instance
+            .item_groups
+            .iter()
+            .map(|group| {
+                self.layout_snapshots
+                    .values()
+                    .filter(|ls| {
+                        ls.placed_items
+                            .values()
+                            .any(|pi| group.item_ids.contains(&pi.item_id))
+                    })
+                    .count()
+            })
+            .collect()
+    }
+
+    /// Returns the total number of layouts spanned by the groups beyond the first one each
+    pub fn group_excess_bins(&self, instance: &BPInstance) -> usize {
+        self.group_bins_spanned(instance)
+            .into_iter()
+            .map(|n| n.saturating_sub(1))
+            .sum()
+    }
+
+    /// Whether the minimum demand of every item is met
+    pub fn min_demands_met(&self, instance: &BPInstance) -> bool {
+        let mut placed = vec![0; instance.items.len()];
+        self.layout_snapshots
+            .values()
+            .flat_map(|ls| ls.placed_items.values())
+            .for_each(|pi| placed[pi.item_id] += 1);
+        placed
+            .into_iter()
+            .enumerate()
+            .all(|(id, qty)| qty >= instance.item_min_qty(id))
+    }
+
+    /// Returns the largest reusable remnant of every layout which has one, see [`LayoutSnapshot::largest_remnant`]
+    pub fn largest_remnants(&self, config: RemnantConfig) -> SecondaryMap<LayKey, Remnant> {
+        self.layout_snapshots
+            .iter()
+            .filter_map(|(lkey, ls)| ls.largest_remnant(config).map(|r| (lkey, r)))
+            .collect()
+    }
+
     pub fn cost(&self, instance: &BPInstance) -> u64 {
         self.layout_snapshots
             .values()
@@ -38,3 +88,144 @@ impl BPSolution {
             .sum()
     }
 }
+
+impl Serialize for BPSolution {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct BPSolutionRef<'a> {
+            layout_snapshots: Vec<(LayKey, &'a LayoutSnapshot)>,
+        }
+        BPSolutionRef {
+            layout_snapshots: self.layout_snapshots.iter().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a [`BPSolution`] and rebuilds it against the (imported) instance it belongs to.
+/// The time stamp of the solution is set to the moment of deserialization.
+pub struct BPSolutionSeed<'a>(pub &'a BPInstance);
+
+impl<'de> DeserializeSeed<'de> for BPSolutionSeed<'_> {
+    type Value = BPSolution;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        #[derive(Deserialize)]
+        struct BPSolutionData {
+            layout_snapshots: Vec<(LayKey, LayoutSnapshotData)>,
+        }
+        let data = BPSolutionData::deserialize(deserializer)?;
+        let instance = self.0;
+
+        let mut layout_snapshots = SecondaryMap::new();
+        for (lkey, ls_data) in data.layout_snapshots {
+            let bin_id = ls_data.container_id;
+            if bin_id >= instance.bins.len() {
+                return Err(D::Error::custom(format!(
+                    "solution refers to unknown bin {bin_id}"
+                )));
+            }
+            let container = instance.container(bin_id).clone();
+            let ls = LayoutSnapshot::rebuild(ls_data, container, instance)
+                .map_err(|e| D::Error::custom(format!("{e:#}")))?;
+            layout_snapshots.insert(lkey, ls);
+        }
+
+        Ok(BPSolution {
+            layout_snapshots,
+            time_stamp: Instant::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::DTransformation;
+    use crate::probs::bpp::entities::{BPLayoutType, BPPlacement, BPProblem, Bin};
+    use crate::util::test_util::{container, item};
+
+    #[test]
+    fn serde_round_trip() {
+        let instance = BPInstance::new(
+            vec![(item(0, 10.0, 10.0), 3), (item(1, 30.0, 5.0), 2)],
+            vec![
+                Bin::new(container(0, 50.0, 50.0), 2, 10),
+                Bin::new(container(1, 100.0, 20.0), 1, 15),
+            ],
+            vec![],
+        );
+        let mut problem = BPProblem::new(instance.clone());
+        let place = |problem: &mut BPProblem, layout_id, item_id, pos| {
+            let d_transf = DTransformation::new(0.0, pos);
+            problem.place_item(BPPlacement {
+                layout_id,
+                item_id,
+                d_transf,
+            })
+        };
+        let (lk_a, _) = place(
+            &mut problem,
+            BPLayoutType::Closed { bin_id: 1 },
+            1,
+            (0.0, 0.0),
+        );
+        let (lk_b, _) = place(
+            &mut problem,
+            BPLayoutType::Closed { bin_id: 0 },
+            0,
+            (0.0, 0.0),
+        );
+        place(&mut problem, BPLayoutType::Open(lk_a), 0, (40.0, 0.0));
+        place(&mut problem, BPLayoutType::Open(lk_b), 1, (0.0, 20.0));
+        let solution = problem.save();
+
+        let json = serde_json::to_string(&solution).unwrap();
+        let deserialized = BPSolutionSeed(&instance)
+            .deserialize(&mut serde_json::Deserializer::from_str(&json))
+            .unwrap();
+
+        //the layouts keep their keys, bins and contents
+        assert_eq!(deserialized.layout_snapshots.len(), 2);
+        for (lkey, ls) in solution.layout_snapshots.iter() {
+            let de_ls = &deserialized.layout_snapshots[lkey];
+            assert_eq!(de_ls.container.id, ls.container.id);
+            assert_eq!(de_ls.placed_items.len(), ls.placed_items.len());
+            for (pk, pi) in ls.placed_items.iter() {
+                assert_eq!(de_ls.placed_items[pk].item_id, pi.item_id);
+                assert_eq!(de_ls.placed_items[pk].d_transf, pi.d_transf);
+            }
+        }
+        assert_eq!(deserialized.cost(&instance), solution.cost(&instance));
+
+        //the deserialized solution can be restored into the problem it was saved from
+        let pk = problem.layouts[lk_b].placed_items.keys().next().unwrap();
+        problem.remove_item(lk_b, pk);
+        problem.restore(&deserialized);
+        assert_eq!(problem.layouts.len(), 2);
+        assert_eq!(problem.bin_cost(), solution.cost(&instance));
+        assert_eq!(problem.item_placed_qtys().sum::<usize>(), 4);
+    }
+
+    #[test]
+    fn unknown_bin_rejected() {
+        let instance = BPInstance::new(
+            vec![(item(0, 10.0, 10.0), 1)],
+            vec![Bin::new(container(0, 50.0, 50.0), 1, 10)],
+            vec![],
+        );
+        let mut problem = BPProblem::new(instance.clone());
+        problem.place_item(BPPlacement {
+            layout_id: BPLayoutType::Closed { bin_id: 0 },
+            item_id: 0,
+            d_transf: DTransformation::new(0.0, (0.0, 0.0)),
+        });
+        let json = serde_json::to_string(&problem.save()).unwrap();
+
+        //the same solution against an instance without that bin
+        let other = BPInstance::new(vec![(item(0, 10.0, 10.0), 1)], vec![], vec![]);
+        let result =
+            BPSolutionSeed(&other).deserialize(&mut serde_json::Deserializer::from_str(&json));
+        assert!(result.is_err());
+    }
+}
@@ -1,7 +1,8 @@
 use crate::Instant;
-use crate::io::export::export_layout_snapshot;
+use crate::entities::RemnantConfig;
+use crate::io::export::{export_layout_snapshot, export_remnant};
 use crate::probs::bpp::entities::{BPInstance, BPSolution};
-use crate::probs::bpp::io::ext_repr::ExtBPSolution;
+use crate::probs::bpp::io::ext_repr::{ExtBPSolution, ExtBin, ExtItemGroupSpan};
 
 /// Exports a solution out of the library
 pub fn export(instance: &BPInstance, solution: &BPSolution, epoch: Instant) -> ExtBPSolution {
@@ -14,5 +15,35 @@ pub fn export(instance: &BPInstance, solution: &BPSolution, epoch: Instant) -> E
             .collect(),
         run_time_sec: solution.time_stamp.duration_since(epoch).as_secs(),
         density: solution.density(instance),
+        item_groups: solution
+            .group_bins_spanned(instance)
+            .into_iter()
+            .enumerate()
+            .map(|(id, bins_spanned)| ExtItemGroupSpan {
+                id: id as u64,
+                bins_spanned,
+                excess_bins: bins_spanned.saturating_sub(1),
+            })
+            .collect(),
     }
 }
+
+/// Exports the largest remnant of every layout of the solution as a bin with a stock of 1 and no cost,
+/// to be used in a next instance. The bins are numbered consecutively starting from `first_id`.
+pub fn export_remnants(
+    solution: &BPSolution,
+    config: RemnantConfig,
+    first_id: usize,
+) -> Vec<ExtBin> {
+    solution
+        .largest_remnants(config)
+        .values()
+        .enumerate()
+        .map(|(i, remnant)| ExtBin {
+            base: export_remnant(remnant, first_id + i),
+            stock: 1,
+            cost: 0,
+            weight_capacity: None,
+        })
+        .collect()
+}
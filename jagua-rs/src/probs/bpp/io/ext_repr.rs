@@ -10,16 +10,30 @@ pub struct ExtBPInstance {
     pub items: Vec<ExtItem>,
     /// Set of bins to be used
     pub bins: Vec<ExtBin>,
+    /// Bins which are already in use, with items placed in them that cannot be moved.
+    /// Each layout consumes one unit of stock of its bin, the items count towards their demand.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub fixed_layouts: Vec<ExtLayout>,
+    /// Groups of items which should be kept together, e.g. the parts of the same customer order
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub item_groups: Vec<ExtItemGroup>,
 }
 
-/// Item with a demand
+/// Item with a (minimum and maximum) demand
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ExtItem {
     #[serde(flatten)]
     /// External representation of the item in the base library
     pub base: crate::io::ext_repr::ExtItem,
-    /// Amount of times this item has to be produced
+    /// Maximum amount of times this item can be produced, all of them mandatory unless `min_demand` is specified
+    #[serde(alias = "max_demand")]
     pub demand: u64,
+    /// Amount of times this item has to be produced at least, equal to `demand` if not specified
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub min_demand: Option<u64>,
+    /// IDs of the bins this item may be placed in, allowed in all bins if not specified
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub allowed_bins: Option<Vec<u64>>,
 }
 
 /// Bin with a stock quantity and cost
@@ -31,6 +45,29 @@ pub struct ExtBin {
     pub stock: usize,
     /// The cost of using a bin of this type
     pub cost: u64,
+    /// Maximum total weight of the items placed in a bin of this type, unlimited if not specified
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub weight_capacity: Option<f32>,
+}
+
+/// Group of items which should be kept together
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtItemGroup {
+    /// Unique identifier of the group
+    pub id: u64,
+    /// IDs of the items in the group, an item can belong to at most one group
+    pub item_ids: Vec<u64>,
+    /// How strictly the group should be kept together
+    pub policy: ExtGroupPolicy,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtGroupPolicy {
+    /// All items of the group have to be placed in the same bin
+    SameBin,
+    /// The items of the group should be spread over as few bins as possible
+    MinimizeBins,
 }
 
 /// Bin Packing Problem solution
@@ -44,4 +81,18 @@ pub struct ExtBPSolution {
     pub density: f32,
     /// The time it took to generate the solution in seconds
     pub run_time_sec: u64,
+    /// Per item group, the number of bins its items are spread over
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub item_groups: Vec<ExtItemGroupSpan>,
+}
+
+/// Spread of an item group in a solution
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtItemGroupSpan {
+    /// The id of the group
+    pub id: u64,
+    /// The number of bins containing items of the group
+    pub bins_spanned: usize,
+    /// The number of bins spanned beyond the first one, zero if the group is kept together
+    pub excess_bins: usize,
 }
@@ -1,11 +1,17 @@
-use crate::entities::Item;
-use crate::io::import::Importer;
-use crate::probs::bpp::entities::{BPInstance, Bin};
-use crate::probs::bpp::io::ext_repr::ExtBPInstance;
+use crate::entities::{Instance, Item, Layout};
+use crate::io::import::{
+    Importer, InfeasiblePlacement, ext_to_int_transformation, match_fixed_placements,
+    placement_in_bounds,
+};
+use crate::probs::bpp::entities::{
+    BPFixedLayout, BPInstance, BPLayoutType, BPPlacement, BPProblem, Bin, GroupPolicy, ItemGroup,
+    LayKey,
+};
+use crate::probs::bpp::io::ext_repr::{ExtBPInstance, ExtBPSolution, ExtGroupPolicy};
 use itertools::Itertools;
 use rayon::prelude::*;
 
-use anyhow::{Result, ensure};
+use anyhow::{Result, bail, ensure};
 
 /// Imports an instance into the library
 pub fn import(importer: &Importer, ext_instance: &ExtBPInstance) -> Result<BPInstance> {
@@ -35,7 +41,15 @@ pub fn import(importer: &Importer, ext_instance: &ExtBPInstance) -> Result<BPIns
             .par_iter()
             .map(|ext_bin| {
                 let container = importer.import_container(&ext_bin.base)?;
-                Ok(Bin::new(container, ext_bin.stock, ext_bin.cost))
+                if let Some(capacity) = ext_bin.weight_capacity {
+                    ensure!(
+                        capacity.is_finite() && capacity >= 0.0,
+                        "bin {} has an invalid weight capacity: {capacity}",
+                        container.id
+                    );
+                }
+                Ok(Bin::new(container, ext_bin.stock, ext_bin.cost)
+                    .with_weight_capacity(ext_bin.weight_capacity))
             })
             .collect::<Result<Vec<Bin>>>()?;
 
@@ -48,5 +62,375 @@ pub fn import(importer: &Importer, ext_instance: &ExtBPInstance) -> Result<BPIns
         bins
     };
 
-    Ok(BPInstance::new(items, bins))
+    let fixed_layouts = ext_instance
+        .fixed_layouts
+        .iter()
+        .map(|ext_layout| {
+            let bin_id = ext_layout.container_id as usize;
+            ensure!(
+                bin_id < bins.len(),
+                "fixed layout refers to unknown bin {bin_id}"
+            );
+            let placements = ext_layout
+                .placed_items
+                .iter()
+                .map(|ext_pi| {
+                    let item_id = ext_pi.item_id as usize;
+                    ensure!(
+                        item_id < items.len(),
+                        "fixed item refers to unknown item {item_id}"
+                    );
+                    let item = &items[item_id].0;
+                    let d_transf = ext_to_int_transformation(
+                        &(&ext_pi.transformation).into(),
+                        &item.shape_orig.pre_transform,
+                    );
+                    Ok((item_id, d_transf))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(BPFixedLayout { bin_id, placements })
+        })
+        .collect::<Result<Vec<BPFixedLayout>>>()?;
+
+    for (bin_id, bin) in bins.iter().enumerate() {
+        let n_fixed = fixed_layouts
+            .iter()
+            .filter(|fl| fl.bin_id == bin_id)
+            .count();
+        ensure!(
+            n_fixed <= bin.stock,
+            "bin {bin_id} has {n_fixed} fixed layouts, exceeding its stock of {}",
+            bin.stock
+        );
+    }
+    let min_demands = ext_instance
+        .items
+        .iter()
+        .sorted_by_key(|ext_item| ext_item.base.id)
+        .map(|ext_item| {
+            let min_demand = ext_item.min_demand.unwrap_or(ext_item.demand);
+            ensure!(
+                min_demand <= ext_item.demand,
+                "item {} has a minimum demand of {min_demand}, exceeding its demand of {}",
+                ext_item.base.id,
+                ext_item.demand
+            );
+            Ok(min_demand as usize)
+        })
+        .collect::<Result<Vec<usize>>>()?;
+
+    let allowed_bins = ext_instance
+        .items
+        .iter()
+        .sorted_by_key(|ext_item| ext_item.base.id)
+        .map(|ext_item| {
+            let Some(bin_ids) = &ext_item.allowed_bins else {
+                return Ok(None);
+            };
+            let bin_ids = bin_ids.iter().map(|id| *id as usize).collect_vec();
+            ensure!(
+                bin_ids.iter().all(|id| *id < bins.len()),
+                "item {} is allowed in unknown bins: {bin_ids:?}",
+                ext_item.base.id
+            );
+            Ok(Some(bin_ids))
+        })
+        .collect::<Result<Vec<Option<Vec<usize>>>>>()?;
+
+    for fl in fixed_layouts.iter() {
+        for (item_id, _) in fl.placements.iter() {
+            ensure!(
+                allowed_bins[*item_id]
+                    .as_ref()
+                    .is_none_or(|bin_ids| bin_ids.contains(&fl.bin_id)),
+                "fixed item {item_id} is not allowed in bin {}",
+                fl.bin_id
+            );
+        }
+        if let Some(capacity) = bins[fl.bin_id].weight_capacity {
+            let weight = fl
+                .placements
+                .iter()
+                .map(|(item_id, _)| items[*item_id].0.weight)
+                .sum::<f32>();
+            ensure!(
+                weight <= capacity,
+                "fixed layout of bin {} weighs {weight}, exceeding its weight capacity of {capacity}",
+                fl.bin_id
+            );
+        }
+    }
+    for (item_id, (_, demand)) in items.iter().enumerate() {
+        let n_fixed = fixed_layouts
+            .iter()
+            .flat_map(|fl| fl.placements.iter())
+            .filter(|(id, _)| *id == item_id)
+            .count();
+        ensure!(
+            n_fixed <= *demand,
+            "item {item_id} has {n_fixed} fixed placements, exceeding its demand of {demand}"
+        );
+    }
+
+    let item_groups = {
+        let mut item_groups = ext_instance
+            .item_groups
+            .iter()
+            .map(|ext_group| {
+                let item_ids = ext_group
+                    .item_ids
+                    .iter()
+                    .map(|id| *id as usize)
+                    .collect_vec();
+                ensure!(
+                    item_ids.iter().all(|id| *id < items.len()),
+                    "item group {} refers to unknown items: {item_ids:?}",
+                    ext_group.id
+                );
+                let policy = match ext_group.policy {
+                    ExtGroupPolicy::SameBin => GroupPolicy::SameBin,
+                    ExtGroupPolicy::MinimizeBins => GroupPolicy::MinimizeBins,
+                };
+                Ok(ItemGroup::new(ext_group.id as usize, item_ids, policy))
+            })
+            .collect::<Result<Vec<ItemGroup>>>()?;
+
+        item_groups.sort_by_key(|group| group.id);
+        ensure!(
+            item_groups.iter().enumerate().all(|(i, g)| g.id == i),
+            "All item groups should have consecutive IDs starting from 0. IDs: {:?}",
+            item_groups.iter().map(|g| g.id).collect_vec()
+        );
+        let grouped_item_ids = item_groups.iter().flat_map(|g| g.item_ids.iter());
+        ensure!(
+            grouped_item_ids.clone().all_unique(),
+            "an item can belong to at most one group"
+        );
+        for group in item_groups
+            .iter()
+            .filter(|g| g.policy == GroupPolicy::SameBin)
+        {
+            let n_fixed_layouts = fixed_layouts
+                .iter()
+                .filter(|fl| {
+                    fl.placements
+                        .iter()
+                        .any(|(item_id, _)| group.item_ids.contains(item_id))
+                })
+                .count();
+            ensure!(
+                n_fixed_layouts <= 1,
+                "fixed items of same-bin group {} are spread over {n_fixed_layouts} layouts",
+                group.id
+            );
+        }
+        item_groups
+    };
+
+    Ok(BPInstance::new(items, bins, fixed_layouts)
+        .with_min_demands(min_demands)
+        .with_allowed_bins(allowed_bins)
+        .with_item_groups(item_groups))
+}
+
+/// Imports a solution of the instance into a [`BPProblem`], e.g. to warm-start an optimizer or to validate a solution produced elsewhere.
+/// Layouts containing placements marked as fixed are matched to the fixed layouts of the instance, whose fixed items are placed by the problem itself.
+/// The fixed placements of such a layout have to match those of its fixed layout exactly, and every fixed layout has to be matched.
+/// Items lying outside the bounds of their bin, not allowed in it, exceeding its remaining weight capacity,
+/// or splitting a same-bin group, are not placed.
+/// Returns the problem together with all placements which are infeasible, paired with the index of their layout in the solution.
+pub fn import_solution(
+    instance: &BPInstance,
+    ext_solution: &ExtBPSolution,
+) -> Result<(BPProblem, Vec<(usize, InfeasiblePlacement)>)> {
+    let mut problem = BPProblem::new(instance.clone());
+
+    //at this point, the problem only contains the fixed layouts
+    let mut unmatched_fixed_layouts = problem.layouts.keys().collect_vec();
+    let mut layout_keys: Vec<Option<LayKey>> = vec![];
+    let mut placements = vec![];
+
+    for (l_index, ext_layout) in ext_solution.layouts.iter().enumerate() {
+        let bin_id = ext_layout.container_id as usize;
+        ensure!(
+            bin_id < instance.bins.len(),
+            "layout {l_index} refers to unknown bin {bin_id}"
+        );
+        let mut lkey = match ext_layout.placed_items.iter().any(|pi| pi.fixed) {
+            true => {
+                let Some(pos) = unmatched_fixed_layouts.iter().position(|lk| {
+                    let layout = &problem.layouts[*lk];
+                    layout.container.id == bin_id
+                        && match_fixed_placements(instance, layout, &ext_layout.placed_items)
+                            .is_ok()
+                }) else {
+                    bail!(
+                        "layout {l_index} contains fixed items, but matches no fixed layout of the instance"
+                    );
+                };
+                Some(unmatched_fixed_layouts.swap_remove(pos))
+            }
+            false => None,
+        };
+
+        for (index, ext_pi) in ext_layout.placed_items.iter().enumerate() {
+            if ext_pi.fixed {
+                continue;
+            }
+            let item_id = ext_pi.item_id as usize;
+            ensure!(
+                item_id < instance.items.len(),
+                "placement {index} of layout {l_index} refers to unknown item {item_id}"
+            );
+            if problem.item_demand_qtys[item_id] == 0 {
+                bail!(
+                    "placement {index} of layout {l_index}: item {item_id} is placed more often than its demand"
+                );
+            }
+            let item = instance.item(item_id);
+            let d_transf = ext_to_int_transformation(
+                &(&ext_pi.transformation).into(),
+                &item.shape_orig.pre_transform,
+            );
+            let cde = match lkey {
+                Some(lkey) => problem.layouts[lkey].cde(),
+                None => instance.bins[bin_id].container.base_cde.as_ref(),
+            };
+            let layout_id = match lkey {
+                Some(lkey) => BPLayoutType::Open(lkey),
+                None => BPLayoutType::Closed { bin_id },
+            };
+            //constraints imposed by the problem rather than the layout, violating placements are not placed
+            let violated = ProblemViolations {
+                weight_capacity: !problem.fits_weight(layout_id, item_id),
+                eligibility: !problem.item_allowed_in(layout_id, item_id),
+                group: !problem.group_allows(layout_id, item_id),
+            };
+            let pk = match placement_in_bounds(cde, item, d_transf) && !violated.any() {
+                true => {
+                    if lkey.is_none() {
+                        ensure!(
+                            problem.bin_stock_qtys[bin_id] > 0,
+                            "layout {l_index}: stock of bin {bin_id} is exceeded"
+                        );
+                    }
+                    let (lk, pk) = problem.place_item(BPPlacement {
+                        layout_id,
+                        item_id,
+                        d_transf,
+                    });
+                    lkey = Some(lk);
+                    Some(pk)
+                }
+                false => None,
+            };
+            placements.push((l_index, index, item_id, d_transf, pk, violated));
+        }
+        layout_keys.push(lkey);
+    }
+    ensure!(
+        unmatched_fixed_layouts.is_empty(),
+        "{} fixed layout(s) of the instance are missing from the solution",
+        unmatched_fixed_layouts.len()
+    );
+
+    let infeasible = placements
+        .into_iter()
+        .filter_map(|(l_index, index, item_id, d_transf, pk, violated)| {
+            let item = instance.item(item_id);
+            let mut report = match layout_keys[l_index] {
+                Some(lkey) => {
+                    InfeasiblePlacement::evaluate(&problem.layouts[lkey], item, d_transf, index, pk)
+                }
+                None => {
+                    //none of the items of this layout could be placed, check against an empty bin
+                    let bin_id = ext_solution.layouts[l_index].container_id as usize;
+                    let layout = Layout::new(instance.bins[bin_id].container.clone());
+                    InfeasiblePlacement::evaluate(&layout, item, d_transf, index, pk)
+                }
+            };
+            report.weight_capacity_violated = violated.weight_capacity;
+            report.eligibility_violated = violated.eligibility;
+            report.group_violated = violated.group;
+            match report.is_feasible() {
+                true => None,
+                false => Some((l_index, report)),
+            }
+        })
+        .collect_vec();
+
+    Ok((problem, infeasible))
+}
+
+#[derive(Clone, Copy)]
+struct ProblemViolations {
+    weight_capacity: bool,
+    eligibility: bool,
+    group: bool,
+}
+
+impl ProblemViolations {
+    fn any(&self) -> bool {
+        self.weight_capacity || self.eligibility || self.group
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Instant;
+    use crate::geometry::DTransformation;
+    use crate::probs::bpp::io::export::export;
+    use crate::util::test_util::{container, item};
+
+    /// Exported solution with a fixed layout containing a fixed copy of item 1 and a regular copy of item 0
+    fn instance_and_solution() -> (BPInstance, ExtBPSolution) {
+        let instance = BPInstance::new(
+            vec![(item(0, 10.0, 10.0), 2), (item(1, 20.0, 20.0), 2)],
+            vec![Bin::new(container(0, 50.0, 50.0), 2, 10)],
+            vec![BPFixedLayout {
+                bin_id: 0,
+                placements: vec![(1, DTransformation::new(0.0, (10.0, 10.0)))],
+            }],
+        );
+        let mut problem = BPProblem::new(instance.clone());
+        let lkey = problem.layouts.keys().next().unwrap();
+        problem.place_item(BPPlacement {
+            layout_id: BPLayoutType::Open(lkey),
+            item_id: 0,
+            d_transf: DTransformation::new(0.0, (35.0, 35.0)),
+        });
+        let solution = export(&instance, &problem.save(), Instant::now());
+        (instance, solution)
+    }
+
+    #[test]
+    fn fixed_layouts_matched() {
+        let (instance, solution) = instance_and_solution();
+        let (problem, infeasible) = import_solution(&instance, &solution).unwrap();
+        assert!(infeasible.is_empty());
+        assert_eq!(problem.layouts.len(), 1);
+        assert_eq!(problem.item_demand_qtys, vec![1, 1]);
+    }
+
+    #[test]
+    fn forged_fixed_layouts_rejected() {
+        let (instance, solution) = instance_and_solution();
+
+        //fixed item moved elsewhere
+        let mut moved = solution.clone();
+        let fixed_pi = moved.layouts[0].placed_items.iter_mut().find(|pi| pi.fixed);
+        fixed_pi.unwrap().transformation.translation.0 += 5.0;
+        assert!(import_solution(&instance, &moved).is_err());
+
+        //an additional layout posing as a fixed one
+        let mut duplicated = solution.clone();
+        duplicated.layouts.push(solution.layouts[0].clone());
+        assert!(import_solution(&instance, &duplicated).is_err());
+
+        //fixed layout missing
+        let mut missing = solution.clone();
+        missing.layouts.clear();
+        assert!(import_solution(&instance, &missing).is_err());
+    }
 }
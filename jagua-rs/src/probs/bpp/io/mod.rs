@@ -6,5 +6,11 @@ pub mod ext_repr;
 
 pub use export::export;
 
+#[doc(inline)]
+pub use export::export_remnants;
+
 #[doc(inline)]
 pub use import::import;
+
+#[doc(inline)]
+pub use import::import_solution;
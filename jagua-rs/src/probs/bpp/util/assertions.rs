@@ -1,6 +1,10 @@
 use crate::entities::Item;
-use crate::probs::bpp::entities::{BPProblem, BPSolution, Bin};
+use crate::probs::bpp::entities::{
+    BPFixedLayout, BPProblem, BPSolution, Bin, GroupPolicy, ItemGroup,
+};
 use crate::util::assertions::layouts_match;
+use itertools::Itertools;
+use slotmap::SecondaryMap;
 
 pub fn problem_matches_solution(bpp: &BPProblem, sol: &BPSolution) -> bool {
     let BPSolution {
@@ -15,10 +19,138 @@ pub fn problem_matches_solution(bpp: &BPProblem, sol: &BPSolution) -> bool {
         }
     }
 
-    true
+    remaining_weight_capacities_correct(bpp) && group_layout_qtys_correct(bpp)
 }
 
 pub fn instance_item_bin_ids_correct(items: &[(Item, usize)], bins: &[Bin]) -> bool {
     items.iter().enumerate().all(|(i, (item, _))| item.id == i)
         && bins.iter().enumerate().all(|(i, bin)| bin.id == i)
 }
+
+pub fn fixed_layouts_within_stock_and_demand(
+    items: &[(Item, usize)],
+    bins: &[Bin],
+    fixed_layouts: &[BPFixedLayout],
+) -> bool {
+    let mut remaining_demand = items.iter().map(|(_, qty)| *qty).collect_vec();
+    let mut remaining_stock = bins.iter().map(|bin| bin.stock).collect_vec();
+    let decrement = |qty: Option<&mut usize>| match qty {
+        Some(qty) if *qty > 0 => {
+            *qty -= 1;
+            true
+        }
+        _ => false,
+    };
+    fixed_layouts.iter().all(|fl| {
+        decrement(remaining_stock.get_mut(fl.bin_id))
+            && fl
+                .placements
+                .iter()
+                .all(|(item_id, _)| decrement(remaining_demand.get_mut(*item_id)))
+    })
+}
+
+pub fn remaining_weight_capacities_correct(bpp: &BPProblem) -> bool {
+    bpp.layouts.len() == bpp.remaining_weight_capacities.len()
+        && bpp.layouts.iter().all(|(lkey, l)| {
+            let remaining = match bpp.instance.bins[l.container.id].weight_capacity {
+                Some(capacity) => capacity - l.placed_item_weight(&bpp.instance),
+                None => f32::INFINITY,
+            };
+            bpp.remaining_weight_capacities.get(lkey) == Some(&remaining)
+        })
+}
+
+pub fn fixed_layouts_within_weight_capacity(
+    items: &[(Item, usize)],
+    bins: &[Bin],
+    fixed_layouts: &[BPFixedLayout],
+) -> bool {
+    fixed_layouts.iter().all(|fl| {
+        let weight = fl
+            .placements
+            .iter()
+            .map(|(item_id, _)| items[*item_id].0.weight)
+            .sum::<f32>();
+        bins[fl.bin_id]
+            .weight_capacity
+            .is_none_or(|capacity| weight <= capacity)
+    })
+}
+
+pub fn allowed_bins_correct(
+    allowed_bins: &[Option<Vec<usize>>],
+    items: &[(Item, usize)],
+    bins: &[Bin],
+    fixed_layouts: &[BPFixedLayout],
+) -> bool {
+    let allowed = |item_id: usize, bin_id: usize| match &allowed_bins[item_id] {
+        Some(bin_ids) => bin_ids.contains(&bin_id),
+        None => true,
+    };
+    allowed_bins.len() == items.len()
+        && allowed_bins
+            .iter()
+            .flatten()
+            .flatten()
+            .all(|bin_id| *bin_id < bins.len())
+        && fixed_layouts.iter().all(|fl| {
+            fl.placements
+                .iter()
+                .all(|(item_id, _)| allowed(*item_id, fl.bin_id))
+        })
+}
+
+pub fn item_groups_correct(
+    item_groups: &[ItemGroup],
+    items: &[(Item, usize)],
+    fixed_layouts: &[BPFixedLayout],
+) -> bool {
+    let mut grouped = vec![false; items.len()];
+    let ids_correct = item_groups.iter().enumerate().all(|(i, g)| g.id == i);
+    let members_correct = item_groups
+        .iter()
+        .flat_map(|g| g.item_ids.iter())
+        .all(|item_id| match grouped.get_mut(*item_id) {
+            Some(grouped) if !*grouped => {
+                *grouped = true;
+                true
+            }
+            _ => false,
+        });
+    let fixed_correct = item_groups
+        .iter()
+        .filter(|g| g.policy == GroupPolicy::SameBin)
+        .all(|g| {
+            fixed_layouts
+                .iter()
+                .filter(|fl| {
+                    fl.placements
+                        .iter()
+                        .any(|(item_id, _)| g.item_ids.contains(item_id))
+                })
+                .count()
+                <= 1
+        });
+    ids_correct && members_correct && fixed_correct
+}
+
+pub fn group_layout_qtys_correct(bpp: &BPProblem) -> bool {
+    let mut expected = vec![SecondaryMap::new(); bpp.instance.item_groups.len()];
+    for (lkey, l) in bpp.layouts.iter() {
+        for pi in l.placed_items.values() {
+            if let Some(group) = bpp.instance.item_group(pi.item_id) {
+                *expected[group.id].entry(lkey).unwrap().or_insert(0) += 1;
+            }
+        }
+    }
+    expected == bpp.group_layout_qtys
+}
+
+pub fn min_demands_correct(items: &[(Item, usize)], min_qtys: &[usize]) -> bool {
+    items.len() == min_qtys.len()
+        && items
+            .iter()
+            .zip(min_qtys)
+            .all(|((_, qty), min_qty)| min_qty <= qty)
+}
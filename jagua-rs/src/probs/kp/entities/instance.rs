@@ -0,0 +1,77 @@
+use crate::entities::{Container, Instance, Item};
+use crate::probs::kp::entities::KPPlacement;
+use crate::probs::kp::util::assertions;
+use std::iter;
+
+#[derive(Debug, Clone)]
+/// Instance of the Knapsack Problem: a single container in which to place a subset of the items, maximizing their total value.
+pub struct KPInstance {
+    /// The items which can be placed, the maximum number of copies and the value of a single copy
+    pub items: Vec<(Item, usize, u64)>,
+    /// The container in which to place the items
+    pub container: Container,
+    /// Items which are pre-placed in the container and cannot be removed, they count towards the maximum quantity of their item
+    pub fixed_placements: Vec<KPPlacement>,
+}
+
+impl KPInstance {
+    pub fn new(
+        items: Vec<(Item, usize, u64)>,
+        container: Container,
+        fixed_placements: Vec<KPPlacement>,
+    ) -> Self {
+        assert!(
+            assertions::instance_item_ids_correct(&items),
+            "All items should have consecutive IDs starting from 0"
+        );
+        assert!(
+            assertions::fixed_placements_within_qty(&items, &fixed_placements),
+            "Fixed placements should refer to existing items and not exceed their maximum quantity"
+        );
+
+        Self {
+            items,
+            container,
+            fixed_placements,
+        }
+    }
+
+    pub fn item_qty(&self, id: usize) -> usize {
+        self.items[id].1
+    }
+
+    pub fn item_value(&self, id: usize) -> u64 {
+        self.items[id].2
+    }
+
+    pub fn total_item_qty(&self) -> usize {
+        self.items.iter().map(|(_, qty, _)| *qty).sum()
+    }
+
+    /// Upper bound on the value of any solution: the value of all items at their maximum quantity
+    pub fn total_item_value(&self) -> u64 {
+        self.items
+            .iter()
+            .map(|(_, qty, value)| *qty as u64 * *value)
+            .sum()
+    }
+}
+
+impl Instance for KPInstance {
+    fn items(&self) -> impl Iterator<Item = &Item> {
+        self.items.iter().map(|(item, _qty, _value)| item)
+    }
+
+    fn containers(&self) -> impl Iterator<Item = &Container> {
+        iter::once(&self.container)
+    }
+
+    fn item(&self, id: usize) -> &Item {
+        &self.items.get(id).unwrap().0
+    }
+
+    fn container(&self, id: usize) -> &Container {
+        assert_eq!(id, self.container.id, "unknown container {id}");
+        &self.container
+    }
+}
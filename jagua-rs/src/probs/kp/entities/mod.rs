@@ -0,0 +1,14 @@
+mod instance;
+mod problem;
+mod solution;
+
+#[doc(inline)]
+pub use instance::KPInstance;
+#[doc(inline)]
+pub use problem::KPPlacement;
+#[doc(inline)]
+pub use problem::KPProblem;
+#[doc(inline)]
+pub use solution::KPSolution;
+#[doc(inline)]
+pub use solution::KPSolutionSeed;
@@ -0,0 +1,168 @@
+use crate::Instant;
+use crate::entities::{Instance, Layout, PItemKey};
+use crate::geometry::DTransformation;
+use crate::probs::kp::entities::{KPInstance, KPSolution};
+use crate::probs::kp::util::assertions::problem_matches_solution;
+use itertools::Itertools;
+
+/// Modifiable counterpart of [`KPInstance`]: items can be placed and removed.
+#[derive(Clone)]
+pub struct KPProblem {
+    pub instance: KPInstance,
+    pub layout: Layout,
+    /// Remaining number of copies of each item that can still be placed
+    pub item_demand_qtys: Vec<usize>,
+}
+
+impl KPProblem {
+    pub fn new(instance: KPInstance) -> Self {
+        let item_demand_qtys = instance.items.iter().map(|(_, qty, _)| *qty).collect_vec();
+        let layout = Layout::new(instance.container.clone());
+        let mut problem = Self {
+            instance,
+            layout,
+            item_demand_qtys,
+        };
+
+        for p in problem.instance.fixed_placements.clone() {
+            problem.register_included_item(p.item_id);
+            let item = problem.instance.item(p.item_id);
+            problem.layout.place_fixed_item(item, p.d_transf);
+        }
+
+        problem
+    }
+
+    /// Places an item according to the given `KPPlacement` in the problem.
+    /// Panics if all copies of the item are already placed.
+    pub fn place_item(&mut self, placement: KPPlacement) -> PItemKey {
+        self.register_included_item(placement.item_id);
+        let item = self.instance.item(placement.item_id);
+
+        self.layout.place_item(item, placement.d_transf)
+    }
+
+    /// Removes a placed item from the container. Returns the placement of the item.
+    /// Panics if the item is fixed.
+    pub fn remove_item(&mut self, pkey: PItemKey) -> KPPlacement {
+        assert!(
+            !self.layout.placed_items[pkey].fixed,
+            "fixed items cannot be removed"
+        );
+        let pi = self.layout.remove_item(pkey);
+        self.deregister_included_item(pi.item_id);
+
+        KPPlacement {
+            item_id: pi.item_id,
+            d_transf: pi.d_transf,
+        }
+    }
+
+    /// Creates a snapshot of the current state of the problem as a [`KPSolution`].
+    pub fn save(&self) -> KPSolution {
+        let solution = KPSolution {
+            layout_snapshot: self.layout.save(),
+            time_stamp: Instant::now(),
+        };
+
+        debug_assert!(problem_matches_solution(self, &solution));
+
+        solution
+    }
+
+    /// Restores the state of the problem to the given [`KPSolution`].
+    pub fn restore(&mut self, solution: &KPSolution) {
+        self.layout.restore(&solution.layout_snapshot);
+
+        //Restore the item demands
+        {
+            self.item_demand_qtys
+                .iter_mut()
+                .enumerate()
+                .for_each(|(id, qty)| *qty = self.instance.item_qty(id));
+
+            self.layout
+                .placed_items
+                .iter()
+                .for_each(|(_, pi)| self.item_demand_qtys[pi.item_id] -= 1);
+        }
+        debug_assert!(problem_matches_solution(self, solution));
+    }
+
+    fn register_included_item(&mut self, item_id: usize) {
+        assert!(
+            self.item_demand_qtys[item_id] > 0,
+            "item {item_id} is placed more often than its demand"
+        );
+        self.item_demand_qtys[item_id] -= 1;
+    }
+
+    fn deregister_included_item(&mut self, item_id: usize) {
+        self.item_demand_qtys[item_id] += 1;
+    }
+
+    /// Total value of all items placed in the container
+    pub fn value(&self) -> u64 {
+        self.layout
+            .placed_items
+            .values()
+            .map(|pi| self.instance.item_value(pi.item_id))
+            .sum()
+    }
+
+    pub fn density(&self) -> f32 {
+        self.layout.density(&self.instance)
+    }
+}
+
+/// Represents a placement of an item in the knapsack problem.
+#[derive(Debug, Clone, Copy)]
+pub struct KPPlacement {
+    pub item_id: usize,
+    pub d_transf: DTransformation,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_util::{container, item};
+
+    /// Two copies of item 0 worth 5 each, and a single copy of item 1 worth 30
+    fn problem() -> KPProblem {
+        KPProblem::new(KPInstance::new(
+            vec![(item(0, 10.0, 10.0), 2, 5), (item(1, 20.0, 20.0), 1, 30)],
+            container(0, 50.0, 50.0),
+            vec![],
+        ))
+    }
+
+    fn place(problem: &mut KPProblem, item_id: usize, pos: (f32, f32)) -> PItemKey {
+        problem.place_item(KPPlacement {
+            item_id,
+            d_transf: DTransformation::new(0.0, pos),
+        })
+    }
+
+    #[test]
+    fn value() {
+        let mut problem = problem();
+        assert_eq!(problem.value(), 0);
+
+        place(&mut problem, 0, (5.0, 5.0));
+        let pk = place(&mut problem, 1, (20.0, 20.0));
+        assert_eq!(problem.value(), 35);
+        assert_eq!(problem.save().value(&problem.instance), 35);
+
+        problem.remove_item(pk);
+        assert_eq!(problem.value(), 5);
+        assert_eq!(problem.item_demand_qtys, vec![1, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "item 1 is placed more often than its demand")]
+    fn placement_beyond_demand() {
+        let mut problem = problem();
+        place(&mut problem, 1, (5.0, 5.0));
+        place(&mut problem, 1, (25.0, 25.0));
+    }
+}
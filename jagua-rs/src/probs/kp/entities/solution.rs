@@ -0,0 +1,68 @@
+use crate::Instant;
+use crate::entities::{LayoutSnapshot, LayoutSnapshotData};
+use crate::probs::kp::entities::KPInstance;
+use serde::de::{DeserializeSeed, Error};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Snapshot of [`KPProblem`](crate::probs::kp::entities::KPProblem) at a specific moment. Can be used to restore to a previous state.
+/// Serializes without its time stamp, deserialize using [`KPSolutionSeed`].
+#[derive(Debug, Clone)]
+pub struct KPSolution {
+    pub layout_snapshot: LayoutSnapshot,
+    /// Instant the solution was created
+    pub time_stamp: Instant,
+}
+
+impl KPSolution {
+    pub fn density(&self, instance: &KPInstance) -> f32 {
+        self.layout_snapshot.density(instance)
+    }
+
+    /// Total value of all items placed in the container
+    pub fn value(&self, instance: &KPInstance) -> u64 {
+        self.layout_snapshot
+            .placed_items
+            .values()
+            .map(|pi| instance.item_value(pi.item_id))
+            .sum()
+    }
+}
+
+impl Serialize for KPSolution {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct KPSolutionRef<'a> {
+            layout_snapshot: &'a LayoutSnapshot,
+        }
+        KPSolutionRef {
+            layout_snapshot: &self.layout_snapshot,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a [`KPSolution`] and rebuilds it against the (imported) instance it belongs to.
+/// The time stamp of the solution is set to the moment of deserialization.
+pub struct KPSolutionSeed<'a>(pub &'a KPInstance);
+
+impl<'de> DeserializeSeed<'de> for KPSolutionSeed<'_> {
+    type Value = KPSolution;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        #[derive(Deserialize)]
+        struct KPSolutionData {
+            layout_snapshot: LayoutSnapshotData,
+        }
+        let KPSolutionData { layout_snapshot } = KPSolutionData::deserialize(deserializer)?;
+        let instance = self.0;
+
+        let container = instance.container.clone();
+        let layout_snapshot = LayoutSnapshot::rebuild(layout_snapshot, container, instance)
+            .map_err(|e| D::Error::custom(format!("{e:#}")))?;
+
+        Ok(KPSolution {
+            layout_snapshot,
+            time_stamp: Instant::now(),
+        })
+    }
+}
@@ -0,0 +1,14 @@
+use crate::Instant;
+use crate::io::export::export_layout_snapshot;
+use crate::probs::kp::entities::{KPInstance, KPSolution};
+use crate::probs::kp::io::ext_repr::ExtKPSolution;
+
+/// Exports a solution out of the library
+pub fn export(instance: &KPInstance, solution: &KPSolution, epoch: Instant) -> ExtKPSolution {
+    ExtKPSolution {
+        value: solution.value(instance),
+        layout: export_layout_snapshot(&solution.layout_snapshot, instance),
+        density: solution.density(instance),
+        run_time_sec: solution.time_stamp.duration_since(epoch).as_secs(),
+    }
+}
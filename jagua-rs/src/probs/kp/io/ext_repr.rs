@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use crate::io::ext_repr::{ExtContainer, ExtLayout, ExtPlacedItem};
+
+/// Knapsack Problem instance
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtKPInstance {
+    /// The name of the instance
+    pub name: String,
+    /// Set of items which can be placed
+    pub items: Vec<ExtItem>,
+    /// The container in which to place the items
+    pub container: ExtContainer,
+    /// Items which are already placed in the container and cannot be moved, they count towards the demand of their item
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub fixed_items: Vec<ExtPlacedItem>,
+}
+
+/// Item with a value
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtItem {
+    #[serde(flatten)]
+    /// External representation of the item in the base library
+    pub base: crate::io::ext_repr::ExtItem,
+    /// Maximum amount of times this item can be placed
+    pub demand: u64,
+    /// The value of placing a single copy of this item
+    pub value: u64,
+}
+
+/// Knapsack Problem solution
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtKPSolution {
+    /// Total value of all the items placed in the container
+    pub value: u64,
+    /// Layout of the container
+    pub layout: ExtLayout,
+    /// Sum of the area of the placed items divided by the area of the container
+    pub density: f32,
+    /// The time it took to generate the solution in seconds
+    pub run_time_sec: u64,
+}
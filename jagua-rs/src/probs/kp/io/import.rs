@@ -0,0 +1,194 @@
+use crate::entities::Instance;
+use crate::entities::Item;
+use crate::io::import::{
+    Importer, InfeasiblePlacement, ext_to_int_transformation, match_fixed_placements,
+    placement_in_bounds,
+};
+use crate::probs::kp::entities::{KPInstance, KPPlacement, KPProblem};
+use crate::probs::kp::io::ext_repr::{ExtKPInstance, ExtKPSolution};
+use anyhow::{Result, bail, ensure};
+use itertools::Itertools;
+use rayon::prelude::*;
+
+/// Imports an instance into the library
+pub fn import(importer: &Importer, ext_instance: &ExtKPInstance) -> Result<KPInstance> {
+    let items: Vec<(Item, usize, u64)> = {
+        let mut items = ext_instance
+            .items
+            .par_iter()
+            .map(|ext_item| {
+                let item = importer.import_item(&ext_item.base)?;
+                let demand = ext_item.demand as usize;
+                Ok((item, demand, ext_item.value))
+            })
+            .collect::<Result<Vec<(Item, usize, u64)>>>()?;
+
+        items.sort_by_key(|(item, _, _)| item.id);
+        ensure!(
+            items
+                .iter()
+                .enumerate()
+                .all(|(i, (item, _, _))| item.id == i),
+            "All items should have consecutive IDs starting from 0. IDs: {:?}",
+            items
+                .iter()
+                .map(|(item, _, _)| item.id)
+                .sorted()
+                .collect_vec()
+        );
+        items
+    };
+
+    let container = importer.import_container(&ext_instance.container)?;
+
+    let fixed_placements = ext_instance
+        .fixed_items
+        .iter()
+        .map(|ext_pi| {
+            let item_id = ext_pi.item_id as usize;
+            ensure!(
+                item_id < items.len(),
+                "fixed item refers to unknown item {item_id}"
+            );
+            let item = &items[item_id].0;
+            let d_transf = ext_to_int_transformation(
+                &(&ext_pi.transformation).into(),
+                &item.shape_orig.pre_transform,
+            );
+            ensure!(
+                placement_in_bounds(container.base_cde.as_ref(), item, d_transf),
+                "fixed item {item_id} lies outside the container"
+            );
+            Ok(KPPlacement { item_id, d_transf })
+        })
+        .collect::<Result<Vec<KPPlacement>>>()?;
+
+    for (item_id, (_, demand, _)) in items.iter().enumerate() {
+        let n_fixed = fixed_placements
+            .iter()
+            .filter(|p| p.item_id == item_id)
+            .count();
+        ensure!(
+            n_fixed <= *demand,
+            "item {item_id} has {n_fixed} fixed placements, exceeding its demand of {demand}"
+        );
+    }
+
+    Ok(KPInstance::new(items, container, fixed_placements))
+}
+
+/// Imports a solution of the instance into a [`KPProblem`], e.g. to warm-start an optimizer or to validate a solution produced elsewhere.
+/// Fixed items are placed by the problem itself: placements marked as fixed have to match them exactly, and none of them may be missing.
+/// Items lying outside the bounds of the container are not placed.
+/// Returns the problem together with all placements which are infeasible.
+pub fn import_solution(
+    instance: &KPInstance,
+    ext_solution: &ExtKPSolution,
+) -> Result<(KPProblem, Vec<InfeasiblePlacement>)> {
+    let mut problem = KPProblem::new(instance.clone());
+
+    ensure!(
+        ext_solution.layout.container_id as usize == instance.container.id,
+        "solution refers to unknown container {}",
+        ext_solution.layout.container_id
+    );
+    match_fixed_placements(instance, &problem.layout, &ext_solution.layout.placed_items)?;
+
+    let mut placements = vec![];
+    for (index, ext_pi) in ext_solution.layout.placed_items.iter().enumerate() {
+        if ext_pi.fixed {
+            continue;
+        }
+        let item_id = ext_pi.item_id as usize;
+        ensure!(
+            item_id < instance.items.len(),
+            "placement {index} refers to unknown item {item_id}"
+        );
+        if problem.item_demand_qtys[item_id] == 0 {
+            bail!("placement {index}: item {item_id} is placed more often than its demand");
+        }
+        let item = instance.item(item_id);
+        let d_transf = ext_to_int_transformation(
+            &(&ext_pi.transformation).into(),
+            &item.shape_orig.pre_transform,
+        );
+        let pk = match placement_in_bounds(problem.layout.cde(), item, d_transf) {
+            true => Some(problem.place_item(KPPlacement { item_id, d_transf })),
+            false => None,
+        };
+        placements.push((index, item_id, d_transf, pk));
+    }
+
+    let infeasible = placements
+        .into_iter()
+        .filter_map(|(index, item_id, d_transf, pk)| {
+            InfeasiblePlacement::check(&problem.layout, instance.item(item_id), d_transf, index, pk)
+        })
+        .collect_vec();
+
+    Ok((problem, infeasible))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Instant;
+    use crate::geometry::DTransformation;
+    use crate::probs::kp::io::export::export;
+    use crate::util::test_util::cde_config;
+    use serde_json::json;
+
+    /// Container of 50x50 with two copies of item 0 (worth 5) and a copy of item 1 (worth 30), fixed in the top right corner
+    fn instance() -> KPInstance {
+        let square = |size: f32| json!({"type": "rectangle", "data": {"x_min": 0.0, "y_min": 0.0, "width": size, "height": size}});
+        let ext_instance: ExtKPInstance = serde_json::from_value(json!({
+            "name": "round_trip",
+            "container": {"id": 0, "shape": square(50.0)},
+            "items": [
+                {"id": 0, "demand": 2, "value": 5, "shape": square(10.0)},
+                {"id": 1, "demand": 1, "value": 30, "shape": square(20.0)},
+            ],
+            "fixed_items": [
+                {"item_id": 1, "transformation": {"rotation": 0.0, "translation": [25.0, 25.0]}},
+            ],
+        }))
+        .unwrap();
+        import(
+            &Importer::new(cde_config(), None, None, None),
+            &ext_instance,
+        )
+        .unwrap()
+    }
+
+    fn place(problem: &mut KPProblem, pos: (f32, f32)) {
+        problem.place_item(KPPlacement {
+            item_id: 0,
+            d_transf: DTransformation::new(0.0, pos),
+        });
+    }
+
+    #[test]
+    fn round_trip() {
+        let instance = instance();
+        let mut problem = KPProblem::new(instance.clone());
+        assert_eq!(problem.value(), 30);
+
+        place(&mut problem, (10.0, 10.0));
+        let solution = problem.save();
+        place(&mut problem, (10.0, 30.0));
+        assert_eq!(problem.value(), 40);
+
+        problem.restore(&solution);
+        assert_eq!(problem.value(), 35);
+        assert_eq!(problem.item_demand_qtys, vec![1, 0]);
+
+        let ext_solution = export(&instance, &problem.save(), Instant::now());
+        assert_eq!(ext_solution.value, 35);
+        assert_eq!(ext_solution.layout.placed_items.len(), 2);
+
+        let (imported, infeasible) = import_solution(&instance, &ext_solution).unwrap();
+        assert!(infeasible.is_empty());
+        assert_eq!(imported.value(), 35);
+        assert_eq!(imported.item_demand_qtys, problem.item_demand_qtys);
+    }
+}
@@ -0,0 +1,14 @@
+mod export;
+mod import;
+
+/// External (serializable) representations of all Knapsack Problem related entities.
+pub mod ext_repr;
+
+/// Exports a knapsack solution out of the library.
+pub use export::export;
+
+/// Imports a knapsack instance into the library.
+pub use import::import;
+
+/// Imports a knapsack solution back into a problem.
+pub use import::import_solution;
@@ -0,0 +1,39 @@
+use crate::entities::Item;
+use crate::probs::kp::entities::{KPPlacement, KPProblem, KPSolution};
+use crate::util::assertions::layouts_match;
+use itertools::Itertools;
+
+pub fn problem_matches_solution(kp: &KPProblem, sol: &KPSolution) -> bool {
+    let KPSolution {
+        layout_snapshot,
+        time_stamp: _,
+    } = sol;
+
+    assert_eq!(kp.value(), sol.value(&kp.instance));
+    assert!(layouts_match(&kp.layout, layout_snapshot));
+
+    true
+}
+
+pub fn instance_item_ids_correct(items: &[(Item, usize, u64)]) -> bool {
+    items
+        .iter()
+        .enumerate()
+        .all(|(i, (item, _qty, _value))| item.id == i)
+}
+
+pub fn fixed_placements_within_qty(
+    items: &[(Item, usize, u64)],
+    fixed_placements: &[KPPlacement],
+) -> bool {
+    let mut remaining = items.iter().map(|(_, qty, _)| *qty).collect_vec();
+    fixed_placements
+        .iter()
+        .all(|p| match remaining.get_mut(p.item_id) {
+            Some(qty) if *qty > 0 => {
+                *qty -= 1;
+                true
+            }
+            _ => false,
+        })
+}
@@ -2,6 +2,22 @@
 #[cfg(feature = "spp")]
 pub mod spp;
 
+/// Multi-Strip Packing Problem (MSPP) module
+#[cfg(feature = "mspp")]
+pub mod mspp;
+
 /// Bin Packing Problem (BPP) module
 #[cfg(feature = "bpp")]
 pub mod bpp;
+
+/// Knapsack Problem (KP) module
+#[cfg(feature = "kp")]
+pub mod kp;
+
+/// Open Dimension Problem (ODP) module
+#[cfg(feature = "odp")]
+pub mod odp;
+
+/// Relative tolerance applied to the extent of the shapes when fitting a variable container around them
+#[cfg(any(feature = "spp", feature = "odp"))]
+const FIT_TOLERANCE: f32 = 1.00001;
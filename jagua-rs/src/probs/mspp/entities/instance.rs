@@ -0,0 +1,55 @@
+use crate::entities::{Container, Instance, Item};
+use crate::probs::mspp::entities::StripType;
+use crate::probs::mspp::util::assertions::instance_item_strip_type_ids_correct;
+use std::iter;
+
+#[derive(Debug, Clone)]
+/// Instance of the Multi-Strip Packing Problem: the items are distributed over strips of several types,
+/// minimizing the total cost of the consumed length.
+pub struct MSPInstance {
+    /// The items to be packed and their demands
+    pub items: Vec<(Item, usize)>,
+    /// The types of strips available to pack the items
+    pub strip_types: Vec<StripType>,
+}
+
+impl MSPInstance {
+    pub fn new(items: Vec<(Item, usize)>, strip_types: Vec<StripType>) -> Self {
+        assert!(instance_item_strip_type_ids_correct(&items, &strip_types));
+
+        Self { items, strip_types }
+    }
+
+    pub fn item_area(&self) -> f32 {
+        self.items
+            .iter()
+            .map(|(item, qty)| item.shape_orig.area() * *qty as f32)
+            .sum()
+    }
+
+    pub fn item_qty(&self, id: usize) -> usize {
+        self.items[id].1
+    }
+
+    pub fn total_item_qty(&self) -> usize {
+        self.items.iter().map(|(_, qty)| *qty).sum()
+    }
+}
+
+impl Instance for MSPInstance {
+    fn items(&self) -> impl Iterator<Item = &Item> {
+        self.items.iter().map(|(item, _qty)| item)
+    }
+
+    fn containers(&self) -> impl Iterator<Item = &Container> {
+        iter::empty()
+    }
+
+    fn item(&self, id: usize) -> &Item {
+        &self.items.get(id).unwrap().0
+    }
+
+    fn container(&self, _id: usize) -> &Container {
+        panic!("no predefined containers for multi-strip packing instances")
+    }
+}
@@ -0,0 +1,21 @@
+mod instance;
+mod problem;
+mod solution;
+mod strip_type;
+
+#[doc(inline)]
+pub use instance::MSPInstance;
+#[doc(inline)]
+pub use problem::LayKey;
+#[doc(inline)]
+pub use problem::MSPLayoutType;
+#[doc(inline)]
+pub use problem::MSPPlacement;
+#[doc(inline)]
+pub use problem::MSPProblem;
+#[doc(inline)]
+pub use solution::MSPSolution;
+#[doc(inline)]
+pub use solution::MSPSolutionSeed;
+#[doc(inline)]
+pub use strip_type::StripType;
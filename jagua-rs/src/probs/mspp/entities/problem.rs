@@ -0,0 +1,336 @@
+use crate::Instant;
+use crate::entities::{Instance, Layout};
+use crate::entities::{PItemKey, PlacedItem};
+use crate::geometry::DTransformation;
+use crate::probs::mspp::entities::{MSPInstance, MSPSolution};
+use crate::probs::mspp::util::assertions::problem_matches_solution;
+use crate::probs::spp::entities::Strip;
+use itertools::Itertools;
+use slotmap::{SecondaryMap, SlotMap, new_key_type};
+
+new_key_type! {
+    /// Unique key for each [`Layout`] in a [`MSPProblem`] and [`MSPSolution`]
+    pub struct LayKey;
+}
+
+/// Dynamic counterpart of [`MSPInstance`]: items can be placed and removed, every strip in use can be extended or fitted.
+#[derive(Clone)]
+pub struct MSPProblem {
+    pub instance: MSPInstance,
+    pub layouts: SlotMap<LayKey, Layout>,
+    /// The strip used by each layout, its type is identified by the id of the layout's container
+    pub strips: SecondaryMap<LayKey, Strip>,
+    pub item_demand_qtys: Vec<usize>,
+}
+
+impl MSPProblem {
+    pub fn new(instance: MSPInstance) -> Self {
+        let item_demand_qtys = instance.items.iter().map(|(_, qty)| *qty).collect_vec();
+
+        Self {
+            instance,
+            layouts: SlotMap::with_key(),
+            strips: SecondaryMap::new(),
+            item_demand_qtys,
+        }
+    }
+
+    /// Removes a layout from the problem. All items placed inside it will be deregistered.
+    pub fn remove_layout(&mut self, key: LayKey) {
+        self.deregister_layout(key);
+    }
+
+    /// Places an item according to the provided [`MSPPlacement`] in the problem.
+    pub fn place_item(&mut self, p_opt: MSPPlacement) -> (LayKey, PItemKey) {
+        let lkey = match p_opt.layout_id {
+            MSPLayoutType::Open(lkey) => lkey,
+            MSPLayoutType::Closed { strip_type_id } => {
+                //start a new strip of this type
+                let strip_type = &self.instance.strip_types[strip_type_id];
+                let strip = strip_type.base_strip.clone();
+                let layout = Layout::new(strip_type.container(&strip));
+                self.register_layout(layout, strip)
+            }
+        };
+        let layout = &mut self.layouts[lkey];
+        let item = self.instance.item(p_opt.item_id);
+        let pik = layout.place_item(item, p_opt.d_transf);
+
+        self.register_included_item(p_opt.item_id);
+
+        (lkey, pik)
+    }
+
+    /// Removes an item from a layout. If the layout is empty, it will be closed.
+    pub fn remove_item(&mut self, lkey: LayKey, pik: PItemKey) -> MSPPlacement {
+        let pi = self.layouts[lkey].remove_item(pik);
+        self.deregister_included_item(pi.item_id);
+        if self.layouts[lkey].is_empty() {
+            //if layout is empty, close it
+            let strip_type_id = self.layouts[lkey].container.id;
+            self.deregister_layout(lkey);
+            MSPPlacement::from_placed_item(MSPLayoutType::Closed { strip_type_id }, &pi)
+        } else {
+            MSPPlacement::from_placed_item(MSPLayoutType::Open(lkey), &pi)
+        }
+    }
+
+    /// Modifies the width of the strip of a layout in the back, keeping the front fixed.
+    pub fn change_strip_width(&mut self, lkey: LayKey, new_width: f32) {
+        let strip = &mut self.strips[lkey];
+        strip.set_width(new_width);
+        let container = self.instance.strip_types[self.layouts[lkey].container.id].container(strip);
+        self.layouts[lkey].swap_container(container);
+    }
+
+    /// Shrinks the strip of a layout to the minimum width that fits all its items.
+    /// Does nothing if the layout is empty.
+    pub fn fit_strip(&mut self, lkey: LayKey) {
+        let layout = &self.layouts[lkey];
+        let feasible_before = layout.is_feasible();
+
+        let bboxes = layout.placed_items.values().map(|pi| pi.shape.bbox);
+        if let Some(fitted_width) = self.strips[lkey].fitted_width(bboxes) {
+            self.change_strip_width(lkey, fitted_width);
+        }
+        debug_assert!(feasible_before == self.layouts[lkey].is_feasible());
+    }
+
+    /// Shrinks the strips of all layouts to the minimum width that fits their items.
+    pub fn fit_strips(&mut self) {
+        for lkey in self.layouts.keys().collect_vec() {
+            self.fit_strip(lkey);
+        }
+    }
+
+    /// Creates a snapshot of the current state of the problem as a [`MSPSolution`].
+    pub fn save(&self) -> MSPSolution {
+        let layout_snapshots = self
+            .layouts
+            .iter()
+            .map(|(lkey, l)| (lkey, l.save()))
+            .collect();
+
+        let solution = MSPSolution {
+            layout_snapshots,
+            strips: self.strips.clone(),
+            time_stamp: Instant::now(),
+        };
+
+        debug_assert!(problem_matches_solution(self, &solution));
+
+        solution
+    }
+
+    /// Restores the state of the problem to the given [`MSPSolution`].
+    pub fn restore(&mut self, solution: &MSPSolution) {
+        let mut layouts_to_remove = vec![];
+
+        //Check which layouts from the problem are also present in the solution.
+        //If a layout with an identical strip is present, we can do a (partial) restore instead of fully rebuilding it.
+        for (lkey, layout) in self.layouts.iter_mut() {
+            match solution.layout_snapshots.get(lkey) {
+                Some(ls)
+                    if layout.container.id == ls.container.id
+                        && self.strips[lkey] == solution.strips[lkey] =>
+                {
+                    layout.restore(ls)
+                }
+                _ => layouts_to_remove.push(lkey),
+            }
+        }
+
+        //Remove all layouts that were not present in the solution (or have a different strip)
+        for lkey in layouts_to_remove {
+            self.layouts.remove(lkey);
+            self.strips.remove(lkey);
+        }
+
+        //Create new layouts for all keys present in solution but not in problem
+        for (lkey, ls) in solution.layout_snapshots.iter() {
+            if !self.layouts.contains_key(lkey) {
+                let new_lkey = self.layouts.insert(Layout::from_snapshot(ls));
+                self.strips.insert(new_lkey, solution.strips[lkey].clone());
+            }
+        }
+
+        //Restore the item demands
+        {
+            self.item_demand_qtys
+                .iter_mut()
+                .enumerate()
+                .for_each(|(id, demand)| {
+                    *demand = self.instance.item_qty(id);
+                });
+
+            self.layouts.values().for_each(|layout| {
+                layout
+                    .placed_items
+                    .values()
+                    .for_each(|pi| self.item_demand_qtys[pi.item_id] -= 1);
+            });
+        }
+
+        debug_assert!(problem_matches_solution(self, solution));
+    }
+
+    pub fn density(&self) -> f32 {
+        let total_strip_area = self
+            .layouts
+            .values()
+            .map(|l| l.container.area())
+            .sum::<f32>();
+
+        let total_item_area = self
+            .layouts
+            .values()
+            .map(|l| l.placed_item_area(&self.instance))
+            .sum::<f32>();
+
+        total_item_area / total_strip_area
+    }
+
+    pub fn item_placed_qtys(&self) -> impl Iterator<Item = usize> {
+        self.item_demand_qtys
+            .iter()
+            .enumerate()
+            .map(|(i, demand)| self.instance.item_qty(i) - demand)
+    }
+
+    /// Returns the total cost of the length consumed of all strips in use.
+    pub fn strip_cost(&self) -> f32 {
+        self.layouts
+            .iter()
+            .map(|(lkey, l)| self.instance.strip_types[l.container.id].cost(&self.strips[lkey]))
+            .sum()
+    }
+
+    fn register_layout(&mut self, layout: Layout, strip: Strip) -> LayKey {
+        layout
+            .placed_items
+            .values()
+            .for_each(|pi| self.register_included_item(pi.item_id));
+        let lkey = self.layouts.insert(layout);
+        self.strips.insert(lkey, strip);
+        lkey
+    }
+
+    fn deregister_layout(&mut self, key: LayKey) {
+        let layout = self.layouts.remove(key).expect("layout key not present");
+        self.strips.remove(key);
+        layout
+            .placed_items
+            .values()
+            .for_each(|pi| self.deregister_included_item(pi.item_id));
+    }
+
+    fn register_included_item(&mut self, item_id: usize) {
+        self.item_demand_qtys[item_id] -= 1;
+    }
+
+    fn deregister_included_item(&mut self, item_id: usize) {
+        self.item_demand_qtys[item_id] += 1;
+    }
+}
+
+#[derive(Clone, Debug, Copy)]
+/// Encapsulates all required information to place an [`Item`](crate::entities::Item) in a [`MSPProblem`].
+pub struct MSPPlacement {
+    /// Which [`Layout`] to place the item in
+    pub layout_id: MSPLayoutType,
+    /// The id of the [`Item`](crate::entities::Item) to be placed
+    pub item_id: usize,
+    /// The transformation to apply to the item when placing it
+    pub d_transf: DTransformation,
+}
+
+impl MSPPlacement {
+    pub fn from_placed_item(layout_id: MSPLayoutType, placed_item: &PlacedItem) -> Self {
+        MSPPlacement {
+            layout_id,
+            item_id: placed_item.item_id,
+            d_transf: placed_item.d_transf,
+        }
+    }
+}
+
+/// Enum to distinguish between both open [`Layout`]s, and potentially new ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MSPLayoutType {
+    /// An existing layout, identified by its key
+    Open(LayKey),
+    /// A layout that does not yet exist, but can be created by starting a new strip of this type
+    Closed { strip_type_id: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::shape_modification::ShapeModifyConfig;
+    use crate::probs::mspp::entities::StripType;
+    use crate::util::test_util::{cde_config, item};
+    use float_cmp::approx_eq;
+
+    /// Strip type 0 is 50 high and costs 1 per unit of length, strip type 1 is 30 high and costs 2
+    fn problem() -> MSPProblem {
+        let strip_type = |id: usize, height: f32, cost: f32| {
+            let mut strip = Strip::new(height, cde_config(), ShapeModifyConfig::default()).unwrap();
+            strip.set_width(100.0);
+            StripType::new(id, strip, cost)
+        };
+        MSPProblem::new(MSPInstance::new(
+            vec![(item(0, 10.0, 10.0), 2)],
+            vec![strip_type(0, 50.0, 1.0), strip_type(1, 30.0, 2.0)],
+        ))
+    }
+
+    fn place(problem: &mut MSPProblem, layout_id: MSPLayoutType) -> (LayKey, PItemKey) {
+        problem.place_item(MSPPlacement {
+            layout_id,
+            item_id: 0,
+            d_transf: DTransformation::new(0.0, (5.0, 5.0)),
+        })
+    }
+
+    #[test]
+    fn open_and_close_strips() {
+        let mut problem = problem();
+        let (lkey_0, _) = place(&mut problem, MSPLayoutType::Closed { strip_type_id: 0 });
+        let (lkey_1, pk_1) = place(&mut problem, MSPLayoutType::Closed { strip_type_id: 1 });
+        assert_eq!(problem.layouts.len(), 2);
+        assert_eq!(problem.item_demand_qtys, vec![0]);
+
+        //every layout uses a strip of its own type
+        assert_eq!(problem.layouts[lkey_0].container.id, 0);
+        assert_eq!(problem.layouts[lkey_1].container.id, 1);
+        assert_eq!(problem.strips[lkey_0].fixed_height, 50.0);
+        assert_eq!(problem.strips[lkey_1].fixed_height, 30.0);
+
+        //removing the last item closes the strip
+        let placement = problem.remove_item(lkey_1, pk_1);
+        assert_eq!(
+            placement.layout_id,
+            MSPLayoutType::Closed { strip_type_id: 1 }
+        );
+        assert_eq!(problem.layouts.len(), 1);
+        assert!(!problem.strips.contains_key(lkey_1));
+        assert_eq!(problem.item_demand_qtys, vec![1]);
+    }
+
+    #[test]
+    fn strip_cost() {
+        let mut problem = problem();
+        assert_eq!(problem.strip_cost(), 0.0);
+
+        place(&mut problem, MSPLayoutType::Closed { strip_type_id: 0 });
+        place(&mut problem, MSPLayoutType::Closed { strip_type_id: 1 });
+        assert!(approx_eq!(f32, problem.strip_cost(), 300.0));
+
+        //both strips are fitted to a length of 15, at a different cost
+        problem.fit_strips();
+        assert!(approx_eq!(f32, problem.strip_cost(), 45.0, epsilon = 0.01));
+        let solution = problem.save();
+        assert_eq!(solution.cost(&problem.instance), problem.strip_cost());
+        assert!(problem.layouts.values().all(|l| l.is_feasible()));
+    }
+}
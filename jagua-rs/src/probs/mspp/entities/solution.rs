@@ -0,0 +1,172 @@
+use crate::Instant;
+use crate::entities::{LayoutSnapshot, LayoutSnapshotData};
+use crate::probs::mspp::entities::{LayKey, MSPInstance};
+use crate::probs::spp::entities::Strip;
+use anyhow::ensure;
+use serde::de::{DeserializeSeed, Error};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use slotmap::SecondaryMap;
+
+/// Snapshot of [`MSPProblem`](crate::probs::mspp::entities::MSPProblem) at a specific moment.
+/// Can be used to restore to a previous state.
+/// Serializes without its time stamp, deserialize using [`MSPSolutionSeed`].
+#[derive(Debug, Clone)]
+pub struct MSPSolution {
+    /// A map of the layout snapshots, identified by the same keys as in the problem
+    pub layout_snapshots: SecondaryMap<LayKey, LayoutSnapshot>,
+    /// The strip used by each layout
+    pub strips: SecondaryMap<LayKey, Strip>,
+    /// Instant the solution was created
+    pub time_stamp: Instant,
+}
+
+impl MSPSolution {
+    pub fn density(&self, instance: &MSPInstance) -> f32 {
+        let total_strip_area = self
+            .layout_snapshots
+            .values()
+            .map(|ls| ls.container.area())
+            .sum::<f32>();
+
+        let total_item_area = self
+            .layout_snapshots
+            .values()
+            .map(|ls| ls.placed_item_area(instance))
+            .sum::<f32>();
+
+        total_item_area / total_strip_area
+    }
+
+    pub fn cost(&self, instance: &MSPInstance) -> f32 {
+        self.layout_snapshots
+            .iter()
+            .map(|(lkey, ls)| instance.strip_types[ls.container.id].cost(&self.strips[lkey]))
+            .sum()
+    }
+}
+
+impl Serialize for MSPSolution {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct MSPSolutionRef<'a> {
+            layout_snapshots: Vec<(LayKey, &'a Strip, &'a LayoutSnapshot)>,
+        }
+        MSPSolutionRef {
+            layout_snapshots: self
+                .layout_snapshots
+                .iter()
+                .map(|(lkey, ls)| (lkey, &self.strips[lkey], ls))
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a [`MSPSolution`] and rebuilds it against the (imported) instance it belongs to.
+/// The time stamp of the solution is set to the moment of deserialization.
+pub struct MSPSolutionSeed<'a>(pub &'a MSPInstance);
+
+impl<'de> DeserializeSeed<'de> for MSPSolutionSeed<'_> {
+    type Value = MSPSolution;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        #[derive(Deserialize)]
+        struct MSPSolutionData {
+            layout_snapshots: Vec<(LayKey, Strip, LayoutSnapshotData)>,
+        }
+        let data = MSPSolutionData::deserialize(deserializer)?;
+        let instance = self.0;
+
+        let mut layout_snapshots = SecondaryMap::new();
+        let mut strips = SecondaryMap::new();
+        for (lkey, strip, ls_data) in data.layout_snapshots {
+            let rebuild = || {
+                let type_id = ls_data.container_id;
+                ensure!(
+                    type_id < instance.strip_types.len(),
+                    "solution refers to unknown strip type {type_id}"
+                );
+                let strip_type = &instance.strip_types[type_id];
+                //only the width of the strip is part of the solution, the rest is defined by its type
+                ensure!(
+                    Strip {
+                        width: strip_type.base_strip.width,
+                        ..strip.clone()
+                    } == strip_type.base_strip,
+                    "strip does not match its type {type_id}, only its width can differ"
+                );
+                LayoutSnapshot::rebuild(ls_data, strip_type.container(&strip), instance)
+            };
+            let ls = rebuild().map_err(|e| D::Error::custom(format!("{e:#}")))?;
+            layout_snapshots.insert(lkey, ls);
+            strips.insert(lkey, strip);
+        }
+
+        Ok(MSPSolution {
+            layout_snapshots,
+            strips,
+            time_stamp: Instant::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::DTransformation;
+    use crate::geometry::shape_modification::ShapeModifyConfig;
+    use crate::probs::mspp::entities::{MSPLayoutType, MSPPlacement, MSPProblem, StripType};
+    use crate::probs::spp::entities::StripDefect;
+    use crate::util::test_util::{cde_config, item, rect};
+    use serde_json::{Value, json};
+
+    /// A single strip type of 50 high and 100 wide with a hole, and a strip of it with a single item
+    fn problem() -> MSPProblem {
+        let hole = StripDefect {
+            quality: 0,
+            shape: rect(20.0, 20.0, 30.0, 30.0).into(),
+        };
+        let mut strip = Strip::new(50.0, cde_config(), ShapeModifyConfig::default())
+            .unwrap()
+            .with_defects(vec![hole]);
+        strip.set_width(100.0);
+        let instance = MSPInstance::new(
+            vec![(item(0, 10.0, 10.0), 1)],
+            vec![StripType::new(0, strip, 1.0)],
+        );
+        let mut problem = MSPProblem::new(instance);
+        problem.place_item(MSPPlacement {
+            layout_id: MSPLayoutType::Closed { strip_type_id: 0 },
+            item_id: 0,
+            d_transf: DTransformation::new(0.0, (0.0, 0.0)),
+        });
+        problem
+    }
+
+    fn deserialize(problem: &MSPProblem, json: Value) -> serde_json::Result<MSPSolution> {
+        MSPSolutionSeed(&problem.instance).deserialize(json)
+    }
+
+    #[test]
+    fn round_trip() {
+        let problem = problem();
+        let json = serde_json::to_value(problem.save()).unwrap();
+        let solution = deserialize(&problem, json).unwrap();
+        assert_eq!(solution.layout_snapshots.len(), 1);
+        let strip = solution.strips.values().next().unwrap();
+        assert_eq!(strip, &problem.instance.strip_types[0].base_strip);
+    }
+
+    #[test]
+    fn tampered_strip_rejected() {
+        let problem = problem();
+        let mut json = serde_json::to_value(problem.save()).unwrap();
+        //the hole is removed from the strip of the first layout
+        json["layout_snapshots"][0][1]["defects"] = json!([]);
+        let err = deserialize(&problem, json).unwrap_err();
+        assert!(
+            err.to_string().contains("only its width can differ"),
+            "{err}"
+        );
+    }
+}
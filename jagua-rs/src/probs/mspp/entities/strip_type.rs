@@ -0,0 +1,36 @@
+use crate::entities::Container;
+use crate::probs::spp::entities::Strip;
+
+#[derive(Debug, Clone)]
+/// A type of strip in the Multi-Strip Packing Problem (e.g. a roll of fabric), with its own fixed height and cost per unit of consumed length.
+pub struct StripType {
+    /// Unique identifier for the strip type
+    pub id: usize,
+    /// The strip from which every layout of this type starts
+    pub base_strip: Strip,
+    /// The cost of a single unit of length of the strip
+    pub cost_per_length: f32,
+}
+
+impl StripType {
+    pub fn new(id: usize, base_strip: Strip, cost_per_length: f32) -> Self {
+        Self {
+            id,
+            base_strip,
+            cost_per_length,
+        }
+    }
+
+    /// Creates the container of a `strip` of this type, identified by the id of the type.
+    pub fn container(&self, strip: &Strip) -> Container {
+        Container {
+            id: self.id,
+            ..Container::from(strip)
+        }
+    }
+
+    /// Cost of consuming a `strip` of this type
+    pub fn cost(&self, strip: &Strip) -> f32 {
+        strip.width * self.cost_per_length
+    }
+}
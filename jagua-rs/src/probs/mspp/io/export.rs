@@ -0,0 +1,21 @@
+use crate::Instant;
+use crate::io::export::export_layout_snapshot;
+use crate::probs::mspp::entities::{MSPInstance, MSPSolution};
+use crate::probs::mspp::io::ext_repr::{ExtMSPSolution, ExtStripLayout};
+
+/// Exports a solution out of the library
+pub fn export(instance: &MSPInstance, solution: &MSPSolution, epoch: Instant) -> ExtMSPSolution {
+    ExtMSPSolution {
+        cost: solution.cost(instance),
+        strips: solution
+            .layout_snapshots
+            .iter()
+            .map(|(lkey, ls)| ExtStripLayout {
+                strip_width: solution.strips[lkey].width,
+                layout: export_layout_snapshot(ls, instance),
+            })
+            .collect(),
+        density: solution.density(instance),
+        run_time_sec: solution.time_stamp.duration_since(epoch).as_secs(),
+    }
+}
@@ -0,0 +1,56 @@
+use crate::io::ext_repr::ExtLayout;
+use serde::{Deserialize, Serialize};
+
+/// Multi-Strip Packing Problem instance
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtMSPInstance {
+    /// The name of the instance
+    pub name: String,
+    /// Set of items to be produced
+    pub items: Vec<ExtItem>,
+    /// Types of strips which can be used
+    pub strip_types: Vec<ExtStripType>,
+}
+
+/// Item with a demand
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtItem {
+    #[serde(flatten)]
+    /// External representation of the item in the base library
+    pub base: crate::io::ext_repr::ExtItem,
+    /// Amount of times this item has to be produced
+    pub demand: u64,
+}
+
+/// Strip (e.g. a roll) with a fixed height and cost per unit of length
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtStripType {
+    /// Unique identifier of the strip type
+    pub id: u64,
+    /// Fixed height of the strip
+    pub strip_height: f32,
+    /// The cost of a single unit of length of the strip
+    pub cost_per_length: f32,
+}
+
+/// Multi-Strip Packing Problem solution
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtMSPSolution {
+    /// Total cost of the length consumed of all strips
+    pub cost: f32,
+    /// Strips which compose the solution
+    pub strips: Vec<ExtStripLayout>,
+    /// Sum of the area of the produced items divided by the sum of the area of the strips
+    pub density: f32,
+    /// The time it took to generate the solution in seconds
+    pub run_time_sec: u64,
+}
+
+/// A strip in use, along with its layout. The id of the layout's container refers to the strip type.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtStripLayout {
+    /// The consumed length of the strip
+    pub strip_width: f32,
+    /// Layout of the strip
+    pub layout: ExtLayout,
+}
@@ -0,0 +1,77 @@
+use crate::entities::Item;
+use crate::geometry::shape_modification::ShapeModifyConfig;
+use crate::io::import::Importer;
+use crate::probs::mspp::entities::{MSPInstance, StripType};
+use crate::probs::mspp::io::ext_repr::ExtMSPInstance;
+use crate::probs::spp::entities::Strip;
+use anyhow::{Result, ensure};
+use itertools::Itertools;
+use rayon::prelude::*;
+
+/// Imports an instance into the library
+pub fn import(importer: &Importer, ext_instance: &ExtMSPInstance) -> Result<MSPInstance> {
+    let items: Vec<(Item, usize)> = {
+        let mut items = ext_instance
+            .items
+            .par_iter()
+            .map(|ext_item| {
+                let item = importer.import_item(&ext_item.base)?;
+                let demand = ext_item.demand as usize;
+                Ok((item, demand))
+            })
+            .collect::<Result<Vec<(Item, usize)>>>()?;
+
+        items.sort_by_key(|(item, _)| item.id);
+        ensure!(
+            items.iter().enumerate().all(|(i, (item, _))| item.id == i),
+            "All items should have consecutive IDs starting from 0. IDs: {:?}",
+            items.iter().map(|(item, _)| item.id).sorted().collect_vec()
+        );
+        items
+    };
+
+    let total_item_area = items
+        .iter()
+        .map(|(item, demand)| item.area() * *demand as f32)
+        .sum::<f32>();
+
+    let strip_types = {
+        let mut strip_types = ext_instance
+            .strip_types
+            .iter()
+            .map(|ext_st| {
+                ensure!(
+                    ext_st.cost_per_length >= 0.0,
+                    "strip type {} has a negative cost",
+                    ext_st.id
+                );
+                let mut base_strip = Strip::new(
+                    ext_st.strip_height,
+                    importer.cde_config,
+                    ShapeModifyConfig {
+                        offset: importer.shape_modify_config.offset,
+                        simplify_tolerance: None,
+                        narrow_concavity_cutoff_ratio: None,
+                    },
+                )?;
+                // Initialize the base width to fit all items at 100% density
+                base_strip.set_width(total_item_area / ext_st.strip_height);
+                Ok(StripType::new(
+                    ext_st.id as usize,
+                    base_strip,
+                    ext_st.cost_per_length,
+                ))
+            })
+            .collect::<Result<Vec<StripType>>>()?;
+
+        strip_types.sort_by_key(|st| st.id);
+        ensure!(
+            strip_types.iter().enumerate().all(|(i, st)| st.id == i),
+            "All strip types should have consecutive IDs starting from 0. IDs: {:?}",
+            strip_types.iter().map(|st| st.id).sorted().collect_vec()
+        );
+        strip_types
+    };
+
+    Ok(MSPInstance::new(items, strip_types))
+}
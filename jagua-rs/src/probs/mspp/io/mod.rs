@@ -0,0 +1,11 @@
+mod export;
+mod import;
+
+/// External (serializable) representations of all Multi-Strip Packing Problem related entities.
+pub mod ext_repr;
+
+/// Exports a multi-strip packing solution out of the library.
+pub use export::export;
+
+/// Imports a multi-strip packing instance into the library.
+pub use import::import;
@@ -0,0 +1,8 @@
+/// Entities to model the Multi-Strip Packing Problem (MSPP)
+pub mod entities;
+
+/// Importing problem instances into and exporting solutions out of this library
+pub mod io;
+
+/// Helper functions which do not belong to any specific module
+pub mod util;
@@ -0,0 +1,27 @@
+use crate::entities::Item;
+use crate::probs::mspp::entities::{MSPProblem, MSPSolution, StripType};
+use crate::util::assertions::layouts_match;
+
+pub fn problem_matches_solution(mspp: &MSPProblem, sol: &MSPSolution) -> bool {
+    let MSPSolution {
+        layout_snapshots,
+        strips,
+        time_stamp: _,
+    } = sol;
+
+    //layouts which were rebuilt during a restore can be identified by a different key than in the solution
+    mspp.layouts.len() == layout_snapshots.len()
+        && mspp.layouts.iter().all(|(lkey, l)| {
+            layout_snapshots
+                .iter()
+                .any(|(skey, ls)| strips[skey] == mspp.strips[lkey] && layouts_match(l, ls))
+        })
+}
+
+pub fn instance_item_strip_type_ids_correct(
+    items: &[(Item, usize)],
+    strip_types: &[StripType],
+) -> bool {
+    items.iter().enumerate().all(|(i, (item, _))| item.id == i)
+        && strip_types.iter().enumerate().all(|(i, st)| st.id == i)
+}
@@ -0,0 +1,91 @@
+use crate::entities::{Container, Instance, Item};
+use crate::probs::odp::entities::{AspectRatioRange, Sheet};
+use crate::probs::odp::util::assertions;
+use serde::{Deserialize, Serialize};
+use std::iter;
+
+#[derive(Debug, Clone)]
+/// Instance of the Open Dimension Problem: all items have to be packed into a rectangular sheet
+/// of which both dimensions are free, minimizing either its area or perimeter.
+pub struct ODInstance {
+    /// The items to be packed and their demands
+    pub items: Vec<(Item, usize)>,
+    /// The sheet in which to pack the items, its initial dimensions serve as a starting point
+    pub base_sheet: Sheet,
+    /// The measure of the sheet to minimize
+    pub objective: ODObjective,
+    /// Allowed aspect ratios of the sheet, if restricted
+    pub aspect_ratio: Option<AspectRatioRange>,
+}
+
+impl ODInstance {
+    pub fn new(
+        items: Vec<(Item, usize)>,
+        base_sheet: Sheet,
+        objective: ODObjective,
+        aspect_ratio: Option<AspectRatioRange>,
+    ) -> Self {
+        assert!(
+            assertions::instance_item_ids_correct(&items),
+            "All items should have consecutive IDs starting from 0"
+        );
+
+        Self {
+            items,
+            base_sheet,
+            objective,
+            aspect_ratio,
+        }
+    }
+
+    pub fn item_area(&self) -> f32 {
+        self.items
+            .iter()
+            .map(|(item, qty)| item.shape_orig.area() * *qty as f32)
+            .sum()
+    }
+
+    pub fn item_qty(&self, id: usize) -> usize {
+        self.items[id].1
+    }
+
+    pub fn total_item_qty(&self) -> usize {
+        self.items.iter().map(|(_, qty)| *qty).sum()
+    }
+}
+
+impl Instance for ODInstance {
+    fn items(&self) -> impl Iterator<Item = &Item> {
+        self.items.iter().map(|(item, _qty)| item)
+    }
+
+    fn containers(&self) -> impl Iterator<Item = &Container> {
+        iter::empty()
+    }
+
+    fn item(&self, id: usize) -> &Item {
+        &self.items.get(id).unwrap().0
+    }
+
+    fn container(&self, _id: usize) -> &Container {
+        panic!("no predefined containers for open dimension instances")
+    }
+}
+
+/// Measure of the sheet to minimize in the Open Dimension Problem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ODObjective {
+    Area,
+    Perimeter,
+}
+
+impl ODObjective {
+    /// The value of the objective for a sheet, lower is better
+    pub fn evaluate(&self, sheet: &Sheet) -> f32 {
+        match self {
+            ODObjective::Area => sheet.area(),
+            ODObjective::Perimeter => sheet.perimeter(),
+        }
+    }
+}
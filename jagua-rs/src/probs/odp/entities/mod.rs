@@ -0,0 +1,21 @@
+mod instance;
+mod problem;
+mod sheet;
+mod solution;
+
+#[doc(inline)]
+pub use instance::ODInstance;
+#[doc(inline)]
+pub use instance::ODObjective;
+#[doc(inline)]
+pub use problem::ODPlacement;
+#[doc(inline)]
+pub use problem::ODProblem;
+#[doc(inline)]
+pub use sheet::AspectRatioRange;
+#[doc(inline)]
+pub use sheet::Sheet;
+#[doc(inline)]
+pub use solution::ODSolution;
+#[doc(inline)]
+pub use solution::ODSolutionSeed;
@@ -0,0 +1,266 @@
+use crate::Instant;
+use crate::entities::{Instance, Layout, PItemKey};
+use crate::geometry::DTransformation;
+use crate::probs::odp::entities::{ODInstance, ODSolution, Sheet};
+use crate::probs::odp::util::assertions::problem_matches_solution;
+use itertools::Itertools;
+
+/// Modifiable counterpart of [`ODInstance`]: items can be placed and removed, the sheet can be resized or fitted in both dimensions.
+#[derive(Clone)]
+pub struct ODProblem {
+    pub instance: ODInstance,
+    pub sheet: Sheet,
+    pub layout: Layout,
+    pub item_demand_qtys: Vec<usize>,
+}
+
+impl ODProblem {
+    pub fn new(instance: ODInstance) -> Self {
+        let item_demand_qtys = instance.items.iter().map(|(_, qty)| *qty).collect_vec();
+        let sheet = instance.base_sheet;
+        let layout = Layout::new(sheet.into());
+        Self {
+            instance,
+            sheet,
+            layout,
+            item_demand_qtys,
+        }
+    }
+
+    /// Modifies the dimensions of the sheet, keeping its bottom left corner fixed.
+    pub fn change_sheet_size(&mut self, new_width: f32, new_height: f32) {
+        self.sheet.set_size(new_width, new_height);
+        self.layout.swap_container(self.sheet.into());
+    }
+
+    /// Shrinks the sheet to the minimum width and height that fit all items.
+    /// If the aspect ratio is restricted, one of the dimensions is grown again to comply.
+    /// Does nothing if no items are placed.
+    pub fn fit_sheet(&mut self) {
+        if let Some((width, height)) = self.fitted_dimensions() {
+            self.resize_within_aspect_ratio(width, height);
+        }
+    }
+
+    /// Shrinks the width of the sheet to the minimum that fits all items.
+    /// If the aspect ratio is restricted, the width is not shrunk beyond its lower bound,
+    /// and the height is grown if the sheet is still too wide for its height. Otherwise, the height is left untouched.
+    /// Does nothing if no items are placed.
+    pub fn fit_width(&mut self) {
+        if let Some((width, _)) = self.fitted_dimensions() {
+            self.resize_within_aspect_ratio(width, self.sheet.height);
+        }
+    }
+
+    /// Shrinks the height of the sheet to the minimum that fits all items.
+    /// If the aspect ratio is restricted, the height is not shrunk beyond its lower bound,
+    /// and the width is grown if the sheet is still too narrow for its height. Otherwise, the width is left untouched.
+    /// Does nothing if no items are placed.
+    pub fn fit_height(&mut self) {
+        if let Some((_, height)) = self.fitted_dimensions() {
+            self.resize_within_aspect_ratio(self.sheet.width, height);
+        }
+    }
+
+    /// Places an item according to the given `ODPlacement` in the problem.
+    pub fn place_item(&mut self, placement: ODPlacement) -> PItemKey {
+        self.register_included_item(placement.item_id);
+        let item = self.instance.item(placement.item_id);
+
+        self.layout.place_item(item, placement.d_transf)
+    }
+
+    /// Removes a placed item from the sheet. Returns the placement of the item.
+    pub fn remove_item(&mut self, pkey: PItemKey) -> ODPlacement {
+        let pi = self.layout.remove_item(pkey);
+        self.deregister_included_item(pi.item_id);
+
+        ODPlacement {
+            item_id: pi.item_id,
+            d_transf: pi.d_transf,
+        }
+    }
+
+    /// Creates a snapshot of the current state of the problem as a [`ODSolution`].
+    pub fn save(&self) -> ODSolution {
+        let solution = ODSolution {
+            layout_snapshot: self.layout.save(),
+            sheet: self.sheet,
+            time_stamp: Instant::now(),
+        };
+
+        debug_assert!(problem_matches_solution(self, &solution));
+
+        solution
+    }
+
+    /// Restores the state of the problem to the given [`ODSolution`].
+    pub fn restore(&mut self, solution: &ODSolution) {
+        if self.sheet == solution.sheet {
+            // the sheet is the same, restore the layout
+            self.layout.restore(&solution.layout_snapshot);
+        } else {
+            // the sheet has changed, rebuild the layout
+            self.layout = Layout::from_snapshot(&solution.layout_snapshot);
+            self.sheet = solution.sheet;
+        }
+
+        //Restore the item demands
+        {
+            self.item_demand_qtys
+                .iter_mut()
+                .enumerate()
+                .for_each(|(id, qty)| *qty = self.instance.item_qty(id));
+
+            self.layout
+                .placed_items
+                .iter()
+                .for_each(|(_, pi)| self.item_demand_qtys[pi.item_id] -= 1);
+        }
+        debug_assert!(problem_matches_solution(self, solution));
+    }
+
+    fn register_included_item(&mut self, item_id: usize) {
+        self.item_demand_qtys[item_id] -= 1;
+    }
+
+    fn deregister_included_item(&mut self, item_id: usize) {
+        self.item_demand_qtys[item_id] += 1;
+    }
+
+    /// Smallest dimensions of the sheet which contain all placed items, `None` if there are none
+    fn fitted_dimensions(&self) -> Option<(f32, f32)> {
+        self.sheet
+            .fitted_size(self.layout.placed_items.values().map(|pi| pi.shape.bbox))
+    }
+
+    fn resize_within_aspect_ratio(&mut self, width: f32, height: f32) {
+        let feasible_before = self.layout.is_feasible();
+
+        let (width, height) = match self.instance.aspect_ratio {
+            Some(ar) => ar.enlarge(width, height),
+            None => (width, height),
+        };
+        self.change_sheet_size(width, height);
+
+        debug_assert!(feasible_before == self.layout.is_feasible());
+    }
+
+    /// Whether the current dimensions of the sheet comply with the aspect ratio restriction of the instance
+    pub fn aspect_ratio_feasible(&self) -> bool {
+        self.instance
+            .aspect_ratio
+            .is_none_or(|ar| ar.contains(self.sheet.aspect_ratio()))
+    }
+
+    /// The value of the objective for the current sheet, lower is better
+    pub fn objective_value(&self) -> f32 {
+        self.instance.objective.evaluate(&self.sheet)
+    }
+
+    pub fn density(&self) -> f32 {
+        self.layout.density(&self.instance)
+    }
+}
+
+/// Represents a placement of an item in the open dimension problem.
+#[derive(Debug, Clone, Copy)]
+pub struct ODPlacement {
+    pub item_id: usize,
+    pub d_transf: DTransformation,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::shape_modification::ShapeModifyConfig;
+    use crate::probs::odp::entities::{AspectRatioRange, ODObjective};
+    use crate::util::test_util::{cde_config, item};
+    use float_cmp::approx_eq;
+
+    /// Sheet of 100x100 with two copies of a 10x10 item
+    fn problem(aspect_ratio: Option<AspectRatioRange>) -> ODProblem {
+        let sheet = Sheet::new(100.0, 100.0, cde_config(), ShapeModifyConfig::default()).unwrap();
+        ODProblem::new(ODInstance::new(
+            vec![(item(0, 10.0, 10.0), 2)],
+            sheet,
+            ODObjective::Area,
+            aspect_ratio,
+        ))
+    }
+
+    /// Places both items next to each other, spanning 30 wide and 15 high
+    fn place_items(problem: &mut ODProblem) {
+        for pos in [(5.0, 5.0), (20.0, 5.0)] {
+            problem.place_item(ODPlacement {
+                item_id: 0,
+                d_transf: DTransformation::new(0.0, pos),
+            });
+        }
+    }
+
+    fn assert_sheet_size(problem: &ODProblem, width: f32, height: f32) {
+        let sheet = &problem.sheet;
+        assert!(
+            approx_eq!(f32, sheet.width, width, epsilon = 0.01)
+                && approx_eq!(f32, sheet.height, height, epsilon = 0.01),
+            "sheet is {}x{}, expected {width}x{height}",
+            sheet.width,
+            sheet.height
+        );
+    }
+
+    #[test]
+    fn fit_empty_sheet() {
+        let mut problem = problem(None);
+        problem.fit_sheet();
+        problem.fit_width();
+        problem.fit_height();
+        assert_sheet_size(&problem, 100.0, 100.0);
+    }
+
+    #[test]
+    fn fit_sheet() {
+        let mut problem = problem(None);
+        place_items(&mut problem);
+
+        problem.fit_width();
+        assert_sheet_size(&problem, 30.0, 100.0);
+        problem.fit_height();
+        assert_sheet_size(&problem, 30.0, 15.0);
+        assert!(problem.layout.is_feasible());
+        assert!(approx_eq!(
+            f32,
+            problem.objective_value(),
+            450.0,
+            epsilon = 0.1
+        ));
+
+        //the fitted sheet survives a restore of the layout
+        let solution = problem.save();
+        problem.change_sheet_size(100.0, 100.0);
+        problem.restore(&solution);
+        assert_sheet_size(&problem, 30.0, 15.0);
+    }
+
+    #[test]
+    fn fit_sheet_within_aspect_ratio() {
+        //the fitted sheet is too wide, its height is grown again
+        let mut wide = problem(Some(AspectRatioRange::new(0.5, 1.0).unwrap()));
+        place_items(&mut wide);
+        wide.fit_sheet();
+        assert_sheet_size(&wide, 30.0, 30.0);
+        assert!(wide.aspect_ratio_feasible());
+
+        //the width is not shrunk beyond its lower bound
+        let mut narrow = problem(Some(AspectRatioRange::new(1.0, 2.0).unwrap()));
+        place_items(&mut narrow);
+        narrow.fit_width();
+        assert_sheet_size(&narrow, 100.0, 100.0);
+
+        //the height is not shrunk beyond its lower bound
+        narrow.fit_height();
+        assert_sheet_size(&narrow, 100.0, 50.0);
+        assert!(narrow.aspect_ratio_feasible());
+    }
+}
@@ -0,0 +1,144 @@
+use crate::collision_detection::CDEConfig;
+use crate::entities::Container;
+use crate::geometry::primitives::{Rect, SPolygon};
+use crate::geometry::shape_modification::{ShapeModifyConfig, ShapeModifyMode};
+use crate::geometry::{DTransformation, OriginalShape};
+use crate::probs::FIT_TOLERANCE;
+use anyhow::{Result, ensure};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Copy, PartialEq, Serialize, Deserialize)]
+/// Represents a rectangular container of which both the width and height are variable.
+pub struct Sheet {
+    pub cde_config: CDEConfig,
+    pub shape_modify_config: ShapeModifyConfig,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Sheet {
+    pub fn new(
+        width: f32,
+        height: f32,
+        cde_config: CDEConfig,
+        shape_modify_config: ShapeModifyConfig,
+    ) -> Result<Self> {
+        ensure!(
+            width > 0.0 && height > 0.0,
+            "sheet dimensions must be positive"
+        );
+        Ok(Sheet {
+            cde_config,
+            shape_modify_config,
+            width,
+            height,
+        })
+    }
+
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        self.width = width;
+        self.height = height;
+    }
+
+    pub fn area(&self) -> f32 {
+        self.width * self.height
+    }
+
+    pub fn perimeter(&self) -> f32 {
+        2.0 * (self.width + self.height)
+    }
+
+    /// Minimum dimensions of the sheet to contain shapes with the given bounding boxes, `None` if there are none.
+    /// Includes a small tolerance (avoiding false collision positives) and the shape offset (if any).
+    pub fn fitted_size(&self, bboxes: impl IntoIterator<Item = Rect>) -> Option<(f32, f32)> {
+        let (x_max, y_max) = bboxes
+            .into_iter()
+            .map(|bbox| (bbox.x_max, bbox.y_max))
+            .reduce(|(x1, y1), (x2, y2)| (x1.max(x2), y1.max(y2)))?;
+        // the sheet needs to be at least `offset` larger than the shapes
+        let offset = self.shape_modify_config.offset.unwrap_or(0.0);
+        Some((
+            x_max * FIT_TOLERANCE + offset,
+            y_max * FIT_TOLERANCE + offset,
+        ))
+    }
+
+    /// Ratio of the width to the height of the sheet
+    pub fn aspect_ratio(&self) -> f32 {
+        self.width / self.height
+    }
+}
+
+impl From<Sheet> for Container {
+    fn from(s: Sheet) -> Container {
+        Container::new(
+            0,
+            OriginalShape {
+                shape: SPolygon::from(Rect::try_new(0.0, 0.0, s.width, s.height).unwrap()),
+                pre_transform: DTransformation::empty(),
+                modify_mode: ShapeModifyMode::Deflate,
+                modify_config: s.shape_modify_config,
+            },
+            vec![],
+            vec![],
+            vec![],
+            s.cde_config,
+        )
+        .unwrap()
+    }
+}
+
+/// Range of allowed aspect ratios (width / height) of a [`Sheet`].
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub struct AspectRatioRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl AspectRatioRange {
+    pub fn new(min: f32, max: f32) -> Result<Self> {
+        ensure!(
+            min > 0.0 && min <= max,
+            "invalid aspect ratio range [{min}, {max}]"
+        );
+        Ok(AspectRatioRange { min, max })
+    }
+
+    pub fn contains(&self, ratio: f32) -> bool {
+        //small tolerance to absorb floating point errors
+        ratio >= self.min * 0.9999 && ratio <= self.max * 1.0001
+    }
+
+    /// Grows one of the dimensions so the ratio of `width` to `height` falls within the range.
+    /// The other dimension is left untouched.
+    pub fn enlarge(&self, width: f32, height: f32) -> (f32, f32) {
+        let ratio = width / height;
+        if ratio < self.min {
+            (height * self.min, height)
+        } else if ratio > self.max {
+            (width, width / self.max)
+        } else {
+            (width, height)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aspect_ratio_range() {
+        assert!(AspectRatioRange::new(0.0, 1.0).is_err());
+        assert!(AspectRatioRange::new(2.0, 1.0).is_err());
+
+        let range = AspectRatioRange::new(0.5, 2.0).unwrap();
+        assert!(range.contains(0.5) && range.contains(1.0) && range.contains(2.0));
+        assert!(!range.contains(0.4) && !range.contains(2.1));
+
+        //only one dimension is grown, the other is left untouched
+        assert_eq!(range.enlarge(10.0, 10.0), (10.0, 10.0));
+        assert_eq!(range.enlarge(10.0, 40.0), (20.0, 40.0));
+        assert_eq!(range.enlarge(40.0, 10.0), (40.0, 20.0));
+    }
+}
@@ -0,0 +1,70 @@
+use crate::Instant;
+use crate::entities::{LayoutSnapshot, LayoutSnapshotData};
+use crate::probs::odp::entities::{ODInstance, Sheet};
+use serde::de::{DeserializeSeed, Error};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Snapshot of [`ODProblem`](crate::probs::odp::entities::ODProblem) at a specific moment. Can be used to restore to a previous state.
+/// Serializes without its time stamp, deserialize using [`ODSolutionSeed`].
+#[derive(Debug, Clone)]
+pub struct ODSolution {
+    pub sheet: Sheet,
+    pub layout_snapshot: LayoutSnapshot,
+    /// Instant the solution was created
+    pub time_stamp: Instant,
+}
+
+impl ODSolution {
+    pub fn density(&self, instance: &ODInstance) -> f32 {
+        self.layout_snapshot.density(instance)
+    }
+
+    /// The value of the objective of the instance for this solution, lower is better
+    pub fn objective_value(&self, instance: &ODInstance) -> f32 {
+        instance.objective.evaluate(&self.sheet)
+    }
+}
+
+impl Serialize for ODSolution {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct ODSolutionRef<'a> {
+            sheet: &'a Sheet,
+            layout_snapshot: &'a LayoutSnapshot,
+        }
+        ODSolutionRef {
+            sheet: &self.sheet,
+            layout_snapshot: &self.layout_snapshot,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a [`ODSolution`] and rebuilds it against the (imported) instance it belongs to.
+/// The time stamp of the solution is set to the moment of deserialization.
+pub struct ODSolutionSeed<'a>(pub &'a ODInstance);
+
+impl<'de> DeserializeSeed<'de> for ODSolutionSeed<'_> {
+    type Value = ODSolution;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        #[derive(Deserialize)]
+        struct ODSolutionData {
+            sheet: Sheet,
+            layout_snapshot: LayoutSnapshotData,
+        }
+        let ODSolutionData {
+            sheet,
+            layout_snapshot,
+        } = ODSolutionData::deserialize(deserializer)?;
+
+        let layout_snapshot = LayoutSnapshot::rebuild(layout_snapshot, sheet.into(), self.0)
+            .map_err(|e| D::Error::custom(format!("{e:#}")))?;
+
+        Ok(ODSolution {
+            sheet,
+            layout_snapshot,
+            time_stamp: Instant::now(),
+        })
+    }
+}
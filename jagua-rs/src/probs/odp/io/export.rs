@@ -0,0 +1,16 @@
+use crate::Instant;
+use crate::io::export::export_layout_snapshot;
+use crate::probs::odp::entities::{ODInstance, ODSolution};
+use crate::probs::odp::io::ext_repr::ExtODSolution;
+
+/// Exports a solution out of the library
+pub fn export(instance: &ODInstance, solution: &ODSolution, epoch: Instant) -> ExtODSolution {
+    ExtODSolution {
+        sheet_width: solution.sheet.width,
+        sheet_height: solution.sheet.height,
+        objective_value: solution.objective_value(instance),
+        layout: export_layout_snapshot(&solution.layout_snapshot, instance),
+        density: solution.density(instance),
+        run_time_sec: solution.time_stamp.duration_since(epoch).as_secs(),
+    }
+}
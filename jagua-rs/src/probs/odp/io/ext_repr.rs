@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::io::ext_repr::ExtLayout;
+
+/// Open Dimension Problem instance
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtODInstance {
+    /// The name of the instance
+    pub name: String,
+    /// Set of items to be produced
+    pub items: Vec<ExtItem>,
+    /// The measure of the sheet to minimize
+    pub objective: ExtODObjective,
+    /// Minimum ratio of the width to the height of the sheet
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub min_aspect_ratio: Option<f32>,
+    /// Maximum ratio of the width to the height of the sheet
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub max_aspect_ratio: Option<f32>,
+}
+
+/// Item with a demand
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtItem {
+    #[serde(flatten)]
+    /// External representation of the item in the base library
+    pub base: crate::io::ext_repr::ExtItem,
+    /// Amount of times this item has to be produced
+    pub demand: u64,
+}
+
+/// Measure of the sheet to minimize
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtODObjective {
+    /// Area of the sheet
+    Area,
+    /// Perimeter of the sheet
+    Perimeter,
+}
+
+/// Open Dimension Problem solution
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExtODSolution {
+    /// The width of the sheet
+    pub sheet_width: f32,
+    /// The height of the sheet
+    pub sheet_height: f32,
+    /// The value of the objective (area or perimeter of the sheet)
+    pub objective_value: f32,
+    /// Layout of the sheet
+    pub layout: ExtLayout,
+    /// Sum of the area of the produced items divided by the area of the sheet
+    pub density: f32,
+    /// The time it took to generate the solution in seconds
+    pub run_time_sec: u64,
+}
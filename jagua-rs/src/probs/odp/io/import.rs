@@ -0,0 +1,65 @@
+use crate::entities::Item;
+use crate::geometry::shape_modification::ShapeModifyConfig;
+use crate::io::import::Importer;
+use crate::probs::odp::entities::{AspectRatioRange, ODInstance, ODObjective, Sheet};
+use crate::probs::odp::io::ext_repr::{ExtODInstance, ExtODObjective};
+use anyhow::{Result, ensure};
+use itertools::Itertools;
+use rayon::prelude::*;
+
+/// Imports an instance into the library
+pub fn import(importer: &Importer, ext_instance: &ExtODInstance) -> Result<ODInstance> {
+    let items: Vec<(Item, usize)> = {
+        let mut items = ext_instance
+            .items
+            .par_iter()
+            .map(|ext_item| {
+                let item = importer.import_item(&ext_item.base)?;
+                let demand = ext_item.demand as usize;
+                Ok((item, demand))
+            })
+            .collect::<Result<Vec<(Item, usize)>>>()?;
+
+        items.sort_by_key(|(item, _)| item.id);
+        ensure!(
+            items.iter().enumerate().all(|(i, (item, _))| item.id == i),
+            "All items should have consecutive IDs starting from 0. IDs: {:?}",
+            items.iter().map(|(item, _)| item.id).sorted().collect_vec()
+        );
+        items
+    };
+
+    let objective = match ext_instance.objective {
+        ExtODObjective::Area => ODObjective::Area,
+        ExtODObjective::Perimeter => ODObjective::Perimeter,
+    };
+
+    let aspect_ratio = match (ext_instance.min_aspect_ratio, ext_instance.max_aspect_ratio) {
+        (None, None) => None,
+        (min, max) => Some(AspectRatioRange::new(
+            min.unwrap_or(f32::MIN_POSITIVE),
+            max.unwrap_or(f32::INFINITY),
+        )?),
+    };
+
+    let total_item_area = items
+        .iter()
+        .map(|(item, demand)| item.area() * *demand as f32)
+        .sum::<f32>();
+    ensure!(total_item_area > 0.0, "instance contains no items");
+
+    // Initialize the base sheet for 100% density, as square as the aspect ratio allows
+    let ratio = aspect_ratio.map_or(1.0, |ar| ar.min.max(1.0).min(ar.max));
+    let base_sheet = Sheet::new(
+        (total_item_area * ratio).sqrt(),
+        (total_item_area / ratio).sqrt(),
+        importer.cde_config,
+        ShapeModifyConfig {
+            offset: importer.shape_modify_config.offset,
+            simplify_tolerance: None,
+            narrow_concavity_cutoff_ratio: None,
+        },
+    )?;
+
+    Ok(ODInstance::new(items, base_sheet, objective, aspect_ratio))
+}
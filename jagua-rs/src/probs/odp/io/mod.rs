@@ -0,0 +1,11 @@
+mod export;
+mod import;
+
+/// External (serializable) representations of all Open Dimension Problem related entities.
+pub mod ext_repr;
+
+/// Exports an open dimension solution out of the library.
+pub use export::export;
+
+/// Imports an open dimension instance into the library.
+pub use import::import;
@@ -0,0 +1,24 @@
+use crate::entities::Item;
+use crate::probs::odp::entities::{ODProblem, ODSolution};
+use crate::util::assertions::layouts_match;
+
+pub fn problem_matches_solution(odp: &ODProblem, sol: &ODSolution) -> bool {
+    let ODSolution {
+        sheet,
+        layout_snapshot,
+        time_stamp: _,
+    } = sol;
+
+    assert_eq!(*sheet, odp.sheet);
+    assert_eq!(odp.density(), sol.density(&odp.instance));
+    assert!(layouts_match(&odp.layout, layout_snapshot));
+
+    true
+}
+
+pub fn instance_item_ids_correct(items: &[(Item, usize)]) -> bool {
+    items
+        .iter()
+        .enumerate()
+        .all(|(i, (item, _qty))| item.id == i)
+}
@@ -1,25 +1,54 @@
 use crate::entities::{Container, Instance, Item};
-use crate::probs::spp::entities::Strip;
+use crate::probs::spp::entities::{SPPlacement, Strip};
 use crate::probs::spp::util::assertions;
 use std::iter;
 
 #[derive(Debug, Clone)]
 /// Instance of the Strip Packing Problem.
 pub struct SPInstance {
-    /// The items to be packed and their demands
+    /// The items to be packed and their (maximum) demands
     pub items: Vec<(Item, usize)>,
+    /// The minimum demand of each item, equal to its demand unless part of it is optional
+    pub item_min_qtys: Vec<usize>,
     /// The strip in which to pack the items
     pub base_strip: Strip,
+    /// Items which are pre-placed in the strip and cannot be removed, they count towards the demand of their item
+    pub fixed_placements: Vec<SPPlacement>,
 }
 
 impl SPInstance {
-    pub fn new(items: Vec<(Item, usize)>, base_strip: Strip) -> Self {
+    pub fn new(
+        items: Vec<(Item, usize)>,
+        base_strip: Strip,
+        fixed_placements: Vec<SPPlacement>,
+    ) -> Self {
         assert!(
             assertions::instance_item_ids_correct(&items),
             "All items should have consecutive IDs starting from 0"
         );
+        assert!(
+            assertions::fixed_placements_within_demand(&items, &fixed_placements),
+            "Fixed placements should refer to existing items and not exceed their demand"
+        );
+
+        let item_min_qtys = items.iter().map(|(_, qty)| *qty).collect();
 
-        Self { items, base_strip }
+        Self {
+            items,
+            item_min_qtys,
+            base_strip,
+            fixed_placements,
+        }
+    }
+
+    /// Makes part of the demand of the items optional, only `min_qtys` (indexed by item id) have to be placed
+    pub fn with_min_demands(mut self, min_qtys: Vec<usize>) -> Self {
+        assert!(
+            assertions::min_demands_correct(&self.items, &min_qtys),
+            "Minimum demands should be defined for every item and not exceed its demand"
+        );
+        self.item_min_qtys = min_qtys;
+        self
     }
 
     pub fn item_area(&self) -> f32 {
@@ -36,6 +65,14 @@ impl SPInstance {
     pub fn total_item_qty(&self) -> usize {
         self.items.iter().map(|(_, qty)| *qty).sum()
     }
+
+    pub fn item_min_qty(&self, id: usize) -> usize {
+        self.item_min_qtys[id]
+    }
+
+    pub fn total_item_min_qty(&self) -> usize {
+        self.item_min_qtys.iter().sum()
+    }
 }
 
 impl Instance for SPInstance {
@@ -12,4 +12,12 @@ pub use problem::SPProblem;
 #[doc(inline)]
 pub use solution::SPSolution;
 #[doc(inline)]
+pub use solution::SPSolutionSeed;
+#[doc(inline)]
+pub use strip::EdgeProfile;
+#[doc(inline)]
 pub use strip::Strip;
+#[doc(inline)]
+pub use strip::StripDefect;
+#[doc(inline)]
+pub use strip::StripDirection;
@@ -1,6 +1,7 @@
 use crate::Instant;
 use crate::entities::{Instance, Layout, PItemKey};
 use crate::geometry::DTransformation;
+use crate::geometry::geo_traits::Transformable;
 use crate::probs::spp::entities::strip::Strip;
 use crate::probs::spp::entities::{SPInstance, SPSolution};
 use crate::probs::spp::util::assertions::problem_matches_solution;
@@ -12,47 +13,69 @@ pub struct SPProblem {
     pub instance: SPInstance,
     pub strip: Strip,
     pub layout: Layout,
+    /// Number of copies of each item which can still be placed before its (maximum) demand is reached
     pub item_demand_qtys: Vec<usize>,
 }
 
 impl SPProblem {
     pub fn new(instance: SPInstance) -> Self {
         let item_demand_qtys = instance.items.iter().map(|(_, qty)| *qty).collect_vec();
-        let strip = instance.base_strip;
-        let layout = Layout::new(strip.into());
+        let mut strip = instance.base_strip.clone();
 
-        Self {
+        //make sure the strip is long enough to contain all fixed items
+        let fixed_bboxes = instance.fixed_placements.iter().map(|p| {
+            let item = instance.item(p.item_id);
+            item.shape_cd.transform_clone(&p.d_transf.compose()).bbox
+        });
+        if let Some(min_width) = strip.fitted_width(fixed_bboxes)
+            && min_width > strip.width
+        {
+            strip.set_width(min_width);
+        }
+
+        let layout = Layout::new((&strip).into());
+        let mut problem = Self {
             instance,
             strip,
             layout,
             item_demand_qtys,
+        };
+
+        for p in problem.instance.fixed_placements.clone() {
+            problem.register_included_item(p.item_id);
+            let item = problem.instance.item(p.item_id);
+            problem.layout.place_fixed_item(item, p.d_transf);
         }
+
+        problem
     }
 
-    /// Modifies the width of the strip in the back, keeping the front fixed.
+    /// Returns the number of copies of the item which still have to be placed to meet its minimum demand
+    pub fn item_min_demand_qty(&self, item_id: usize) -> usize {
+        let placed = self.instance.item_qty(item_id) - self.item_demand_qtys[item_id];
+        self.instance.item_min_qty(item_id).saturating_sub(placed)
+    }
+
+    /// Whether the minimum demand of every item is met
+    pub fn min_demands_met(&self) -> bool {
+        (0..self.instance.items.len()).all(|id| self.item_min_demand_qty(id) == 0)
+    }
+
+    /// Modifies the width (length along its direction) of the strip in the back, keeping the front fixed.
     pub fn change_strip_width(&mut self, new_width: f32) {
         self.strip.set_width(new_width);
-        self.layout.swap_container(self.strip.into());
+        self.layout.swap_container((&self.strip).into());
     }
 
-    /// Shrinks the strip to the minimum width that fits all items.
+    /// Shrinks the strip to the minimum width (length along its direction) that fits all items.
+    /// Does nothing if no items are placed.
     pub fn fit_strip(&mut self) {
         let feasible_before = self.layout.is_feasible();
 
-        //Find the rightmost item in the strip and add some tolerance (avoiding false collision positives)
-        let item_x_max = self
-            .layout
-            .placed_items
-            .values()
-            .map(|pi| pi.shape.bbox.x_max)
-            .max_by(|a, b| a.partial_cmp(b).unwrap())
-            .unwrap()
-            * 1.00001;
-
-        // add the shape offset if any, the strip needs to be at least `offset` wider than the items
-        let fitted_width = item_x_max + self.strip.shape_modify_config.offset.unwrap_or(0.0);
-
-        self.change_strip_width(fitted_width);
+        let item_bboxes = self.layout.placed_items.values().map(|pi| pi.shape.bbox);
+        if let Some(fitted_width) = self.strip.fitted_width(item_bboxes) {
+            self.change_strip_width(fitted_width);
+        }
         debug_assert!(feasible_before == self.layout.is_feasible());
     }
 
@@ -66,7 +89,12 @@ impl SPProblem {
 
     /// Removes a placed item from the strip. Returns the placement of the item.
     /// Set `commit_instantly` to false if there's a high chance that this modification will be reverted.
+    /// Panics if the item is fixed.
     pub fn remove_item(&mut self, pkey: PItemKey) -> SPPlacement {
+        assert!(
+            !self.layout.placed_items[pkey].fixed,
+            "fixed items cannot be removed"
+        );
         let pi = self.layout.remove_item(pkey);
         self.deregister_included_item(pi.item_id);
 
@@ -80,7 +108,7 @@ impl SPProblem {
     pub fn save(&self) -> SPSolution {
         let solution = SPSolution {
             layout_snapshot: self.layout.save(),
-            strip: self.strip,
+            strip: self.strip.clone(),
             time_stamp: Instant::now(),
         };
 
@@ -97,7 +125,7 @@ impl SPProblem {
         } else {
             // the strip has changed, rebuild the layout
             self.layout = Layout::from_snapshot(&solution.layout_snapshot);
-            self.strip = solution.strip;
+            self.strip = solution.strip.clone();
         }
 
         //Restore the item demands
@@ -138,3 +166,168 @@ pub struct SPPlacement {
     pub item_id: usize,
     pub d_transf: DTransformation,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::shape_modification::ShapeModifyConfig;
+    use crate::probs::spp::entities::StripDirection;
+    use crate::util::test_util::{cde_config, item};
+
+    /// Strip of 50 high and 100 wide, with a copy of item 1 fixed beyond its initial width
+    fn instance(fixed: bool) -> SPInstance {
+        let mut strip = Strip::new(50.0, cde_config(), ShapeModifyConfig::default()).unwrap();
+        strip.set_width(100.0);
+        let fixed_placements = match fixed {
+            true => vec![SPPlacement {
+                item_id: 1,
+                d_transf: DTransformation::new(0.0, (120.0, 10.0)),
+            }],
+            false => vec![],
+        };
+        SPInstance::new(
+            vec![(item(0, 10.0, 10.0), 2), (item(1, 20.0, 20.0), 2)],
+            strip,
+            fixed_placements,
+        )
+    }
+
+    fn fixed_pkey(problem: &SPProblem) -> PItemKey {
+        let mut fixed = problem
+            .layout
+            .placed_items
+            .iter()
+            .filter(|(_, pi)| pi.fixed);
+        let (pk, _) = fixed.next().unwrap();
+        assert!(fixed.next().is_none());
+        pk
+    }
+
+    #[test]
+    fn fixed_items_placed_and_counted() {
+        let problem = SPProblem::new(instance(true));
+        let pk = fixed_pkey(&problem);
+        assert_eq!(problem.layout.placed_items[pk].item_id, 1);
+        assert_eq!(problem.item_demand_qtys, vec![2, 1]);
+
+        //the strip is extended to contain the fixed item
+        assert!(problem.strip_width() >= 140.0);
+        assert!(problem.layout.is_feasible());
+    }
+
+    #[test]
+    #[should_panic(expected = "fixed items cannot be removed")]
+    fn fixed_items_not_removable() {
+        let mut problem = SPProblem::new(instance(true));
+        let pk = fixed_pkey(&problem);
+        problem.remove_item(pk);
+    }
+
+    #[test]
+    fn fixed_items_survive_restore() {
+        let mut problem = SPProblem::new(instance(true));
+        let initial = problem.save();
+
+        let pk = problem.place_item(SPPlacement {
+            item_id: 0,
+            d_transf: DTransformation::new(0.0, (10.0, 10.0)),
+        });
+        problem.fit_strip();
+        let fitted = problem.save();
+
+        //restore with the same strip
+        problem.remove_item(pk);
+        problem.restore(&fitted);
+        assert_eq!(problem.item_demand_qtys, vec![1, 1]);
+        assert!(problem.layout.placed_items[fixed_pkey(&problem)].fixed);
+
+        //restore with a different strip, rebuilding the layout
+        problem.change_strip_width(200.0);
+        problem.restore(&initial);
+        assert_eq!(problem.item_demand_qtys, vec![2, 1]);
+        assert!(problem.layout.placed_items[fixed_pkey(&problem)].fixed);
+    }
+
+    #[test]
+    fn fit_strip() {
+        let mut problem = SPProblem::new(instance(true));
+        problem.change_strip_width(500.0);
+        problem.fit_strip();
+        //the fixed item is the furthest one
+        let width = problem.strip_width();
+        assert!((140.0..140.01).contains(&width), "{width}");
+
+        //nothing to fit
+        let mut problem = SPProblem::new(instance(false));
+        problem.fit_strip();
+        assert_eq!(problem.strip_width(), 100.0);
+    }
+
+    #[test]
+    fn vertical_strip() {
+        //50 wide across, growing along the y-axis
+        let mut strip = Strip::new(50.0, cde_config(), ShapeModifyConfig::default())
+            .unwrap()
+            .with_direction(StripDirection::Vertical);
+        strip.set_width(100.0);
+        let instance = SPInstance::new(vec![(item(0, 10.0, 10.0), 2)], strip, vec![]);
+        let mut problem = SPProblem::new(instance);
+        let bbox = problem.layout.container.outer_orig.shape.bbox;
+        assert_eq!((bbox.x_max, bbox.y_max), (50.0, 100.0));
+
+        let pk = problem.place_item(SPPlacement {
+            item_id: 0,
+            d_transf: DTransformation::new(0.0, (35.0, 60.0)),
+        });
+        problem.fit_strip();
+        let width = problem.strip_width();
+        assert!((70.0..70.01).contains(&width), "{width}");
+        assert_eq!(problem.strip.dimensions(), (50.0, width));
+        assert!(problem.layout.is_feasible());
+
+        //the breadth of the strip is bounded along the x-axis
+        problem.remove_item(pk);
+        problem.place_item(SPPlacement {
+            item_id: 0,
+            d_transf: DTransformation::new(0.0, (45.0, 10.0)),
+        });
+        assert!(!problem.layout.is_feasible());
+    }
+
+    #[test]
+    fn min_demands() {
+        //only a single copy of item 0 is mandatory
+        let optional = instance(false).with_min_demands(vec![1, 0]);
+        let mut problem = SPProblem::new(optional.clone());
+        assert_eq!(problem.item_min_demand_qty(0), 1);
+        assert!(!problem.min_demands_met());
+        assert!(!problem.save().min_demands_met(&optional));
+
+        problem.place_item(SPPlacement {
+            item_id: 0,
+            d_transf: DTransformation::new(0.0, (10.0, 10.0)),
+        });
+        assert!(problem.min_demands_met());
+        assert!(problem.save().min_demands_met(&optional));
+
+        //optional copies can still be placed up to the maximum demand
+        problem.place_item(SPPlacement {
+            item_id: 0,
+            d_transf: DTransformation::new(0.0, (30.0, 10.0)),
+        });
+        assert_eq!(problem.item_min_demand_qty(0), 0);
+        assert_eq!(problem.item_demand_qtys, vec![0, 2]);
+
+        //fixed items count towards the minimum demand
+        let problem = SPProblem::new(instance(true).with_min_demands(vec![0, 1]));
+        assert!(problem.min_demands_met());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Minimum demands should be defined for every item and not exceed its demand"
+    )]
+    fn min_demands_exceeding_demand_rejected() {
+        instance(false).with_min_demands(vec![3, 0]);
+    }
+}
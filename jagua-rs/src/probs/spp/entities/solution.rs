@@ -1,9 +1,13 @@
 use crate::Instant;
-use crate::entities::LayoutSnapshot;
+use crate::entities::{LayoutSnapshot, LayoutSnapshotData};
 use crate::probs::spp::entities::SPInstance;
 use crate::probs::spp::entities::strip::Strip;
+use anyhow::ensure;
+use serde::de::{DeserializeSeed, Error};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Snapshot of [`SPProblem`](crate::probs::spp::entities::SPProblem) at a specific moment. Can be used to restore to a previous state.
+/// Serializes without its time stamp, deserialize using [`SPSolutionSeed`].
 #[derive(Debug, Clone)]
 pub struct SPSolution {
     pub strip: Strip,
@@ -16,7 +20,145 @@ impl SPSolution {
     pub fn density(&self, instance: &SPInstance) -> f32 {
         self.layout_snapshot.density(instance)
     }
+    /// Whether the minimum demand of every item is met
+    pub fn min_demands_met(&self, instance: &SPInstance) -> bool {
+        let mut placed = vec![0; instance.items.len()];
+        self.layout_snapshot
+            .placed_items
+            .values()
+            .for_each(|pi| placed[pi.item_id] += 1);
+        placed
+            .into_iter()
+            .enumerate()
+            .all(|(id, qty)| qty >= instance.item_min_qty(id))
+    }
+
     pub fn strip_width(&self) -> f32 {
         self.strip.width
     }
 }
+
+impl Serialize for SPSolution {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct SPSolutionRef<'a> {
+            strip: &'a Strip,
+            layout_snapshot: &'a LayoutSnapshot,
+        }
+        SPSolutionRef {
+            strip: &self.strip,
+            layout_snapshot: &self.layout_snapshot,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a [`SPSolution`] and rebuilds it against the (imported) instance it belongs to.
+/// The time stamp of the solution is set to the moment of deserialization.
+pub struct SPSolutionSeed<'a>(pub &'a SPInstance);
+
+impl<'de> DeserializeSeed<'de> for SPSolutionSeed<'_> {
+    type Value = SPSolution;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        #[derive(Deserialize)]
+        struct SPSolutionData {
+            strip: Strip,
+            layout_snapshot: LayoutSnapshotData,
+        }
+        let SPSolutionData {
+            strip,
+            layout_snapshot,
+        } = SPSolutionData::deserialize(deserializer)?;
+        let instance = self.0;
+
+        let rebuild = || {
+            //only the width of the strip is part of the solution, the rest is defined by the instance
+            ensure!(
+                Strip {
+                    width: instance.base_strip.width,
+                    ..strip.clone()
+                } == instance.base_strip,
+                "strip does not match the instance, only its width can differ"
+            );
+            LayoutSnapshot::rebuild(layout_snapshot, (&strip).into(), instance)
+        };
+        let layout_snapshot = rebuild().map_err(|e| D::Error::custom(format!("{e:#}")))?;
+
+        Ok(SPSolution {
+            strip,
+            layout_snapshot,
+            time_stamp: Instant::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::DTransformation;
+    use crate::geometry::shape_modification::ShapeModifyConfig;
+    use crate::probs::spp::entities::{SPPlacement, SPProblem, StripDefect};
+    use crate::util::test_util::{cde_config, item, rect};
+    use serde_json::{Value, json};
+
+    /// Strip of 50 high and 100 wide with a hole, and a single item placed next to it
+    fn problem() -> SPProblem {
+        let hole = StripDefect {
+            quality: 0,
+            shape: rect(20.0, 20.0, 30.0, 30.0).into(),
+        };
+        let mut strip = Strip::new(50.0, cde_config(), ShapeModifyConfig::default())
+            .unwrap()
+            .with_defects(vec![hole]);
+        strip.set_width(100.0);
+        let mut problem = SPProblem::new(SPInstance::new(
+            vec![(item(0, 10.0, 10.0), 1)],
+            strip,
+            vec![],
+        ));
+        problem.place_item(SPPlacement {
+            item_id: 0,
+            d_transf: DTransformation::new(0.0, (0.0, 0.0)),
+        });
+        problem
+    }
+
+    fn deserialize(problem: &SPProblem, json: Value) -> serde_json::Result<SPSolution> {
+        SPSolutionSeed(&problem.instance).deserialize(json)
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut problem = problem();
+        problem.change_strip_width(80.0);
+        let json = serde_json::to_value(problem.save()).unwrap();
+        let solution = deserialize(&problem, json).unwrap();
+        assert_eq!(solution.strip_width(), 80.0);
+        assert_eq!(solution.strip.defects, problem.instance.base_strip.defects);
+        assert_eq!(solution.layout_snapshot.placed_items.len(), 1);
+    }
+
+    #[test]
+    fn tampered_strip_rejected() {
+        let problem = problem();
+        let json = serde_json::to_value(problem.save()).unwrap();
+
+        //the hole is removed from the file
+        let mut tampered = json.clone();
+        tampered["strip"]["defects"] = json!([]);
+        let err = deserialize(&problem, tampered).unwrap_err();
+        assert!(
+            err.to_string().contains("only its width can differ"),
+            "{err}"
+        );
+
+        let mut tampered = json;
+        tampered["strip"]["fixed_height"] = json!(60.0);
+        let err = deserialize(&problem, tampered).unwrap_err();
+        assert!(
+            err.to_string().contains("only its width can differ"),
+            "{err}"
+        );
+    }
+}
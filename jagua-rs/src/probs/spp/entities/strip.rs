@@ -1,17 +1,32 @@
 use crate::collision_detection::CDEConfig;
-use crate::entities::Container;
-use crate::geometry::primitives::{Rect, SPolygon};
+use crate::entities::{Container, InferiorQualityZone, RemnantAxis};
+use crate::geometry::primitives::{Point, Rect, SPolygon};
 use crate::geometry::shape_modification::{ShapeModifyConfig, ShapeModifyMode};
 use crate::geometry::{DTransformation, OriginalShape};
 use anyhow::{Result, ensure};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Copy, PartialEq)]
-/// Represents a rectangular container with fixed height and variable width.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// Represents a container with a fixed dimension and a variable length along its direction.
+/// Despite their names, `fixed_height` and `width` follow the [`StripDirection`]:
+/// for a vertical strip, `fixed_height` is the fixed width and `width` the variable height.
 pub struct Strip {
+    /// Fixed dimension of the strip, perpendicular to its direction
     pub fixed_height: f32,
     pub cde_config: CDEConfig,
     pub shape_modify_config: ShapeModifyConfig,
+    /// Variable length of the strip, along its direction
     pub width: f32,
+    /// Axis along which the strip grows
+    #[serde(default)]
+    pub direction: StripDirection,
+    /// Defects and holes at fixed positions in the strip
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub defects: Vec<StripDefect>,
+    /// Irregular far edge of the strip, replacing the straight edge at `fixed_height`
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub edge_profile: Option<EdgeProfile>,
 }
 
 impl Strip {
@@ -26,27 +41,301 @@ impl Strip {
             cde_config,
             shape_modify_config,
             width: 0.0,
+            direction: StripDirection::default(),
+            defects: vec![],
+            edge_profile: None,
         })
     }
 
+    pub fn with_direction(mut self, direction: StripDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn with_defects(mut self, defects: Vec<StripDefect>) -> Self {
+        self.defects = defects;
+        self
+    }
+
+    pub fn with_edge_profile(mut self, edge_profile: EdgeProfile) -> Self {
+        self.edge_profile = Some(edge_profile);
+        self
+    }
+
     pub fn set_width(&mut self, width: f32) {
         self.width = width;
     }
+
+    /// Furthest extent of `bbox` along the direction of the strip
+    pub fn extent_of(&self, bbox: Rect) -> f32 {
+        match self.direction {
+            StripDirection::Horizontal => bbox.x_max,
+            StripDirection::Vertical => bbox.y_max,
+        }
+    }
+
+    /// Minimum width of the strip to contain shapes with the given bounding boxes, `None` if there are none.
+    /// Includes a small tolerance (avoiding false collision positives) and the shape offset (if any).
+    pub fn fitted_width(&self, bboxes: impl IntoIterator<Item = Rect>) -> Option<f32> {
+        let extent = bboxes
+            .into_iter()
+            .map(|bbox| self.extent_of(bbox))
+            .max_by(|a, b| a.partial_cmp(b).unwrap())?;
+        // the strip needs to be at least `offset` longer than the shapes
+        Some(extent * crate::probs::FIT_TOLERANCE + self.shape_modify_config.offset.unwrap_or(0.0))
+    }
+
+    /// Dimensions of the bounding box of the strip along the x- and y-axis
+    pub fn dimensions(&self) -> (f32, f32) {
+        match self.direction {
+            StripDirection::Horizontal => (self.width, self.fixed_height),
+            StripDirection::Vertical => (self.fixed_height, self.width),
+        }
+    }
+
+    /// Contour of the strip at its current width
+    fn outer(&self) -> SPolygon {
+        match &self.edge_profile {
+            None => {
+                let (x_max, y_max) = self.dimensions();
+                SPolygon::from(Rect::try_new(0.0, 0.0, x_max, y_max).unwrap())
+            }
+            Some(profile) => {
+                //points as (position along the strip, position across the strip)
+                let w = self.width;
+                let far_edge = profile
+                    .points
+                    .iter()
+                    .rev()
+                    .filter(|Point(along, _)| *along > 0.0 && *along < w)
+                    .copied();
+                let points = [
+                    Point(0.0, 0.0),
+                    Point(w, 0.0),
+                    Point(w, profile.breadth_at(w)),
+                ]
+                .into_iter()
+                .chain(far_edge)
+                .chain([Point(0.0, profile.breadth_at(0.0))])
+                .map(|Point(along, across)| match self.direction {
+                    StripDirection::Horizontal => Point(along, across),
+                    StripDirection::Vertical => Point(across, along),
+                })
+                .collect_vec();
+                SPolygon::new(points).expect("edge profile should result in a valid strip")
+            }
+        }
+    }
 }
 
-impl From<Strip> for Container {
-    fn from(s: Strip) -> Container {
+impl From<&Strip> for Container {
+    fn from(s: &Strip) -> Container {
+        let outer = s.outer();
+
+        //only the defects which (partially) lie within the current width of the strip
+        let quality_zones = s
+            .defects
+            .iter()
+            .filter(|d| match s.direction {
+                StripDirection::Horizontal => d.shape.bbox.x_min < outer.bbox.x_max,
+                StripDirection::Vertical => d.shape.bbox.y_min < outer.bbox.y_max,
+            })
+            .into_group_map_by(|d| d.quality)
+            .into_iter()
+            .map(|(quality, defects)| {
+                let shapes = defects
+                    .into_iter()
+                    .map(|d| OriginalShape {
+                        shape: d.shape.clone(),
+                        pre_transform: DTransformation::empty(),
+                        modify_mode: ShapeModifyMode::Inflate,
+                        modify_config: s.shape_modify_config,
+                    })
+                    .collect_vec();
+                InferiorQualityZone::new(quality, shapes).unwrap()
+            })
+            .collect_vec();
+
         Container::new(
             0,
             OriginalShape {
-                shape: SPolygon::from(Rect::try_new(0.0, 0.0, s.width, s.fixed_height).unwrap()),
+                shape: outer,
                 pre_transform: DTransformation::empty(),
                 modify_mode: ShapeModifyMode::Deflate,
                 modify_config: s.shape_modify_config,
             },
+            quality_zones,
+            vec![],
             vec![],
             s.cde_config,
         )
         .unwrap()
     }
 }
+
+/// Axis along which a [`Strip`] grows
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StripDirection {
+    /// Fixed height, variable width (along the x-axis)
+    #[default]
+    Horizontal,
+    /// Fixed width, variable height (along the y-axis)
+    Vertical,
+}
+
+impl From<StripDirection> for RemnantAxis {
+    fn from(direction: StripDirection) -> Self {
+        match direction {
+            StripDirection::Horizontal => RemnantAxis::X,
+            StripDirection::Vertical => RemnantAxis::Y,
+        }
+    }
+}
+
+/// A defect at a fixed position in a [`Strip`], modelled as an inferior quality zone.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StripDefect {
+    /// Quality of the material in the defect. A defect with quality 0 is a hole.
+    pub quality: usize,
+    pub shape: SPolygon,
+}
+
+impl PartialEq for StripDefect {
+    fn eq(&self, other: &Self) -> bool {
+        self.quality == other.quality && self.shape.vertices == other.shape.vertices
+    }
+}
+
+/// Profile of the far edge of a [`Strip`]: the breadth of the strip (across its direction) at positions along its length.
+/// Linearly interpolated between the points and constant beyond the first and last one.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EdgeProfile {
+    /// Points as (position along the strip, breadth), sorted by position
+    pub points: Vec<Point>,
+}
+
+impl EdgeProfile {
+    pub fn new(points: Vec<Point>) -> Result<Self> {
+        ensure!(!points.is_empty(), "edge profile should contain points");
+        ensure!(
+            points.windows(2).all(|w| w[0].0 < w[1].0),
+            "edge profile points should be strictly increasing along the strip"
+        );
+        ensure!(
+            points.iter().all(|Point(_, breadth)| *breadth > 0.0),
+            "edge profile should have a positive breadth"
+        );
+        Ok(EdgeProfile { points })
+    }
+
+    /// Breadth of the strip at position `along`
+    pub fn breadth_at(&self, along: f32) -> f32 {
+        let first = self.points[0];
+        let last = self.points[self.points.len() - 1];
+        if along <= first.0 {
+            first.1
+        } else if along >= last.0 {
+            last.1
+        } else {
+            let (Point(x0, y0), Point(x1, y1)) = self
+                .points
+                .iter()
+                .tuple_windows()
+                .find(|(_, p1)| along <= p1.0)
+                .map(|(p0, p1)| (*p0, *p1))
+                .unwrap();
+            y0 + (y1 - y0) * (along - x0) / (x1 - x0)
+        }
+    }
+
+    /// Largest breadth of the strip
+    pub fn max_breadth(&self) -> f32 {
+        self.points.iter().map(|p| p.1).fold(0.0, f32::max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::Layout;
+    use crate::io::import::InfeasiblePlacement;
+    use crate::util::test_util::{cde_config, item, rect};
+
+    /// Strip of 50 high and 100 wide, with a hole at x 20..30 and a defect of quality 1 at x 60..70
+    fn strip_with_defects() -> Strip {
+        let defect = |quality: usize, x_min: f32| StripDefect {
+            quality,
+            shape: rect(x_min, 20.0, x_min + 10.0, 30.0).into(),
+        };
+        let mut strip = Strip::new(50.0, cde_config(), ShapeModifyConfig::default())
+            .unwrap()
+            .with_defects(vec![defect(0, 20.0), defect(1, 60.0)]);
+        strip.set_width(100.0);
+        strip
+    }
+
+    /// Whether a 10x10 item with its bottom left corner at `pos` can be placed in the empty strip
+    fn fits(strip: &Strip, min_quality: Option<usize>, pos: (f32, f32)) -> bool {
+        let mut item = item(0, 10.0, 10.0);
+        item.min_quality = min_quality;
+        let layout = Layout::new(Container::from(strip));
+        InfeasiblePlacement::check(&layout, &item, DTransformation::new(0.0, pos), 0, None)
+            .is_none()
+    }
+
+    #[test]
+    fn defects_stay_empty() {
+        let strip = strip_with_defects();
+        assert!(fits(&strip, None, (5.0, 5.0)));
+
+        //holes are forbidden for all items
+        assert!(!fits(&strip, None, (22.0, 22.0)));
+        assert!(!fits(&strip, Some(1), (22.0, 22.0)));
+
+        //the defect of quality 1 only allows items which accept it
+        assert!(!fits(&strip, None, (62.0, 22.0)));
+        assert!(fits(&strip, Some(1), (62.0, 22.0)));
+    }
+
+    #[test]
+    fn defects_follow_strip_width() {
+        let mut strip = strip_with_defects();
+        let container = Container::from(&strip);
+        assert_eq!(container.n_qualities(), 2);
+        assert!(container.quality_zones.iter().all(|qz| qz.is_some()));
+
+        //the defect beyond the width of the strip is left out, and is back once the strip is extended
+        strip.set_width(50.0);
+        let container = Container::from(&strip);
+        assert_eq!(container.n_qualities(), 1);
+        assert!(container.quality_zones[0].is_some());
+        strip.set_width(100.0);
+        assert!(!fits(&strip, None, (62.0, 22.0)));
+    }
+
+    #[test]
+    fn edge_profile() {
+        assert!(EdgeProfile::new(vec![]).is_err());
+        assert!(EdgeProfile::new(vec![Point(10.0, 50.0), Point(0.0, 40.0)]).is_err());
+        assert!(EdgeProfile::new(vec![Point(0.0, 0.0)]).is_err());
+
+        //the strip narrows from 50 to 30 along the first 100 units
+        let profile = EdgeProfile::new(vec![Point(0.0, 50.0), Point(100.0, 30.0)]).unwrap();
+        assert_eq!(profile.breadth_at(-10.0), 50.0);
+        assert_eq!(profile.breadth_at(50.0), 40.0);
+        assert_eq!(profile.breadth_at(200.0), 30.0);
+        assert_eq!(profile.max_breadth(), 50.0);
+
+        let mut strip = Strip::new(50.0, cde_config(), ShapeModifyConfig::default())
+            .unwrap()
+            .with_edge_profile(profile);
+        strip.set_width(150.0);
+        assert_eq!(strip.outer().vertices.len(), 5);
+
+        //an item fits under the edge at the front, but not at the back
+        assert!(fits(&strip, None, (5.0, 35.0)));
+        assert!(!fits(&strip, None, (130.0, 25.0)));
+        assert!(fits(&strip, None, (130.0, 15.0)));
+    }
+}
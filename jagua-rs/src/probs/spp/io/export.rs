@@ -5,8 +5,10 @@ use crate::probs::spp::io::ext_repr::ExtSPSolution;
 
 /// Exports a solution out of the library
 pub fn export(instance: &SPInstance, solution: &SPSolution, epoch: Instant) -> ExtSPSolution {
+    let (strip_width, strip_height) = solution.strip.dimensions();
     ExtSPSolution {
-        strip_width: solution.strip.width,
+        strip_width,
+        strip_height,
         layout: export_layout_snapshot(&solution.layout_snapshot, instance),
         density: solution.density(instance),
         run_time_sec: solution.time_stamp.duration_since(epoch).as_secs(),
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::io::ext_repr::ExtLayout;
+use crate::io::ext_repr::{ExtLayout, ExtPlacedItem, ExtQualityZone};
 
 /// Strip Packing Problem instance
 #[derive(Serialize, Deserialize, Clone)]
@@ -9,18 +9,36 @@ pub struct ExtSPInstance {
     pub name: String,
     /// Set of items to be produced
     pub items: Vec<ExtItem>,
-    /// Fixed height of the strip
-    pub strip_height: f32,
+    /// Fixed height of the strip, which then grows along the x-axis
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub strip_height: Option<f32>,
+    /// Fixed width of the strip, which then grows along the y-axis. Mutually exclusive with `strip_height`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub strip_width: Option<f32>,
+    /// Defects at fixed positions in the strip, zones with quality 0 are holes
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub defects: Vec<ExtQualityZone>,
+    /// Irregular far edge of the strip, as (position along the strip, breadth) points.
+    /// Linearly interpolated between the points and constant beyond the first and last one.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub edge_profile: Option<Vec<(f32, f32)>>,
+    /// Items which are already placed in the strip and cannot be moved, they count towards the demand of their item
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub fixed_items: Vec<ExtPlacedItem>,
 }
 
-/// Item with a demand
+/// Item with a (minimum and maximum) demand
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ExtItem {
     #[serde(flatten)]
     /// External representation of the item in the base library
     pub base: crate::io::ext_repr::ExtItem,
-    /// Amount of times this item has to be produced
+    /// Maximum amount of times this item can be produced, all of them mandatory unless `min_demand` is specified
+    #[serde(alias = "max_demand")]
     pub demand: u64,
+    /// Amount of times this item has to be produced at least, equal to `demand` if not specified
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub min_demand: Option<u64>,
 }
 
 /// Strip Packing Problem solution
@@ -28,6 +46,9 @@ pub struct ExtItem {
 pub struct ExtSPSolution {
     /// The strip width of the solution
     pub strip_width: f32,
+    /// The strip height of the solution
+    #[serde(default)]
+    pub strip_height: f32,
     /// Layouts which compose the solution
     pub layout: ExtLayout,
     /// Sum of the area of the produced items divided by the sum of the area of the containers
@@ -1,9 +1,17 @@
+use crate::entities::Instance;
 use crate::entities::Item;
+use crate::geometry::primitives::Point;
 use crate::geometry::shape_modification::ShapeModifyConfig;
-use crate::io::import::Importer;
-use crate::probs::spp::entities::{SPInstance, Strip};
-use crate::probs::spp::io::ext_repr::ExtSPInstance;
-use anyhow::{Result, ensure};
+use crate::io::import::import_simple_shape;
+use crate::io::import::{
+    Importer, InfeasiblePlacement, ext_to_int_transformation, match_fixed_placements,
+    placement_in_bounds,
+};
+use crate::probs::spp::entities::{
+    EdgeProfile, SPInstance, SPPlacement, SPProblem, Strip, StripDefect, StripDirection,
+};
+use crate::probs::spp::io::ext_repr::{ExtSPInstance, ExtSPSolution};
+use anyhow::{Result, bail, ensure};
 use itertools::Itertools;
 use rayon::prelude::*;
 
@@ -29,12 +37,100 @@ pub fn import(importer: &Importer, ext_instance: &ExtSPInstance) -> Result<SPIns
         items
     };
 
-    let total_item_area = items
+    let fixed_placements = ext_instance
+        .fixed_items
         .iter()
-        .map(|(item, demand)| item.area() * *demand as f32)
-        .sum::<f32>();
+        .map(|ext_pi| {
+            let item_id = ext_pi.item_id as usize;
+            ensure!(
+                item_id < items.len(),
+                "fixed item refers to unknown item {item_id}"
+            );
+            let item = &items[item_id].0;
+            let d_transf = ext_to_int_transformation(
+                &(&ext_pi.transformation).into(),
+                &item.shape_orig.pre_transform,
+            );
+            Ok(SPPlacement { item_id, d_transf })
+        })
+        .collect::<Result<Vec<SPPlacement>>>()?;
 
-    let fixed_height = ext_instance.strip_height;
+    for (item_id, (_, demand)) in items.iter().enumerate() {
+        let n_fixed = fixed_placements
+            .iter()
+            .filter(|p| p.item_id == item_id)
+            .count();
+        ensure!(
+            n_fixed <= *demand,
+            "item {item_id} has {n_fixed} fixed placements, exceeding its demand of {demand}"
+        );
+    }
+
+    let min_demands = ext_instance
+        .items
+        .iter()
+        .sorted_by_key(|ext_item| ext_item.base.id)
+        .map(|ext_item| {
+            let min_demand = ext_item.min_demand.unwrap_or(ext_item.demand);
+            ensure!(
+                min_demand <= ext_item.demand,
+                "item {} has a minimum demand of {min_demand}, exceeding its demand of {}",
+                ext_item.base.id,
+                ext_item.demand
+            );
+            Ok(min_demand as usize)
+        })
+        .collect::<Result<Vec<usize>>>()?;
+
+    //only the mandatory items are taken into account, unless all of them are optional
+    let total_item_area = {
+        let mandatory_area = items
+            .iter()
+            .zip(min_demands.iter())
+            .map(|((item, _), min_demand)| item.area() * *min_demand as f32)
+            .sum::<f32>();
+        match mandatory_area > 0.0 {
+            true => mandatory_area,
+            false => items
+                .iter()
+                .map(|(item, demand)| item.area() * *demand as f32)
+                .sum::<f32>(),
+        }
+    };
+
+    let (fixed_height, direction) = match (ext_instance.strip_height, ext_instance.strip_width) {
+        (Some(height), None) => (height, StripDirection::Horizontal),
+        (None, Some(width)) => (width, StripDirection::Vertical),
+        _ => bail!("exactly one of strip_height and strip_width should be defined"),
+    };
+    ensure!(
+        fixed_height > 0.0,
+        "fixed dimension of the strip must be positive"
+    );
+
+    let defects = ext_instance
+        .defects
+        .iter()
+        .map(|zone| {
+            let shape = import_simple_shape(&zone.shape, "strip defects")?;
+            Ok(StripDefect {
+                quality: zone.quality,
+                shape,
+            })
+        })
+        .collect::<Result<Vec<StripDefect>>>()?;
+
+    let edge_profile = match &ext_instance.edge_profile {
+        Some(points) => {
+            let profile = EdgeProfile::new(points.iter().map(|(a, b)| Point(*a, *b)).collect())?;
+            ensure!(
+                profile.max_breadth() <= fixed_height,
+                "edge profile exceeds the fixed dimension of the strip"
+            );
+            Some(profile)
+        }
+        None => None,
+    };
 
     // Initialize the base width for 100% density
     let width = total_item_area / fixed_height;
@@ -48,7 +144,189 @@ pub fn import(importer: &Importer, ext_instance: &ExtSPInstance) -> Result<SPIns
             narrow_concavity_cutoff_ratio: None,
         },
         width,
+        direction,
+        defects,
+        edge_profile,
+    };
+
+    Ok(SPInstance::new(items, base_strip, fixed_placements).with_min_demands(min_demands))
+}
+
+/// Imports a solution of the instance into a [`SPProblem`], e.g. to warm-start an optimizer or to validate a solution produced elsewhere.
+/// Fixed items are placed by the problem itself: placements marked as fixed have to match them exactly, and none of them may be missing.
+/// Items lying outside the bounds of the strip are not placed.
+/// Returns the problem together with all placements which are infeasible.
+pub fn import_solution(
+    instance: &SPInstance,
+    ext_solution: &ExtSPSolution,
+) -> Result<(SPProblem, Vec<InfeasiblePlacement>)> {
+    let mut problem = SPProblem::new(instance.clone());
+
+    let strip_length = match instance.base_strip.direction {
+        StripDirection::Horizontal => ext_solution.strip_width,
+        StripDirection::Vertical => ext_solution.strip_height,
     };
 
-    Ok(SPInstance::new(items, base_strip))
+    //at this point, the layout only contains the fixed items
+    let fixed_extent = problem
+        .layout
+        .placed_items
+        .values()
+        .map(|pi| problem.strip.extent_of(pi.shape.bbox))
+        .fold(0.0, f32::max);
+    ensure!(
+        strip_length > 0.0,
+        "strip length of the solution must be positive"
+    );
+    ensure!(
+        strip_length >= fixed_extent,
+        "strip length of the solution ({strip_length}) is too short to contain the fixed items ({fixed_extent})"
+    );
+    problem.change_strip_width(strip_length);
+    match_fixed_placements(instance, &problem.layout, &ext_solution.layout.placed_items)?;
+
+    let mut placements = vec![];
+    for (index, ext_pi) in ext_solution.layout.placed_items.iter().enumerate() {
+        if ext_pi.fixed {
+            continue;
+        }
+        let item_id = ext_pi.item_id as usize;
+        ensure!(
+            item_id < instance.items.len(),
+            "placement {index} refers to unknown item {item_id}"
+        );
+        if problem.item_demand_qtys[item_id] == 0 {
+            bail!("placement {index}: item {item_id} is placed more often than its demand");
+        }
+        let item = instance.item(item_id);
+        let d_transf = ext_to_int_transformation(
+            &(&ext_pi.transformation).into(),
+            &item.shape_orig.pre_transform,
+        );
+        let pk = match placement_in_bounds(problem.layout.cde(), item, d_transf) {
+            true => Some(problem.place_item(SPPlacement { item_id, d_transf })),
+            false => None,
+        };
+        placements.push((index, item_id, d_transf, pk));
+    }
+
+    let infeasible = placements
+        .into_iter()
+        .filter_map(|(index, item_id, d_transf, pk)| {
+            InfeasiblePlacement::check(&problem.layout, instance.item(item_id), d_transf, index, pk)
+        })
+        .collect_vec();
+
+    Ok((problem, infeasible))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Instant;
+    use crate::geometry::DTransformation;
+    use crate::io::ext_repr::ExtPlacedItem;
+    use crate::probs::spp::io::export::export;
+    use crate::util::test_util::{cde_config, item};
+    use serde_json::json;
+
+    /// Exported solution of a strip with a fixed copy of item 1 and a regular copy of item 0
+    fn instance_and_solution() -> (SPInstance, ExtSPSolution) {
+        let mut strip = Strip::new(50.0, cde_config(), ShapeModifyConfig::default()).unwrap();
+        strip.set_width(100.0);
+        let instance = SPInstance::new(
+            vec![(item(0, 10.0, 10.0), 2), (item(1, 20.0, 20.0), 2)],
+            strip,
+            vec![SPPlacement {
+                item_id: 1,
+                d_transf: DTransformation::new(0.0, (60.0, 10.0)),
+            }],
+        );
+        let mut problem = SPProblem::new(instance.clone());
+        problem.place_item(SPPlacement {
+            item_id: 0,
+            d_transf: DTransformation::new(0.0, (20.0, 20.0)),
+        });
+        let solution = export(&instance, &problem.save(), Instant::now());
+        (instance, solution)
+    }
+
+    fn fixed_entry(solution: &mut ExtSPSolution) -> &mut ExtPlacedItem {
+        solution
+            .layout
+            .placed_items
+            .iter_mut()
+            .find(|pi| pi.fixed)
+            .unwrap()
+    }
+
+    #[test]
+    fn fixed_placements_matched() {
+        let (instance, solution) = instance_and_solution();
+        let (problem, infeasible) = import_solution(&instance, &solution).unwrap();
+        assert!(infeasible.is_empty());
+        assert_eq!(problem.layout.placed_items.len(), 2);
+        assert_eq!(problem.item_demand_qtys, vec![1, 1]);
+    }
+
+    #[test]
+    fn forged_fixed_placements_rejected() {
+        let (instance, solution) = instance_and_solution();
+
+        //fixed item moved elsewhere
+        let mut moved = solution.clone();
+        fixed_entry(&mut moved).transformation.translation.1 += 5.0;
+        assert!(import_solution(&instance, &moved).is_err());
+
+        //fixed item rotated
+        let mut rotated = solution.clone();
+        fixed_entry(&mut rotated).transformation.rotation += 0.1;
+        assert!(import_solution(&instance, &rotated).is_err());
+
+        //a regular item marked as fixed
+        let mut marked = solution.clone();
+        marked
+            .layout
+            .placed_items
+            .iter_mut()
+            .for_each(|pi| pi.fixed = true);
+        assert!(import_solution(&instance, &marked).is_err());
+
+        //fixed item missing
+        let mut missing = solution.clone();
+        missing.layout.placed_items.retain(|pi| !pi.fixed);
+        assert!(import_solution(&instance, &missing).is_err());
+    }
+
+    #[test]
+    fn strip_direction_imported() {
+        let import_with = |strip: serde_json::Value| {
+            let mut ext_instance = json!({
+                "name": "direction",
+                "items": [{"id": 0, "demand": 1, "shape": {"type": "rectangle", "data": {"x_min": 0.0, "y_min": 0.0, "width": 10.0, "height": 10.0}}}],
+            });
+            ext_instance
+                .as_object_mut()
+                .unwrap()
+                .extend(strip.as_object().unwrap().clone());
+            let ext_instance: ExtSPInstance = serde_json::from_value(ext_instance).unwrap();
+            import(
+                &Importer::new(cde_config(), None, None, None),
+                &ext_instance,
+            )
+        };
+
+        let horizontal = import_with(json!({"strip_height": 50.0})).unwrap();
+        assert_eq!(horizontal.base_strip.direction, StripDirection::Horizontal);
+
+        let vertical = import_with(json!({"strip_width": 50.0})).unwrap();
+        assert_eq!(vertical.base_strip.direction, StripDirection::Vertical);
+        assert_eq!(vertical.base_strip.fixed_height, 50.0);
+        let (width, height) = vertical.base_strip.dimensions();
+        assert_eq!(width, 50.0);
+        assert!(height > 0.0);
+
+        assert!(import_with(json!({"strip_height": 50.0, "strip_width": 50.0})).is_err());
+        assert!(import_with(json!({})).is_err());
+    }
 }
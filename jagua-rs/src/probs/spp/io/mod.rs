@@ -9,3 +9,6 @@ pub use export::export;
 
 /// Imports a strip packing instance into the library.
 pub use import::import;
+
+/// Imports a strip packing solution back into a problem.
+pub use import::import_solution;
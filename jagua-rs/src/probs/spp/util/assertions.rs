@@ -1,6 +1,7 @@
 use crate::entities::Item;
-use crate::probs::spp::entities::{SPProblem, SPSolution};
+use crate::probs::spp::entities::{SPPlacement, SPProblem, SPSolution};
 use crate::util::assertions::layouts_match;
+use itertools::Itertools;
 
 pub fn problem_matches_solution(spp: &SPProblem, sol: &SPSolution) -> bool {
     let SPSolution {
@@ -22,3 +23,27 @@ pub fn instance_item_ids_correct(items: &[(Item, usize)]) -> bool {
         .enumerate()
         .all(|(i, (item, _qty))| item.id == i)
 }
+
+pub fn fixed_placements_within_demand(
+    items: &[(Item, usize)],
+    fixed_placements: &[SPPlacement],
+) -> bool {
+    let mut remaining = items.iter().map(|(_, qty)| *qty).collect_vec();
+    fixed_placements
+        .iter()
+        .all(|p| match remaining.get_mut(p.item_id) {
+            Some(qty) if *qty > 0 => {
+                *qty -= 1;
+                true
+            }
+            _ => false,
+        })
+}
+
+pub fn min_demands_correct(items: &[(Item, usize)], min_qtys: &[usize]) -> bool {
+    items.len() == min_qtys.len()
+        && items
+            .iter()
+            .zip(min_qtys)
+            .all(|((_, qty), min_qty)| min_qty <= qty)
+}
@@ -29,6 +29,22 @@ pub fn layouts_match(layout: &Layout, layout_snapshot: &LayoutSnapshot) -> bool
     true
 }
 
+pub fn snapshot_hazards_match_placed_items(layout_snapshot: &LayoutSnapshot) -> bool {
+    let hazard_entities: HashSet<HazardEntity> = layout_snapshot
+        .cde_snapshot
+        .dynamic_hazards
+        .iter()
+        .map(|h| h.entity)
+        .filter(|e| matches!(e, HazardEntity::PlacedItem { .. }))
+        .collect();
+    let pi_entities: HashSet<HazardEntity> = layout_snapshot
+        .placed_items
+        .iter()
+        .map(|(pk, pi)| (pk, pi).into())
+        .collect();
+    hazard_entities == pi_entities
+}
+
 pub fn collision_hazards_sorted_correctly(hazards: &[QTHazard]) -> bool {
     let mut partial_hazard_detected = false;
     for hazard in hazards.iter() {
@@ -98,6 +114,12 @@ pub fn layout_qt_matches_fresh_qt(layout: &Layout) -> bool {
         let hazard = Hazard::new((pk, pi).into(), pi.shape.clone(), true);
         fresh_cde.register_hazard(hazard);
     }
+    //custom zones registered at runtime
+    layout
+        .cde()
+        .hazards()
+        .filter(|h| h.dynamic && matches!(h.entity, HazardEntity::Custom { .. }))
+        .for_each(|h| fresh_cde.register_hazard(h.clone()));
 
     qt_nodes_match(Some(&layout.cde().quadtree), Some(&fresh_cde.quadtree))
         && hazards_match(layout.cde().hazards(), fresh_cde.hazards())
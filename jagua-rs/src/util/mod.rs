@@ -4,3 +4,6 @@ mod fpa;
 
 #[doc(inline)]
 pub use fpa::FPA;
+
+#[cfg(test)]
+pub(crate) mod test_util;
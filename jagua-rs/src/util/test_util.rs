@@ -0,0 +1,56 @@
+//! Small hand-made fixtures shared by the unit tests of the library.
+
+use crate::collision_detection::CDEConfig;
+use crate::entities::{Container, Item};
+use crate::geometry::fail_fast::SPSurrogateConfig;
+use crate::geometry::geo_enums::RotationRange;
+use crate::geometry::primitives::{Rect, SPolygon};
+use crate::geometry::shape_modification::{ShapeModifyConfig, ShapeModifyMode};
+use crate::geometry::{DTransformation, OriginalShape};
+
+pub fn cde_config() -> CDEConfig {
+    CDEConfig {
+        quadtree_depth: 3,
+        cd_threshold: 16,
+        item_surrogate_config: SPSurrogateConfig {
+            n_pole_limits: [(100, 0.0), (20, 0.75), (10, 0.90)],
+            n_ff_poles: 2,
+            n_ff_piers: 0,
+        },
+    }
+}
+
+pub fn rect(x_min: f32, y_min: f32, x_max: f32, y_max: f32) -> Rect {
+    Rect::try_new(x_min, y_min, x_max, y_max).unwrap()
+}
+
+/// Axis-aligned rectangle as an unmodified [`OriginalShape`]
+pub fn rect_shape(r: Rect, modify_mode: ShapeModifyMode) -> OriginalShape {
+    OriginalShape {
+        shape: SPolygon::from(r),
+        pre_transform: DTransformation::empty(),
+        modify_mode,
+        modify_config: ShapeModifyConfig::default(),
+    }
+}
+
+/// Empty rectangular container of `width` by `height`, with its origin in the bottom left corner
+pub fn container(id: usize, width: f32, height: f32) -> Container {
+    let shape = rect_shape(rect(0.0, 0.0, width, height), ShapeModifyMode::Deflate);
+    Container::new(id, shape, vec![], vec![], vec![], cde_config()).unwrap()
+}
+
+/// Rectangular item of `width` by `height`, with its reference point in the bottom left corner
+pub fn item(id: usize, width: f32, height: f32) -> Item {
+    let shape = rect_shape(rect(0.0, 0.0, width, height), ShapeModifyMode::Inflate);
+    Item::new(
+        id,
+        shape,
+        RotationRange::Continuous,
+        None,
+        None,
+        vec![],
+        cde_config().item_surrogate_config,
+    )
+    .unwrap()
+}
@@ -0,0 +1,82 @@
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use jagua_rs::probs::bpp::io::ext_repr::ExtBPSolution;
+use jagua_rs::probs::spp::io::ext_repr::ExtSPSolution;
+use lbf::config::LBFConfig;
+use lbf::io::cli::ProblemVariant;
+use lbf::io::{read_bpp_instance, read_spp_instance};
+use lbf::validation::{ValidationReport, validate_bpp, validate_spp};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Validates a solution against the original shapes and constraints of its instance.
+/// Prints a JSON report of all violations to stdout.
+///
+/// Exit codes: 0 if the solution is feasible, 1 if violations were found, 2 if the input could not be processed.
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+struct ValidatorCli {
+    #[arg(short, long, value_name = "FILE")]
+    input_file: PathBuf,
+    /// Solution JSON, either a bare solution or an output file of `lbf` (containing a `solution` key)
+    #[arg(short, long, value_name = "FILE")]
+    solution_file: PathBuf,
+    #[arg(short, long, value_enum, value_name = "TYPE OF PROBLEM")]
+    prob_var: ProblemVariant,
+    /// Minimum required distance between items and any other hazard
+    #[arg(short, long, value_name = "DISTANCE")]
+    min_item_separation: Option<f32>,
+}
+
+fn main() -> ExitCode {
+    let args = ValidatorCli::parse();
+    match validate(&args) {
+        Ok(report) => {
+            if let Err(e) = serde_json::to_writer_pretty(io::stdout(), &report) {
+                eprintln!("error: could not write report: {e}");
+                return ExitCode::from(2);
+            }
+            println!();
+            match report.feasible {
+                true => ExitCode::SUCCESS,
+                false => ExitCode::from(1),
+            }
+        }
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn validate(args: &ValidatorCli) -> Result<ValidationReport> {
+    let cde_config = LBFConfig::default().cde_config;
+    match args.prob_var {
+        ProblemVariant::StripPackingProblem => {
+            let instance = read_spp_instance(&args.input_file)?;
+            let solution: ExtSPSolution = read_solution(&args.solution_file)?;
+            validate_spp(&instance, &solution, cde_config, args.min_item_separation)
+        }
+        ProblemVariant::BinPackingProblem => {
+            let instance = read_bpp_instance(&args.input_file)?;
+            let solution: ExtBPSolution = read_solution(&args.solution_file)?;
+            validate_bpp(&instance, &solution, cde_config, args.min_item_separation)
+        }
+    }
+}
+
+fn read_solution<S: DeserializeOwned>(path: &Path) -> Result<S> {
+    let file = File::open(path).context("could not open solution file")?;
+    let mut json: Value =
+        serde_json::from_reader(BufReader::new(file)).context("solution file is not valid JSON")?;
+    //unwrap the solution from an lbf output file
+    if let Some(solution) = json.get_mut("solution") {
+        json = solution.take();
+    }
+    serde_json::from_value(json).context("not a valid solution for this problem variant")
+}
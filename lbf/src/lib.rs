@@ -6,6 +6,7 @@ pub mod io;
 pub mod opt;
 pub mod samplers;
 pub mod util;
+pub mod validation;
 
 pub static EPOCH: LazyLock<Instant> = LazyLock::new(Instant::now);
 
@@ -1,13 +1,10 @@
-use std::io::{self, Write, Read};
+use std::io::{self, Read};
 use anyhow::{Context, Result};
 use jagua_rs::io::import::Importer;
 use jagua_rs::probs::bpp::io::ext_repr::ExtBPInstance;
-use jagua_rs::probs::spp::io::ext_repr::ExtSPInstance;
-use jagua_rs::probs::{bpp, spp};
+use jagua_rs::probs::bpp;
 use lbf::config::LBFConfig;
-use lbf::io::output::{BPOutput, SPOutput};
 use lbf::opt::lbf_bpp::LBFOptimizerBP;
-use lbf::opt::lbf_spp::LBFOptimizerSP;
 use lbf::EPOCH;
 use log::info;
 use rand::SeedableRng;
@@ -96,7 +93,7 @@ fn solve_bpp(
     };
     
     let instance = bpp::io::import(&importer, &ext_instance)?;
-    let sol = LBFOptimizerBP::new(instance.clone(), config.clone(), rng).solve();
+    let sol = LBFOptimizerBP::new(instance.clone(), config, rng).solve();
     
     let solution = bpp::io::export(&instance, &sol, *EPOCH);
     let solution_value = serde_json::to_value(solution)?;
@@ -1,9 +1,11 @@
+use itertools::Itertools;
 use jagua_rs::Instant;
 
 use crate::ITEM_LIMIT;
 use crate::config::LBFConfig;
+use crate::opt::loss::PrimaryAxis;
 use crate::opt::search::{item_placement_order, search};
-use jagua_rs::collision_detection::hazards::filter::{HazKeyFilter, NoFilter};
+use jagua_rs::collision_detection::hazards::filter::ItemFilter;
 use jagua_rs::entities::{Instance, Item};
 use jagua_rs::probs::bpp::entities::{
     BPInstance, BPLayoutType, BPPlacement, BPProblem, BPSolution,
@@ -39,36 +41,41 @@ impl LBFOptimizerBP {
     pub fn solve(&mut self) -> BPSolution {
         let start = Instant::now();
 
-        'outer: for item_id in item_placement_order(&self.instance) {
-            let item = self.instance.item(item_id);
-            //place all items of this type
-            'inner: while self.problem.item_demand_qtys[item_id] > 0 {
-                //find a position and insert it
-                let placement = search_layouts(
-                    &self.problem,
-                    item,
-                    &self.config,
-                    &mut self.rng,
-                    &mut self.sample_counter,
-                );
+        //first place the mandatory copies of all items, opening bins where needed,
+        //then fill the open bins with the optional copies, without opening any new ones
+        'outer: for mandatory in [true, false] {
+            for item_id in item_placement_order(&self.instance) {
+                let item = self.instance.item(item_id);
+                //place all items of this type
+                'inner: while remaining_qty(&self.problem, item_id, mandatory) > 0 {
+                    //find a position and insert it
+                    let placement = search_layouts(
+                        &self.problem,
+                        item,
+                        &self.config,
+                        &mut self.rng,
+                        &mut self.sample_counter,
+                        mandatory,
+                    );
 
-                match placement {
-                    Some(i_opt) => {
-                        let l_index = self.problem.place_item(i_opt);
-                        info!(
-                            "[LBF] placing item {}/{} with id {} at [{}] in Layout {:?}",
-                            self.problem.item_placed_qtys().sum::<usize>(),
-                            self.instance.total_item_qty(),
-                            i_opt.item_id,
-                            i_opt.d_transf,
-                            l_index
-                        );
-                        #[allow(clippy::absurd_extreme_comparisons)]
-                        if self.problem.item_placed_qtys().sum::<usize>() >= ITEM_LIMIT {
-                            break 'outer;
+                    match placement {
+                        Some(i_opt) => {
+                            let l_index = self.problem.place_item(i_opt);
+                            info!(
+                                "[LBF] placing item {}/{} with id {} at [{}] in Layout {:?}",
+                                self.problem.item_placed_qtys().sum::<usize>(),
+                                self.instance.total_item_qty(),
+                                i_opt.item_id,
+                                i_opt.d_transf,
+                                l_index
+                            );
+                            #[allow(clippy::absurd_extreme_comparisons)]
+                            if self.problem.item_placed_qtys().sum::<usize>() >= ITEM_LIMIT {
+                                break 'outer;
+                            }
                         }
+                        None => break 'inner, // items of this type do not fit anywhere
                     }
-                    None => break 'inner, // items of this type do not fit anywhere
                 }
             }
         }
@@ -100,33 +107,49 @@ fn search_layouts(
     config: &LBFConfig,
     rng: &mut impl Rng,
     sample_counter: &mut usize,
+    open_bins: bool,
 ) -> Option<BPPlacement> {
-    //search all existing layouts and closed bins with remaining stock
-    let open_layouts = problem.layouts.keys().map(BPLayoutType::Open);
+    //search all existing layouts and (if allowed to open them) closed bins with remaining stock,
+    //layouts already containing members of the item's group first
+    let group_lkeys = match problem.instance.item_group(item.id) {
+        Some(group) => problem.group_layout_qtys[group.id].keys().collect_vec(),
+        None => vec![],
+    };
+    let open_layouts = problem
+        .layouts
+        .keys()
+        .sorted_by_key(|lkey| !group_lkeys.contains(lkey))
+        .map(BPLayoutType::Open);
     let bins_with_stock = problem
         .bin_stock_qtys
         .iter()
         .enumerate()
-        .filter_map(|(bin_id, qty)| match *qty > 0 {
+        .filter_map(|(bin_id, qty)| match open_bins && *qty > 0 {
             true => Some(BPLayoutType::Closed { bin_id }),
             false => None,
         });
 
-    //sequential search until a valid placement is found
-    for layout_id in open_layouts.chain(bins_with_stock) {
+    //sequential search until a valid placement is found, skipping layouts which cannot accept the item
+    for layout_id in open_layouts
+        .chain(bins_with_stock)
+        .filter(|l_id| problem.placement_allowed(*l_id, item.id))
+    {
         debug!("searching in layout {layout_id:?}");
         let cde = match layout_id {
             BPLayoutType::Open(lkey) => problem.layouts[lkey].cde(),
             BPLayoutType::Closed { bin_id } => problem.instance.container(bin_id).base_cde.as_ref(),
         };
 
-        let placement = match &item.min_quality {
-            None => search(cde, item, config, rng, sample_counter, &NoFilter),
-            Some(min_quality) => {
-                let filter = HazKeyFilter::from_irrelevant_qzones(*min_quality, &cde.hazards_map);
-                search(cde, item, config, rng, sample_counter, &filter)
-            }
-        };
+        let filter = ItemFilter::new(item, &cde.hazards_map);
+        let placement = search(
+            cde,
+            item,
+            config,
+            rng,
+            sample_counter,
+            &filter,
+            PrimaryAxis::X,
+        );
 
         if let Some((d_transf, _)) = placement {
             return Some(BPPlacement {
@@ -138,3 +161,11 @@ fn search_layouts(
     }
     None
 }
+
+/// Number of copies of an item which remain to be placed, either to meet its minimum demand or its (maximum) demand
+fn remaining_qty(problem: &BPProblem, item_id: usize, mandatory: bool) -> usize {
+    match mandatory {
+        true => problem.item_min_demand_qty(item_id),
+        false => problem.item_demand_qtys[item_id],
+    }
+}
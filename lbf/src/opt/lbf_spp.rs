@@ -2,11 +2,14 @@ use jagua_rs::Instant;
 
 use crate::ITEM_LIMIT;
 use crate::config::LBFConfig;
+use crate::opt::loss::PrimaryAxis;
 use crate::opt::search::{item_placement_order, search};
 use crate::util::assertions::strip_width_is_in_check;
-use jagua_rs::collision_detection::hazards::filter::{HazKeyFilter, NoFilter};
+use jagua_rs::collision_detection::hazards::filter::ItemFilter;
 use jagua_rs::entities::Instance;
-use jagua_rs::probs::spp::entities::{SPInstance, SPPlacement, SPProblem, SPSolution};
+use jagua_rs::probs::spp::entities::{
+    SPInstance, SPPlacement, SPProblem, SPSolution, StripDirection,
+};
 use log::info;
 use rand::prelude::SmallRng;
 use thousands::Separable;
@@ -37,64 +40,63 @@ impl LBFOptimizerSP {
     pub fn solve(&mut self) -> SPSolution {
         let start = Instant::now();
 
-        'outer: for item_id in item_placement_order(&self.instance) {
-            let item = self.instance.item(item_id);
-            //place all items of this type
-            while self.problem.item_demand_qtys[item_id] > 0 {
-                let cde = self.problem.layout.cde();
-                let placement = match &item.min_quality {
-                    None => search(
+        //fill the strip along its direction
+        let axis = match self.problem.strip.direction {
+            StripDirection::Horizontal => PrimaryAxis::X,
+            StripDirection::Vertical => PrimaryAxis::Y,
+        };
+
+        //first place the mandatory copies of all items, extending the strip where needed,
+        //then fill the remaining space with the optional copies, without extending it any further
+        'outer: for mandatory in [true, false] {
+            for item_id in item_placement_order(&self.instance) {
+                let item = self.instance.item(item_id);
+                //place all items of this type
+                while remaining_qty(&self.problem, item_id, mandatory) > 0 {
+                    let cde = self.problem.layout.cde();
+                    let filter = ItemFilter::new(item, &cde.hazards_map);
+                    let placement = search(
                         cde,
                         item,
                         &self.config,
                         &mut self.rng,
                         &mut self.sample_counter,
-                        &NoFilter,
-                    ),
-                    Some(min_quality) => {
-                        let filter =
-                            HazKeyFilter::from_irrelevant_qzones(*min_quality, &cde.hazards_map);
-                        search(
-                            cde,
-                            item,
-                            &self.config,
-                            &mut self.rng,
-                            &mut self.sample_counter,
-                            &filter,
-                        )
-                    }
-                };
+                        &filter,
+                        axis,
+                    );
 
-                match placement {
-                    Some((d_transf, _)) => {
-                        self.problem.place_item(SPPlacement {
-                            item_id: item.id,
-                            d_transf,
-                        });
-                        info!(
-                            "[LBF] placing item {}/{} with id {} at [{}]",
-                            self.problem.layout.placed_items.len(),
-                            self.instance.total_item_qty(),
-                            item.id,
-                            d_transf,
-                        );
-                        #[allow(clippy::absurd_extreme_comparisons)]
-                        if self.problem.layout.placed_items.len() >= ITEM_LIMIT {
-                            break 'outer;
+                    match placement {
+                        Some((d_transf, _)) => {
+                            self.problem.place_item(SPPlacement {
+                                item_id: item.id,
+                                d_transf,
+                            });
+                            info!(
+                                "[LBF] placing item {}/{} with id {} at [{}]",
+                                self.problem.layout.placed_items.len(),
+                                self.instance.total_item_qty(),
+                                item.id,
+                                d_transf,
+                            );
+                            #[allow(clippy::absurd_extreme_comparisons)]
+                            if self.problem.layout.placed_items.len() >= ITEM_LIMIT {
+                                break 'outer;
+                            }
+                        }
+                        None if !mandatory => break, // optional items only fill the existing strip
+                        None => {
+                            // item does not fit anywhere, increase the strip width
+                            self.problem
+                                .change_strip_width(self.problem.strip.width * 1.1);
+                            info!(
+                                "[LBF] no placement found, extended strip by 10% to {:.3}",
+                                self.problem.strip.width
+                            );
+                            assert!(
+                                strip_width_is_in_check(&self.problem),
+                                " strip width is running away, check if all items fit in the height of the strip"
+                            )
                         }
-                    }
-                    None => {
-                        // item does not fit anywhere, increase the strip width
-                        self.problem
-                            .change_strip_width(self.problem.strip.width * 1.1);
-                        info!(
-                            "[LBF] no placement found, extended strip by 10% to {:.3}",
-                            self.problem.strip.width
-                        );
-                        assert!(
-                            strip_width_is_in_check(&self.problem),
-                            " strip width is running away, check if all items fit in the height of the strip"
-                        )
                     }
                 }
             }
@@ -122,3 +124,11 @@ impl LBFOptimizerSP {
         solution
     }
 }
+
+/// Number of copies of an item which remain to be placed, either to meet its minimum demand or its (maximum) demand
+fn remaining_qty(problem: &SPProblem, item_id: usize, mandatory: bool) -> usize {
+    match mandatory {
+        true => problem.item_min_demand_qty(item_id),
+        false => problem.item_demand_qtys[item_id],
+    }
+}
@@ -2,41 +2,58 @@ use std::cmp::Ordering;
 
 use jagua_rs::geometry::primitives::{Rect, SPolygon};
 
-const X_MULTIPLIER: f32 = 10.0;
+const PRIMARY_MULTIPLIER: f32 = 10.0;
+
+/// The axis along which LBF fills the container first.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum PrimaryAxis {
+    /// Left-bottom fill, used for bins and horizontal strips
+    #[default]
+    X,
+    /// Bottom-left fill, used for vertical strips
+    Y,
+}
 
 /// The loss LBF assigned to a placing option.
-/// Weighted sum of the x_max and y_max of the shape, with the horizontal dimension being more important.
+/// Weighted sum of the x_max and y_max of the shape, with the dimension of the [`PrimaryAxis`] being more important.
 /// <br>
-/// A pure lexicographic comparison (always prioritizing x-axis) would lead to undesirable results due to the continuous nature of the values.
+/// A pure lexicographic comparison (always prioritizing one axis) would lead to undesirable results due to the continuous nature of the values.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct LBFLoss {
     x_max: f32,
     y_max: f32,
+    axis: PrimaryAxis,
 }
 
 impl LBFLoss {
-    pub fn from_bbox(bbox: Rect) -> Self {
+    pub fn from_bbox(bbox: Rect, axis: PrimaryAxis) -> Self {
         Self {
             x_max: bbox.x_max,
             y_max: bbox.y_max,
+            axis,
         }
     }
 
-    pub fn from_shape(shape: &SPolygon) -> Self {
-        LBFLoss::from_bbox(shape.bbox)
+    pub fn from_shape(shape: &SPolygon, axis: PrimaryAxis) -> Self {
+        LBFLoss::from_bbox(shape.bbox, axis)
     }
 
     pub fn cost(&self) -> f32 {
-        self.x_max * X_MULTIPLIER + self.y_max
+        match self.axis {
+            PrimaryAxis::X => self.x_max * PRIMARY_MULTIPLIER + self.y_max,
+            PrimaryAxis::Y => self.y_max * PRIMARY_MULTIPLIER + self.x_max,
+        }
     }
 
     /// Tightens a sampling `Rect` to eliminate regions which would never have a lower loss than `self`.
     pub fn tighten_sample_bbox(&self, sample_bbox: Rect) -> Rect {
-        let cost = self.cost();
-        let x_max_bound = cost / X_MULTIPLIER;
+        let bound = self.cost() / PRIMARY_MULTIPLIER;
 
         let mut tightened_bbox = sample_bbox;
-        tightened_bbox.x_max = f32::min(sample_bbox.x_max, x_max_bound);
+        match self.axis {
+            PrimaryAxis::X => tightened_bbox.x_max = f32::min(sample_bbox.x_max, bound),
+            PrimaryAxis::Y => tightened_bbox.y_max = f32::min(sample_bbox.y_max, bound),
+        }
 
         tightened_bbox
     }
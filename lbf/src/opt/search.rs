@@ -1,5 +1,5 @@
 use crate::config::LBFConfig;
-use crate::opt::loss::LBFLoss;
+use crate::opt::loss::{LBFLoss, PrimaryAxis};
 use crate::samplers::ls_sampler::LSSampler;
 use crate::samplers::uniform_rect_sampler::UniformRectSampler;
 use itertools::Itertools;
@@ -14,6 +14,7 @@ use rand::Rng;
 use std::cmp::{Ordering, Reverse};
 
 /// Search the layout (i.e. CDE) for a valid placement of the item, with minimal loss.
+/// The container is filled first along `axis`.
 pub fn search(
     cde: &CDEngine,
     item: &Item,
@@ -21,6 +22,7 @@ pub fn search(
     rng: &mut impl Rng,
     sample_counter: &mut usize,
     filter: &impl HazardFilter,
+    axis: PrimaryAxis,
 ) -> Option<(DTransformation, LBFLoss)> {
     let surrogate = item.shape_cd.surrogate();
     //create a clone of the shape which will we can use to apply the transformations
@@ -44,7 +46,7 @@ pub fn search(
         if !cde.detect_surrogate_collision(surrogate, &transf, filter) {
             //if no collision is detected on the surrogate, apply the transformation
             buffer.transform_from(&item.shape_cd, &transf);
-            let cost = LBFLoss::from_shape(&buffer);
+            let cost = LBFLoss::from_shape(&buffer, axis);
 
             //only validate the sample if it possibly can replace the current best
             let worth_testing = match (best.as_ref(), &cost) {
@@ -54,9 +56,14 @@ pub fn search(
                 (None, _) => true,
             };
 
-            if worth_testing && !cde.detect_poly_collision(&buffer, filter) {
+            if worth_testing
+                && !cde.detect_poly_collision(&buffer, filter)
+                && !cde.detect_quality_region_collision(&item.quality_regions, &transf)
+                && !(item.grain_sensitive
+                    && cde.detect_grain_collision(&buffer, d_transf.rotation()))
+            {
                 //sample is valid and improves on the current best
-                debug!("[UNI: {i}/{uni_sample_budget}] better: {} ", &d_transf);
+                debug!("[UNI: {i}/{uni_sample_budget}] better: {} ", d_transf);
 
                 best = Some((d_transf, cost));
 
@@ -84,15 +91,20 @@ pub fn search(
         let transf = d_transf.compose();
         if !cde.detect_surrogate_collision(surrogate, &transf, filter) {
             buffer.transform_from(&item.shape_cd, &transf);
-            let cost = LBFLoss::from_shape(&buffer);
+            let cost = LBFLoss::from_shape(&buffer, axis);
 
             //only validate the sample if it possibly can replace the current best
             let worth_testing = cost < *best_cost;
 
-            if worth_testing && !cde.detect_poly_collision(&buffer, filter) {
+            if worth_testing
+                && !cde.detect_poly_collision(&buffer, filter)
+                && !cde.detect_quality_region_collision(&item.quality_regions, &transf)
+                && !(item.grain_sensitive
+                    && cde.detect_grain_collision(&buffer, d_transf.rotation()))
+            {
                 //sample is valid and improves on the current best
                 ls_sampler.shift_mean(d_transf);
-                debug!("[LS: {i}/{ls_sample_budget}] better: {}", &d_transf);
+                debug!("[LS: {i}/{ls_sample_budget}] better: {}", d_transf);
                 (*best_sample, *best_cost) = (d_transf, cost);
             }
         }
@@ -0,0 +1,624 @@
+use std::collections::HashSet;
+use std::f32::consts::PI;
+
+use anyhow::{Result, anyhow};
+use itertools::Itertools;
+use jagua_rs::collision_detection::CDEConfig;
+use jagua_rs::collision_detection::hazards::HazardEntity;
+use jagua_rs::entities::{Container, Instance};
+use jagua_rs::geometry::geo_enums::{GeoPosition, RotationRange};
+use jagua_rs::geometry::geo_traits::{DistanceTo, SeparationDistance, Transformable};
+use jagua_rs::geometry::primitives::SPolygon;
+use jagua_rs::geometry::{DTransformation, normalize_rotation};
+use jagua_rs::io::ext_repr::ExtLayout;
+use jagua_rs::io::import::{Importer, InfeasiblePlacement, ext_to_int_transformation};
+use jagua_rs::probs::bpp::entities::GroupPolicy;
+use jagua_rs::probs::bpp::io::ext_repr::{ExtBPInstance, ExtBPSolution};
+use jagua_rs::probs::spp::io::ext_repr::{ExtSPInstance, ExtSPSolution};
+use jagua_rs::probs::{bpp, spp};
+use serde::Serialize;
+
+/// Report of all constraint violations found in a solution
+#[derive(Serialize, Debug, Clone)]
+pub struct ValidationReport {
+    /// Whether the solution does not violate any constraint
+    pub feasible: bool,
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    fn new(violations: Vec<Violation>) -> Self {
+        ValidationReport {
+            feasible: violations.is_empty(),
+            violations,
+        }
+    }
+}
+
+/// A single constraint violation.
+/// Placements are identified by the index of their layout in the solution and their index within that layout.
+/// Distances and depths are expressed in the units of the instance, angles in degrees.
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Violation {
+    /// The item overlaps something it should not, `magnitude` is the estimated penetration depth
+    Overlap {
+        layout: usize,
+        placement: usize,
+        item_id: usize,
+        with: Obstacle,
+        magnitude: f32,
+    },
+    /// The item is closer to something than the minimum separation, `magnitude` is the shortfall in distance
+    InsufficientSeparation {
+        layout: usize,
+        placement: usize,
+        item_id: usize,
+        with: Obstacle,
+        magnitude: f32,
+    },
+    /// A quality region of the item overlaps a zone of insufficient quality
+    QualityRegion {
+        layout: usize,
+        placement: usize,
+        item_id: usize,
+    },
+    /// The item overlaps a grain zone which does not allow its rotation
+    Grain {
+        layout: usize,
+        placement: usize,
+        item_id: usize,
+        rotation: f32,
+    },
+    /// The rotation is not allowed for the item, `magnitude` is the deviation from the closest allowed rotation
+    Rotation {
+        layout: usize,
+        placement: usize,
+        item_id: usize,
+        rotation: f32,
+        magnitude: f32,
+    },
+    /// The item is placed in a bin it is not allowed in
+    BinEligibility {
+        layout: usize,
+        placement: usize,
+        item_id: usize,
+        bin_id: usize,
+    },
+    /// The item is placed fewer times than its minimum demand, or more often than its (maximum) demand
+    Demand {
+        item_id: usize,
+        placed: usize,
+        min_demand: usize,
+        max_demand: usize,
+    },
+    /// The items of a same-bin group are spread over multiple bins, `magnitude` is the number of bins beyond the first
+    ItemGroup {
+        group_id: usize,
+        layouts: Vec<usize>,
+        magnitude: usize,
+    },
+    /// More bins of a type are used than available
+    BinStock {
+        bin_id: usize,
+        used: usize,
+        stock: usize,
+    },
+    /// The items in the layout weigh more than its bin can carry, `magnitude` is the excess weight
+    WeightCapacity {
+        layout: usize,
+        bin_id: usize,
+        weight: f32,
+        capacity: f32,
+        magnitude: f32,
+    },
+}
+
+/// Whatever a placed item is in conflict with
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Obstacle {
+    /// Another item, `placement` is `None` if it is not part of the solution (e.g. a fixed item of the instance)
+    Item {
+        placement: Option<usize>,
+        item_id: usize,
+    },
+    Container,
+    Hole {
+        idx: usize,
+    },
+    QualityZone {
+        quality: usize,
+        idx: usize,
+    },
+    CustomZone {
+        id: usize,
+        tag: usize,
+    },
+}
+
+/// Validates a strip packing solution against the original shapes of the instance.
+/// If `min_item_separation` is defined, the distance between items and any hazard is verified as well.
+pub fn validate_spp(
+    ext_instance: &ExtSPInstance,
+    ext_solution: &ExtSPSolution,
+    cde_config: CDEConfig,
+    min_item_separation: Option<f32>,
+) -> Result<ValidationReport> {
+    let instance = spp::io::import(&original_shape_importer(cde_config, None), ext_instance)?;
+    let mut violations = vec![];
+
+    let mut ext_solution = ext_solution.clone();
+    let demands = instance
+        .items
+        .iter()
+        .enumerate()
+        .map(|(id, (_, qty))| (instance.item_min_qty(id), *qty))
+        .collect_vec();
+    let mut index_maps = drop_excess_placements(
+        &demands,
+        [&mut ext_solution.layout].into_iter(),
+        &mut violations,
+    );
+
+    let (problem, infeasible) = spp::io::import_solution(&instance, &ext_solution)?;
+    let separation = match min_item_separation {
+        Some(sep) => {
+            let importer = original_shape_importer(cde_config, Some(sep));
+            let sep_instance = spp::io::import(&importer, ext_instance)?;
+            let (_, sep_infeasible) = spp::io::import_solution(&sep_instance, &ext_solution)?;
+            Some((sep, sep_infeasible))
+        }
+        None => None,
+    };
+
+    LayoutValidator {
+        l_index: 0,
+        ext_layout: &ext_solution.layout,
+        index_map: &index_maps.remove(0),
+        container: &problem.layout.container,
+        instance: &instance,
+    }
+    .validate(
+        &infeasible.iter().collect_vec(),
+        separation
+            .as_ref()
+            .map(|(sep, inf)| (*sep, inf.iter().collect_vec())),
+        &mut violations,
+    )?;
+
+    Ok(ValidationReport::new(violations))
+}
+
+/// Validates a bin packing solution against the original shapes of the instance.
+/// If `min_item_separation` is defined, the distance between items and any hazard is verified as well.
+pub fn validate_bpp(
+    ext_instance: &ExtBPInstance,
+    ext_solution: &ExtBPSolution,
+    cde_config: CDEConfig,
+    min_item_separation: Option<f32>,
+) -> Result<ValidationReport> {
+    let instance = bpp::io::import(&original_shape_importer(cde_config, None), ext_instance)?;
+    let mut violations = vec![];
+
+    //layouts in excess of the stock of their bin are reported and left out of the further validation
+    let mut ext_solution = ext_solution.clone();
+    let mut used_bins = vec![0; instance.bins.len()];
+    let has_fixed = |ext_layout: &ExtLayout| ext_layout.placed_items.iter().any(|pi| pi.fixed);
+    //layouts containing fixed items are always kept, they claim their bin first
+    for ext_layout in ext_solution.layouts.iter().filter(|l| has_fixed(l)) {
+        if let Some(used) = used_bins.get_mut(ext_layout.container_id as usize) {
+            *used += 1;
+        }
+    }
+    let mut layout_index_map = vec![];
+    let mut kept_layouts = vec![];
+    for (l_index, ext_layout) in ext_solution.layouts.drain(..).enumerate() {
+        //unknown bins are reported by the import
+        if !has_fixed(&ext_layout)
+            && let Some(used) = used_bins.get_mut(ext_layout.container_id as usize)
+        {
+            *used += 1;
+            if *used > instance.bins[ext_layout.container_id as usize].stock {
+                continue;
+            }
+        }
+        layout_index_map.push(l_index);
+        kept_layouts.push(ext_layout);
+    }
+    ext_solution.layouts = kept_layouts;
+    for (bin_id, used) in used_bins.into_iter().enumerate() {
+        let stock = instance.bins[bin_id].stock;
+        if used > stock {
+            violations.push(Violation::BinStock {
+                bin_id,
+                used,
+                stock,
+            });
+        }
+    }
+
+    let demands = instance
+        .items
+        .iter()
+        .enumerate()
+        .map(|(id, (_, qty))| (instance.item_min_qty(id), *qty))
+        .collect_vec();
+    let index_maps =
+        drop_excess_placements(&demands, ext_solution.layouts.iter_mut(), &mut violations);
+
+    //the total weight per layout is reported once, the placements exceeding it are not reported individually
+    for (l_index, ext_layout) in ext_solution.layouts.iter().enumerate() {
+        let bin_id = ext_layout.container_id as usize;
+        let Some(capacity) = instance.bins.get(bin_id).and_then(|b| b.weight_capacity) else {
+            continue;
+        };
+        let weight = ext_layout
+            .placed_items
+            .iter()
+            .filter_map(|pi| instance.items.get(pi.item_id as usize))
+            .map(|(item, _)| item.weight)
+            .sum::<f32>();
+        if weight > capacity {
+            violations.push(Violation::WeightCapacity {
+                layout: layout_index_map[l_index],
+                bin_id,
+                weight,
+                capacity,
+                magnitude: weight - capacity,
+            });
+        }
+    }
+
+    for group in instance
+        .item_groups
+        .iter()
+        .filter(|g| g.policy == GroupPolicy::SameBin)
+    {
+        let layouts = ext_solution
+            .layouts
+            .iter()
+            .enumerate()
+            .filter(|(_, ext_layout)| {
+                ext_layout
+                    .placed_items
+                    .iter()
+                    .any(|pi| group.item_ids.contains(&(pi.item_id as usize)))
+            })
+            .map(|(l_index, _)| layout_index_map[l_index])
+            .collect_vec();
+        if layouts.len() > 1 {
+            violations.push(Violation::ItemGroup {
+                group_id: group.id,
+                magnitude: layouts.len() - 1,
+                layouts,
+            });
+        }
+    }
+
+    let (_, infeasible) = bpp::io::import_solution(&instance, &ext_solution)?;
+    let sep_infeasible = match min_item_separation {
+        Some(sep) => {
+            let importer = original_shape_importer(cde_config, Some(sep));
+            let sep_instance = bpp::io::import(&importer, ext_instance)?;
+            let (_, sep_infeasible) = bpp::io::import_solution(&sep_instance, &ext_solution)?;
+            Some((sep, sep_infeasible))
+        }
+        None => None,
+    };
+
+    for (l_index, ext_layout) in ext_solution.layouts.iter().enumerate() {
+        LayoutValidator {
+            l_index: layout_index_map[l_index],
+            ext_layout,
+            index_map: &index_maps[l_index],
+            container: &instance.bins[ext_layout.container_id as usize].container,
+            instance: &instance,
+        }
+        .validate(
+            &reports_of_layout(&infeasible, l_index),
+            sep_infeasible
+                .as_ref()
+                .map(|(sep, inf)| (*sep, reports_of_layout(inf, l_index))),
+            &mut violations,
+        )?;
+    }
+
+    Ok(ValidationReport::new(violations))
+}
+
+fn reports_of_layout(
+    reports: &[(usize, InfeasiblePlacement)],
+    l_index: usize,
+) -> Vec<&InfeasiblePlacement> {
+    reports
+        .iter()
+        .filter(|(l, _)| *l == l_index)
+        .map(|(_, r)| r)
+        .collect_vec()
+}
+
+/// Importer which leaves the shapes unmodified, apart from the separation offset (if any)
+fn original_shape_importer(cde_config: CDEConfig, min_item_separation: Option<f32>) -> Importer {
+    Importer::new(cde_config, None, min_item_separation, None)
+}
+
+/// Reports items which are placed fewer times than their minimum demand or more often than their (maximum) demand,
+/// given as `(min, max)` pairs. Placements in excess of the demand are removed from the layouts.
+/// Placements marked as fixed are always kept, they are matched against the instance by the import.
+/// Returns for every layout the original index of each remaining placement.
+fn drop_excess_placements<'a>(
+    demands: &[(usize, usize)],
+    layouts: impl Iterator<Item = &'a mut ExtLayout>,
+    violations: &mut Vec<Violation>,
+) -> Vec<Vec<usize>> {
+    let layouts = layouts.collect_vec();
+    let mut placed = vec![0; demands.len()];
+    for ext_pi in layouts
+        .iter()
+        .flat_map(|l| &l.placed_items)
+        .filter(|pi| pi.fixed)
+    {
+        if let Some(n) = placed.get_mut(ext_pi.item_id as usize) {
+            *n += 1;
+        }
+    }
+    let index_maps = layouts
+        .into_iter()
+        .map(|ext_layout| {
+            let mut index_map = vec![];
+            let mut index = 0;
+            ext_layout.placed_items.retain(|ext_pi| {
+                let item_id = ext_pi.item_id as usize;
+                let keep = ext_pi.fixed
+                    || match placed.get_mut(item_id) {
+                        Some(n) => {
+                            *n += 1;
+                            *n <= demands[item_id].1
+                        }
+                        None => true, //unknown items are reported by the import
+                    };
+                if keep {
+                    index_map.push(index);
+                }
+                index += 1;
+                keep
+            });
+            index_map
+        })
+        .collect_vec();
+
+    for (item_id, (placed, &(min_demand, max_demand))) in
+        placed.into_iter().zip(demands).enumerate()
+    {
+        if placed < min_demand || placed > max_demand {
+            violations.push(Violation::Demand {
+                item_id,
+                placed,
+                min_demand,
+                max_demand,
+            });
+        }
+    }
+    index_maps
+}
+
+/// Translates the infeasible placements of a single layout into violations
+struct LayoutValidator<'a, I: Instance> {
+    /// Index of the layout in the original solution
+    l_index: usize,
+    ext_layout: &'a ExtLayout,
+    /// Original index of every placement in `ext_layout`
+    index_map: &'a [usize],
+    container: &'a Container,
+    instance: &'a I,
+}
+
+impl<I: Instance> LayoutValidator<'_, I> {
+    fn validate(
+        &self,
+        infeasible: &[&InfeasiblePlacement],
+        separation: Option<(f32, Vec<&InfeasiblePlacement>)>,
+        violations: &mut Vec<Violation>,
+    ) -> Result<()> {
+        let layout = self.l_index;
+        //pairs of overlapping items are only reported once
+        let mut reported_pairs = HashSet::new();
+
+        for ip in infeasible {
+            let (placement, item_id) = (self.index_map[ip.index], ip.item_id);
+            let (shape, d_transf) = self.placed_shape(ip.index);
+            for entity in ip.colliding.iter() {
+                let Some((with, other_shape)) =
+                    self.obstacle(entity, infeasible, &mut reported_pairs, placement)?
+                else {
+                    continue;
+                };
+                violations.push(Violation::Overlap {
+                    layout,
+                    placement,
+                    item_id,
+                    with,
+                    magnitude: penetration_depth(&shape, &other_shape, entity.scope()),
+                });
+            }
+            if ip.quality_region_violated {
+                violations.push(Violation::QualityRegion {
+                    layout,
+                    placement,
+                    item_id,
+                });
+            }
+            if ip.grain_violated {
+                violations.push(Violation::Grain {
+                    layout,
+                    placement,
+                    item_id,
+                    rotation: d_transf.rotation().to_degrees(),
+                });
+            }
+            if ip.eligibility_violated {
+                violations.push(Violation::BinEligibility {
+                    layout,
+                    placement,
+                    item_id,
+                    bin_id: self.container.id,
+                });
+            }
+            if ip.rotation_violated {
+                let allowed = &self.instance.item(item_id).allowed_rotation;
+                violations.push(Violation::Rotation {
+                    layout,
+                    placement,
+                    item_id,
+                    rotation: d_transf.rotation().to_degrees(),
+                    magnitude: rotation_deviation(allowed, d_transf.rotation()).to_degrees(),
+                });
+            }
+        }
+
+        if let Some((min_separation, sep_infeasible)) = separation {
+            let mut reported_pairs = HashSet::new();
+            for ip in sep_infeasible.iter() {
+                let (placement, item_id) = (self.index_map[ip.index], ip.item_id);
+                let (shape, _) = self.placed_shape(ip.index);
+                let overlapping = infeasible
+                    .iter()
+                    .find(|o| o.index == ip.index)
+                    .map(|o| o.colliding.as_slice())
+                    .unwrap_or_default();
+                //hazards the item overlaps with are already reported as such
+                for entity in ip.colliding.iter().filter(|e| !overlapping.contains(e)) {
+                    let Some((with, other_shape)) =
+                        self.obstacle(entity, &sep_infeasible, &mut reported_pairs, placement)?
+                    else {
+                        continue;
+                    };
+                    violations.push(Violation::InsufficientSeparation {
+                        layout,
+                        placement,
+                        item_id,
+                        with,
+                        magnitude: min_separation - boundary_distance(&shape, &other_shape),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Shape and transformation of the placement at `index` of the layout
+    fn placed_shape(&self, index: usize) -> (SPolygon, DTransformation) {
+        let ext_pi = &self.ext_layout.placed_items[index];
+        let item = self.instance.item(ext_pi.item_id as usize);
+        let d_transf = ext_to_int_transformation(
+            &(&ext_pi.transformation).into(),
+            &item.shape_orig.pre_transform,
+        );
+        (item.shape_cd.transform_clone(&d_transf.compose()), d_transf)
+    }
+
+    /// Describes the entity and returns its shape.
+    /// Colliding items report each other, so other items are looked up in `reports` by their key.
+    /// Returns `None` if the entity is an item whose conflict with `placement` was already reported.
+    /// Fails if the entity is not a hazard of the container.
+    fn obstacle(
+        &self,
+        entity: &HazardEntity,
+        reports: &[&InfeasiblePlacement],
+        reported_pairs: &mut HashSet<(usize, usize)>,
+        placement: usize,
+    ) -> Result<Option<(Obstacle, SPolygon)>> {
+        let obstacle = match *entity {
+            HazardEntity::PlacedItem { id, dt, pk } => {
+                let other = reports
+                    .iter()
+                    .find(|r| r.pk == Some(pk))
+                    .map(|r| self.index_map[r.index]);
+                if let Some(other) = other
+                    && !reported_pairs.insert((placement.min(other), placement.max(other)))
+                {
+                    return Ok(None);
+                }
+                let shape = self
+                    .instance
+                    .item(id)
+                    .shape_cd
+                    .transform_clone(&dt.compose());
+                return Ok(Some((
+                    Obstacle::Item {
+                        placement: other,
+                        item_id: id,
+                    },
+                    shape,
+                )));
+            }
+            HazardEntity::Exterior => Obstacle::Container,
+            HazardEntity::Hole { idx } => Obstacle::Hole { idx },
+            HazardEntity::InferiorQualityZone { quality, idx } => {
+                Obstacle::QualityZone { quality, idx }
+            }
+            HazardEntity::Custom { id, tag, .. } => Obstacle::CustomZone { id, tag },
+        };
+        let hazard = self
+            .container
+            .base_cde
+            .hazards()
+            .find(|h| h.entity == *entity)
+            .ok_or_else(|| {
+                anyhow!(
+                    "{entity:?} is not a hazard of container {}",
+                    self.container.id
+                )
+            })?;
+        Ok(Some((obstacle, hazard.shape.clone())))
+    }
+}
+
+/// Estimates how deep `shape` penetrates the hazard induced by `other`:
+/// the largest distance from a vertex (or the pole of inaccessibility) inside the hazardous region to its boundary.
+fn penetration_depth(shape: &SPolygon, other: &SPolygon, scope: GeoPosition) -> f32 {
+    let depth = |a: &SPolygon, b: &SPolygon, region: GeoPosition| {
+        a.vertices
+            .iter()
+            .chain([&a.poi.center])
+            .map(|v| b.separation_distance(v))
+            .filter(|(pos, _)| *pos == region)
+            .map(|(_, d)| d)
+            .fold(0.0, f32::max)
+    };
+    match scope {
+        GeoPosition::Interior => f32::max(
+            depth(shape, other, GeoPosition::Interior),
+            depth(other, shape, GeoPosition::Interior),
+        ),
+        GeoPosition::Exterior => depth(shape, other, GeoPosition::Exterior),
+    }
+}
+
+/// Minimum distance between the boundaries of two shapes
+fn boundary_distance(a: &SPolygon, b: &SPolygon) -> f32 {
+    let one_way = |a: &SPolygon, b: &SPolygon| {
+        a.vertices
+            .iter()
+            .flat_map(|v| b.edge_iter().map(move |e| e.distance_to(v)))
+            .fold(f32::INFINITY, f32::min)
+    };
+    f32::min(one_way(a, b), one_way(b, a))
+}
+
+/// Angular distance (in radians) between `rotation` and the closest rotation in the range
+fn rotation_deviation(allowed: &RotationRange, rotation: f32) -> f32 {
+    let deviation = |r: f32| {
+        let d = normalize_rotation(rotation - r);
+        d.min(2.0 * PI - d)
+    };
+    match allowed {
+        RotationRange::None => deviation(0.0),
+        RotationRange::Continuous => 0.0,
+        RotationRange::Discrete(rotations) => rotations
+            .iter()
+            .map(|r| deviation(*r))
+            .fold(f32::INFINITY, f32::min),
+    }
+}
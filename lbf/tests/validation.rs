@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    use jagua_rs::probs::spp::io::ext_repr::{ExtSPInstance, ExtSPSolution};
+    use lbf::config::LBFConfig;
+    use lbf::validation::{Obstacle, ValidationReport, Violation, validate_spp};
+    use serde_json::json;
+
+    fn square(size: f32) -> serde_json::Value {
+        json!({"type": "rectangle", "data": {"x_min": 0.0, "y_min": 0.0, "width": size, "height": size}})
+    }
+
+    /// Strip of height 50 with two 10x10 items and a 20x20 item, fixed with its bottom left corner at (60, 25)
+    fn instance() -> ExtSPInstance {
+        serde_json::from_value(json!({
+            "name": "validation",
+            "strip_height": 50.0,
+            "items": [
+                {"id": 0, "demand": 2, "shape": square(10.0)},
+                {"id": 1, "demand": 1, "shape": square(20.0)},
+            ],
+            "fixed_items": [
+                {"item_id": 1, "transformation": {"rotation": 0.0, "translation": [60.0, 25.0]}},
+            ],
+        }))
+        .unwrap()
+    }
+
+    /// Solution with a strip of width 100 and both copies of item 0 with their bottom left corner at the given positions
+    fn solution(pos_a: (f32, f32), pos_b: (f32, f32)) -> ExtSPSolution {
+        let placed = |item_id: u64, (x, y): (f32, f32), fixed: bool| json!({"item_id": item_id, "transformation": {"rotation": 0.0, "translation": [x, y]}, "fixed": fixed});
+        serde_json::from_value(json!({
+            "strip_width": 100.0,
+            "strip_height": 50.0,
+            "layout": {
+                "container_id": 0,
+                "placed_items": [placed(0, pos_a, false), placed(0, pos_b, false), placed(1, (60.0, 25.0), true)],
+            },
+            "density": 0.0,
+            "run_time_sec": 0,
+        }))
+        .unwrap()
+    }
+
+    fn validate(solution: &ExtSPSolution) -> anyhow::Result<ValidationReport> {
+        validate_spp(&instance(), solution, LBFConfig::default().cde_config, None)
+    }
+
+    /// Runs the validator binary on the instance and solution, returns its exit code
+    fn validator_exit_code(name: &str, solution: &ExtSPSolution) -> i32 {
+        let dir =
+            std::env::temp_dir().join(format!("lbf_validation_{}_{name}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |file: &str, value: serde_json::Value| -> PathBuf {
+            let path = dir.join(file);
+            std::fs::write(&path, value.to_string()).unwrap();
+            path
+        };
+        let instance_path = write("instance.json", serde_json::to_value(instance()).unwrap());
+        let solution_path = write("solution.json", serde_json::to_value(solution).unwrap());
+        let status = Command::new(env!("CARGO_BIN_EXE_validator"))
+            .args(["-p", "spp", "-i"])
+            .arg(&instance_path)
+            .arg("-s")
+            .arg(&solution_path)
+            .output()
+            .unwrap()
+            .status;
+        std::fs::remove_dir_all(&dir).unwrap();
+        status.code().unwrap()
+    }
+
+    #[test]
+    fn feasible_solution() {
+        let solution = solution((10.0, 10.0), (30.0, 10.0));
+        let report = validate(&solution).unwrap();
+        assert!(report.feasible, "{:?}", report.violations);
+        assert_eq!(validator_exit_code("feasible", &solution), 0);
+    }
+
+    #[test]
+    fn overlapping_items() {
+        let solution = solution((10.0, 10.0), (15.0, 13.0));
+        let report = validate(&solution).unwrap();
+        assert!(!report.feasible);
+        // the pair is only reported once
+        assert!(
+            matches!(
+                report.violations.as_slice(),
+                [Violation::Overlap {
+                    layout: 0,
+                    item_id: 0,
+                    with: Obstacle::Item { item_id: 0, .. },
+                    magnitude,
+                    ..
+                }] if *magnitude > 0.0
+            ),
+            "{:?}",
+            report.violations
+        );
+        assert_eq!(validator_exit_code("overlap", &solution), 1);
+    }
+
+    #[test]
+    fn item_out_of_bounds() {
+        let solution = solution((10.0, 10.0), (10.0, 48.0));
+        let report = validate(&solution).unwrap();
+        assert!(!report.feasible);
+        assert!(
+            matches!(
+                report.violations.as_slice(),
+                [Violation::Overlap {
+                    layout: 0,
+                    placement: 1,
+                    item_id: 0,
+                    with: Obstacle::Container,
+                    ..
+                }]
+            ),
+            "{:?}",
+            report.violations
+        );
+        assert_eq!(validator_exit_code("out_of_bounds", &solution), 1);
+    }
+
+    #[test]
+    fn demand_not_met() {
+        let mut solution = solution((10.0, 10.0), (30.0, 10.0));
+        solution.layout.placed_items.remove(1);
+        let report = validate(&solution).unwrap();
+        assert!(
+            matches!(
+                report.violations.as_slice(),
+                [Violation::Demand {
+                    item_id: 0,
+                    placed: 1,
+                    min_demand: 2,
+                    max_demand: 2,
+                }]
+            ),
+            "{:?}",
+            report.violations
+        );
+        assert_eq!(validator_exit_code("demand", &solution), 1);
+    }
+
+    #[test]
+    fn forged_fixed_item() {
+        // the fixed item is moved onto one of the other items to hide the overlap
+        let mut solution = solution((10.0, 10.0), (30.0, 10.0));
+        solution.layout.placed_items[2].transformation.translation = (10.0, 10.0);
+        assert!(validate(&solution).is_err());
+        assert_eq!(validator_exit_code("forged_fixed", &solution), 2);
+    }
+}